@@ -20,12 +20,15 @@
  * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
  */
 mod bezier;
+mod svg;
+mod raster;
 use isogeometric_analysis::bezier::BezierTeacup;
 use isogeometric_analysis::bezier::BezierTeaspoon;
 use isogeometric_analysis::bezier::BezierTeapot;
 use self::bezier::AxisRange;
 use gnuplot::AutoOption;
 use structopt::StructOpt;
+use std::path::PathBuf;
 
 #[derive(Debug, StructOpt)]
 #[structopt(name = "isogeometric-analysis-demos", about = "A demo for the isogeometric_analysis crate")]
@@ -55,7 +58,11 @@ struct Opt {
     #[structopt(long, help = "Shows an arc")]
     ratbezier_arc1: bool,
     #[structopt(long, help = "Multiplot")]
-    multiplot: bool
+    multiplot: bool,
+    #[structopt(long, parse(from_os_str), help = "Writes the 2D curve demos to an SVG file instead of using gnuplot")]
+    svg: Option<PathBuf>,
+    #[structopt(long, parse(from_os_str), help = "Renders the 3D patch demos to a PNG or PPM file instead of using gnuplot")]
+    render: Option<PathBuf>
 }
 
 fn main() {
@@ -68,6 +75,10 @@ fn main() {
     }
 
     if opt.bezier1 {
+        if let Some(path) = &opt.svg {
+            svg::write_bezier_curve_demo_1(path);
+            return;
+        }
         bezier::show_bezier_curve_demo_1(opt.multiplot);
         return;
     }
@@ -83,31 +94,55 @@ fn main() {
     }
 
     if opt.ratbezier_circle1 {
+        if let Some(path) = &opt.svg {
+            svg::write_ratbezier_circle_demo(3, 3, path);
+            return;
+        }
         bezier::show_ratbezier_circle_demo(3, 3);
         return;
     }
 
     if opt.ratbezier_circle2 {
+        if let Some(path) = &opt.svg {
+            svg::write_ratbezier_circle_demo(5, 6, path);
+            return;
+        }
         bezier::show_ratbezier_circle_demo(5, 6);
         return;
     }
 
     if opt.ratbezier_circle3 {
+        if let Some(path) = &opt.svg {
+            svg::write_ratbezier_circle_demo(5, 9, path);
+            return;
+        }
         bezier::show_ratbezier_circle_demo(5, 9);
         return;
     }
 
     if opt.ratbezier_circle4 {
+        if let Some(path) = &opt.svg {
+            svg::write_ratbezier_circle_demo(5, 12, path);
+            return;
+        }
         bezier::show_ratbezier_circle_demo(5, 12);
         return;
     }
 
     if opt.ratbezier_arc1 {
+        if let Some(path) = &opt.svg {
+            svg::write_ratbezier_arc_demo(path);
+            return;
+        }
         bezier::show_ratbezier_arc_demo();
         return;
     }
 
     if opt.bezier_teapot {
+        if let Some(path) = &opt.render {
+            raster::render_3d_patches(BezierTeapot::build_patches(), path);
+            return;
+        }
         bezier::show_3d_patches(BezierTeapot::build_patches(), AxisRange {
             min: AutoOption::Fix(-4.),
             max: AutoOption::Fix(4.)
@@ -122,6 +157,10 @@ fn main() {
     }
 
     if opt.bezier_teacup {
+        if let Some(path) = &opt.render {
+            raster::render_3d_patches(BezierTeacup::build_patches(), path);
+            return;
+        }
         bezier::show_3d_patches(BezierTeacup::build_patches(), AxisRange {
             min: AutoOption::Fix(-1.),
             max: AutoOption::Fix(1.)
@@ -136,6 +175,10 @@ fn main() {
     }
 
     if opt.bezier_teaspoon {
+        if let Some(path) = &opt.render {
+            raster::render_3d_patches(BezierTeaspoon::build_patches(), path);
+            return;
+        }
         bezier::show_3d_patches(BezierTeaspoon::build_patches(), AxisRange {
             min: AutoOption::Fix(-1.),
             max: AutoOption::Fix(1.)
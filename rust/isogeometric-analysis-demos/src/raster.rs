@@ -0,0 +1,47 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use isogeometric_analysis::bezier::BezierSurf;
+use isogeometric_analysis::io::{Camera, Rasterizer, RasterOptions};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+///
+/// Renders the patches to a PNG image, or to a PPM image when the extension is ppm.
+/// 
+pub fn render_3d_patches(patches: Vec<BezierSurf<3>>, path: &Path) {
+    let mut raster = Rasterizer::new(RasterOptions {
+        camera: Camera::framing(&patches, -60., 25., 30.),
+        ..RasterOptions::default()
+    });
+    raster.draw_patches(&patches, None);
+    let ppm = path.extension().map(|e| e.eq_ignore_ascii_case("ppm")).unwrap_or(false);
+    let result = File::create(path).and_then(|f| {
+        let mut w = BufWriter::new(f);
+        if ppm { raster.image().write_ppm(&mut w) } else { raster.image().write_png(&mut w) }
+    });
+    match result {
+        Err(e) => { log::warn!("Could not write {}: {}", path.display(), e) },
+        Ok(_v) => {}
+    }
+}
@@ -0,0 +1,79 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use isogeometric_analysis::bezier::{BezierCircle, BezierCurve, BezierCurveDemo1, RatBezierCurve};
+use isogeometric_analysis::core::RealPoint2d;
+use isogeometric_analysis::io::{SvgOptions, SvgWriter};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+fn options() -> SvgOptions {
+    SvgOptions {
+        control_polygon: true,
+        control_points: true,
+        ..SvgOptions::default()
+    }
+}
+
+fn save(svg: &SvgWriter, path: &Path) {
+    match File::create(path).and_then(|f| svg.write(&mut BufWriter::new(f))) {
+        Err(e) => { log::warn!("Could not write {}: {}", path.display(), e) },
+        Ok(_v) => {}
+    }
+}
+
+///
+/// Writes the first demo of a Bezier curve to SVG.
+/// 
+pub fn write_bezier_curve_demo_1(path: &Path) {
+    let mut svg = SvgWriter::new(options());
+    svg.add_bezier(&BezierCurveDemo1::create());
+    save(&svg, path);
+}
+
+///
+/// Writes an arc as a Bezier and as a rational Bezier curve to SVG.
+/// 
+pub fn write_ratbezier_arc_demo(path: &Path) {
+    let cp = vec![
+        RealPoint2d::point2d(0f64, 1f64),
+        RealPoint2d::point2d(1f64, 1f64),
+        RealPoint2d::point2d(1f64, 0f64)
+    ];
+    let mut svg = SvgWriter::new(options());
+    svg.add_bezier(&BezierCurve::create(cp.clone()));
+    svg.add_rat_bezier(&RatBezierCurve::<2, 3>::create(cp, vec![1f64, 2f64.sqrt()/2.0, 1f64]));
+    save(&svg, path);
+}
+
+///
+/// Writes a circle made of rational Bezier curves to SVG.
+/// 
+pub fn write_ratbezier_circle_demo(r: u32, segments: u32, path: &Path) {
+    let mut svg = SvgWriter::new(options());
+    svg.add_circle(&BezierCircle {
+        radius: r,
+        segments
+    });
+    save(&svg, path);
+}
@@ -21,12 +21,15 @@
  */
 
 use crate::core::fact;
-use crate::core::{RealPoint, RealPoint1d, RealPoint2d, RealPoint3d, p2};
+use crate::core::{RealPoint, RealPoint1d, RealPoint2d, RealPoint3d, RealRange, p1, p2};
+use crate::core::{ParametricCurve, ParametricSurface, rational_derivatives};
 use crate::core::Mapping;
+use crate::core::BoundingBox;
 use std::f64::consts::PI;
 use num::traits::Pow;
 use array2d::Array2D;
 use embed_doc_image::embed_doc_image;
+use std::collections::HashMap;
 
 ///
 /// Represents a Bernstein polynomial. Bernstein polynomials are functions
@@ -192,7 +195,7 @@ impl<const SIZE: usize> BezierCurve<SIZE> {
     pub fn evaluate_direct_cubic<'a>(&self, xi: &RealPoint1d, output: &'a mut RealPoint<SIZE>) -> &'a mut RealPoint<SIZE> {
         *output = self.p[0]*Pow::<f64>::pow(1. - xi.x(), 3.);
         *output += self.p[1]*3.*xi.x()*Pow::<f64>::pow(1. - xi.x(), 2.);
-        *output += self.p[2]*2.*Pow::<f64>::pow(xi.x(), 2.)*(1. - xi.x());
+        *output += self.p[2]*3.*Pow::<f64>::pow(xi.x(), 2.)*(1. - xi.x());
         *output += self.p[3]*Pow::<f64>::pow(xi.x(), 3.);
         output
     }
@@ -205,6 +208,34 @@ impl<const SIZE: usize> BezierCurve<SIZE> {
         (self.p.len() - 1) as u32
     }
 
+    ///
+    /// Returns the bounding box of the control points, which contains the curve.
+    ///
+    pub fn bounding_box(&self) -> BoundingBox<SIZE> {
+        BoundingBox::from_points(&self.p)
+    }
+
+    ///
+    /// Splits the curve at xi with De Casteljau's algorithm. The two curves are
+    /// reparametrized on [0, 1].
+    ///
+    pub fn split(&self, xi: f64) -> (BezierCurve<SIZE>, BezierCurve<SIZE>) {
+        let (left, right) = split_points(&self.p, xi);
+        (BezierCurve::create(left), BezierCurve::create(right))
+    }
+
+    ///
+    /// Returns the hodograph, i.e. the derivative of the curve, as a Bezier curve of one
+    /// degree less. The derivative of a constant curve is the zero curve.
+    ///
+    pub fn derivative(&self) -> BezierCurve<SIZE> {
+        let n = self.p.len() - 1;
+        if n == 0 {
+            return BezierCurve::create(vec![RealPoint::<SIZE>::origin()]);
+        }
+        BezierCurve::create(self.p.windows(2).map(|w| (w[1] - w[0])*(n as f64)).collect())
+    }
+
     ///
     /// Returns a copy of the control points.
     ///
@@ -214,9 +245,48 @@ impl<const SIZE: usize> BezierCurve<SIZE> {
     }
 }
 
+impl<const SIZE: usize> ParametricCurve<SIZE> for BezierCurve<SIZE> {
+    fn domain(&self) -> RealRange {
+        RealRange { a: 0f64, b: 1f64 }
+    }
+
+    ///
+    /// Evaluates the curve and its hodographs in xi.
+    ///
+    fn derivatives(&self, xi: f64, n: usize) -> Vec<RealPoint<SIZE>> {
+        let mut ret = vec![self.evaluate_de_casteljau(&p1(xi))];
+        let mut hodograph = self.derivative();
+        for _ in 0..n {
+            ret.push(hodograph.evaluate_de_casteljau(&p1(xi)));
+            hodograph = hodograph.derivative();
+        }
+        ret
+    }
+}
+
+///
+/// Splits the control polygon of a Bezier curve at xi, returning the control polygons of
+/// the two halves.
+///
+pub(crate) fn split_points<const S: usize>(p: &[RealPoint<S>], xi: f64) -> (Vec<RealPoint<S>>, Vec<RealPoint<S>>) {
+    let mut q = p.to_vec();
+    let mut left = vec![q[0]];
+    let mut right = vec![q[q.len() - 1]];
+    for k in 1..q.len() {
+        for i in 0..(q.len() - k) {
+            q[i] = q[i]*(1. - xi) + q[i + 1]*xi;
+        }
+        left.push(q[0]);
+        right.push(q[q.len() - k - 1]);
+    }
+    right.reverse();
+    (left, right)
+}
+
 ///
 /// Represents a Bezier surface.
 /// 
+#[derive(Clone)]
 pub struct BezierSurf<const S: usize> {
     pub data: Array2D<RealPoint<S>>
 }
@@ -233,6 +303,83 @@ impl<const S: usize> BezierSurf<S> {
     ///
     #[inline(always)]
     pub fn degree_eta(&self) -> u32 { (self.data.row_len() - 1) as u32 }
+
+    ///
+    /// Returns the bounding box of the control points, which contains the surface.
+    ///
+    pub fn bounding_box(&self) -> BoundingBox<S> {
+        BoundingBox::from_points(self.data.elements_row_major_iter())
+    }
+
+    ///
+    /// Splits the surface at xi along the Xi axis.
+    ///
+    pub fn split_xi(&self, xi: f64) -> (BezierSurf<S>, BezierSurf<S>) {
+        let (left, right): (Vec<_>, Vec<_>) = self.data.as_columns().iter().map(|c| split_points(c, xi)).unzip();
+        (BezierSurf { data: Array2D::from_columns(&left) }, BezierSurf { data: Array2D::from_columns(&right) })
+    }
+
+    ///
+    /// Splits the surface at eta along the Eta axis.
+    ///
+    pub fn split_eta(&self, eta: f64) -> (BezierSurf<S>, BezierSurf<S>) {
+        let (left, right): (Vec<_>, Vec<_>) = self.data.as_rows().iter().map(|r| split_points(r, eta)).unzip();
+        (BezierSurf { data: Array2D::from_rows(&left) }, BezierSurf { data: Array2D::from_rows(&right) })
+    }
+
+    ///
+    /// Returns the partial derivative along Xi as a Bezier surface.
+    ///
+    pub fn derivative_xi(&self) -> BezierSurf<S> {
+        let n = self.degree_xi() as usize;
+        let rows = self.data.as_rows();
+        if n == 0 {
+            return BezierSurf { data: Array2D::filled_with(RealPoint::<S>::origin(), 1, rows[0].len()) };
+        }
+        let rows = rows.windows(2)
+            .map(|w| w[0].iter().zip(w[1].iter()).map(|(a, b)| (*b - *a)*(n as f64)).collect())
+            .collect::<Vec<Vec<RealPoint<S>>>>();
+        BezierSurf { data: Array2D::from_rows(&rows) }
+    }
+
+    ///
+    /// Returns the partial derivative along Eta as a Bezier surface.
+    ///
+    pub fn derivative_eta(&self) -> BezierSurf<S> {
+        let m = self.degree_eta() as usize;
+        let rows = self.data.as_rows();
+        if m == 0 {
+            return BezierSurf { data: Array2D::filled_with(RealPoint::<S>::origin(), rows.len(), 1) };
+        }
+        let rows = rows.iter()
+            .map(|r| r.windows(2).map(|w| (w[1] - w[0])*(m as f64)).collect())
+            .collect::<Vec<Vec<RealPoint<S>>>>();
+        BezierSurf { data: Array2D::from_rows(&rows) }
+    }
+}
+
+impl ParametricSurface for BezierSurf<3> {
+    fn domain(&self) -> (RealRange, RealRange) {
+        (RealRange { a: 0f64, b: 1f64 }, RealRange { a: 0f64, b: 1f64 })
+    }
+
+    ///
+    /// Evaluates the partial hodographs of the patch in (xi, eta).
+    ///
+    fn derivatives(&self, xi: f64, eta: f64, n: usize) -> Vec<Vec<RealPoint3d>> {
+        let input = p2(xi, eta);
+        let mut ret = vec![vec![RealPoint3d::origin(); n + 1]; n + 1];
+        let mut dxi = self.clone();
+        for (k, row) in ret.iter_mut().enumerate() {
+            let mut d = dxi.clone();
+            for value in row.iter_mut().take(n + 1 - k) {
+                d.evaluate_fill(&input, value);
+                d = d.derivative_eta();
+            }
+            dxi = dxi.derivative_xi();
+        }
+        ret
+    }
 }
 
 impl<const S: usize> Mapping<f64, f64, 2, S> for BezierSurf<S> {
@@ -283,8 +430,9 @@ impl<const S: usize> BezierSurf<S> {
         let eta = RealPoint1d::point1d(input.y());
         let xi = RealPoint1d::point1d(input.x());
         let mut q = Vec::<RealPoint<S>>::new();
+        let rows = self.data.as_rows();
         for i in 0..=n {
-            let bezcurve1 = BezierCurve::<S>::create(self.data.as_rows()[i as usize].clone());
+            let bezcurve1 = BezierCurve::<S>::create(rows[i as usize].clone());
             q.push(bezcurve1.evaluate_de_casteljau(&eta));
         }
 
@@ -331,6 +479,14 @@ impl<const S: usize, const H: usize> RatBezierCurve<S, H> {
             bez: BezierCurve::<H>::create(pw.clone())
         }
     }
+
+    ///
+    /// Returns the bounding box of the control points, which contains the curve when the
+    /// weights are positive.
+    ///
+    pub fn bounding_box(&self) -> BoundingBox<S> {
+        BoundingBox::from_points(&self.p)
+    }
 }
 
 impl<const S: usize, const H: usize> Mapping<f64, f64, 1, S> for RatBezierCurve<S, H> {
@@ -347,6 +503,23 @@ impl<const S: usize, const H: usize> Mapping<f64, f64, 1, S> for RatBezierCurve<
     }
 }
 
+impl<const S: usize, const H: usize> ParametricCurve<S> for RatBezierCurve<S, H> {
+    fn domain(&self) -> RealRange {
+        RealRange { a: 0f64, b: 1f64 }
+    }
+
+    ///
+    /// Differentiates the curve in homogeneous coordinates, where it is polynomial, and
+    /// applies the quotient rule.
+    ///
+    fn derivatives(&self, xi: f64, n: usize) -> Vec<RealPoint<S>> {
+        let dw = self.bez.derivatives(xi, n);
+        let a = dw.iter().map(|p| p.resize::<S>()).collect::<Vec<RealPoint<S>>>();
+        let w = dw.iter().map(|p| p.value(H - 1)).collect::<Vec<f64>>();
+        rational_derivatives(&a, &w)
+    }
+}
+
 ///
 /// Struct to compute a circle with rational bezier curves. This is an example of a circle
 /// with radius 2 and with 6 segments:
@@ -451,6 +624,11 @@ impl BezierCurveDemo1 {
     }
 }
 
+///
+/// Index table of bicubic patches and the vertices it refers to.
+///
+pub type IndexedVertices = (Vec<[usize; 16]>, Vec<[f64; 3]>);
+
 pub struct BezierFactory {}
 
 impl BezierFactory {
@@ -473,10 +651,91 @@ impl BezierFactory {
 
 		patches
     }
+
+    ///
+    /// Inverse of from_indexed_vertices: collects the control points of bicubic patches
+    /// into a vertex array and builds the index table. Control points closer than
+    /// tolerance are stored once and shared. Indices are 1-based, as in the Utah
+    /// format. Returns None if a patch is not bicubic.
+    ///
+    pub fn to_indexed_vertices(patches: &[BezierSurf<3>], tolerance: f64) -> Option<IndexedVertices> {
+        let mut welder = VertexWelder::new(tolerance);
+        let mut patch_array = Vec::<[usize; 16]>::new();
+        for patch in patches {
+            if patch.data.num_rows() != 4 || patch.data.num_columns() != 4 {
+                log::warn!("Only bicubic patches can be indexed");
+                return None;
+            }
+            let mut indices = [0usize; 16];
+            for (j, p) in patch.data.elements_row_major_iter().enumerate() {
+                indices[j] = welder.index_of(p) + 1;
+            }
+            patch_array.push(indices);
+        }
+
+        let vertex_array = welder.vertices.iter().map(|v| [v.x(), v.y(), v.z()]).collect();
+        Some((patch_array, vertex_array))
+    }
+}
+
+///
+/// Merges points closer than a tolerance by hashing them on a grid with cells as
+/// large as the tolerance.
+///
+pub(crate) struct VertexWelder {
+    pub vertices: Vec<RealPoint3d>,
+    tolerance: f64,
+    cells: HashMap<[i64; 3], Vec<usize>>
+}
+
+impl VertexWelder {
+    pub fn new(tolerance: f64) -> VertexWelder {
+        VertexWelder {
+            vertices: Vec::new(),
+            tolerance,
+            cells: HashMap::new()
+        }
+    }
+
+    ///
+    /// Returns the 0-based index of the vertex closer than the tolerance to p, adding
+    /// p if there is none.
+    ///
+    pub fn index_of(&mut self, p: &RealPoint3d) -> usize {
+        let cell = self.cell(p);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    if let Some(candidates) = self.cells.get(&[cell[0] + dx, cell[1] + dy, cell[2] + dz]) {
+                        for &i in candidates {
+                            if self.vertices[i].dist(p) <= self.tolerance {
+                                return i;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let i = self.vertices.len();
+        self.vertices.push(*p);
+        self.cells.entry(cell).or_default().push(i);
+        i
+    }
+
+    fn cell(&self, p: &RealPoint3d) -> [i64; 3] {
+        let size = if self.tolerance > 0f64 { self.tolerance } else { f64::EPSILON };
+        [
+            (p.x()/size).floor() as i64,
+            (p.y()/size).floor() as i64,
+            (p.z()/size).floor() as i64
+        ]
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::bezier::BezierCurve;
     use crate::bezier::RatBezierCurve;
     use crate::bezier::BezierCurveDemo1;
     use crate::bezier::BezierCircle;
@@ -501,6 +760,26 @@ mod tests {
             demo1.evaluate_fill(&input, &mut output);
             assert_approx_eq!(RealPoint2d, output, outputrat);
         }
+
+        // The direct evaluation of low degrees must agree with De Casteljau's.
+        for n in 1..demo1.control_points().len() {
+            let bez = BezierCurve::create(demo1.control_points()[0..=n].to_vec());
+            for i in 0..=10 {
+                let input = RealPoint1d::point1d((i as f64)/10f64);
+                assert_approx_eq!(RealPoint2d, bez.evaluate(&input), bez.evaluate_de_casteljau(&input), epsilon = 1E-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_split() {
+        let demo1 = BezierCurveDemo1::create();
+        let (left, right) = demo1.split(0.3);
+        for i in 0..=10 {
+            let t = (i as f64)/10.;
+            assert_approx_eq!(RealPoint2d, left.evaluate(&RealPoint1d::point1d(t)), demo1.evaluate(&RealPoint1d::point1d(0.3*t)), epsilon = 1E-12);
+            assert_approx_eq!(RealPoint2d, right.evaluate(&RealPoint1d::point1d(t)), demo1.evaluate(&RealPoint1d::point1d(0.3 + 0.7*t)), epsilon = 1E-12);
+        }
     }
 
     #[test]
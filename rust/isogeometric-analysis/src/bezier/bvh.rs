@@ -0,0 +1,290 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::bezier::{BezierSurf, Projection, Ray, RayHit};
+use crate::core::{BoundingBox, RealPoint3d};
+
+///
+/// Maximum number of patches in a leaf.
+///
+const LEAF_PATCHES: usize = 4;
+
+enum BvhContent {
+    Leaf { start: usize, end: usize },
+    Inner { left: usize, right: usize }
+}
+
+struct BvhNode {
+    bbox: BoundingBox<3>,
+    content: BvhContent
+}
+
+///
+/// Bounding volume hierarchy over a collection of patches. Each patch is bounded by the
+/// box of its control points; the nodes split the patches at the median of their box
+/// centers along the longest axis.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use isogeometric_analysis::bezier::{BezierTeapot, PatchBvh, Ray};
+/// use isogeometric_analysis::core::p3;
+/// let bvh = PatchBvh::new(BezierTeapot::build_patches());
+/// let hit = bvh.first_hit(&Ray { origin: p3(0., -10., 1.5), direction: p3(0., 1., 0.) });
+/// assert!(hit.is_some());
+/// ```
+/// 
+pub struct PatchBvh {
+    patches: Vec<BezierSurf<3>>,
+    boxes: Vec<BoundingBox<3>>,
+    nodes: Vec<BvhNode>,
+    order: Vec<usize>
+}
+
+impl PatchBvh {
+    pub fn new(patches: Vec<BezierSurf<3>>) -> PatchBvh {
+        let boxes = patches.iter().map(|p| p.bounding_box()).collect::<Vec<BoundingBox<3>>>();
+        let mut bvh = PatchBvh {
+            order: (0..patches.len()).collect(),
+            patches,
+            boxes,
+            nodes: Vec::new()
+        };
+        if !bvh.patches.is_empty() {
+            bvh.build(0, bvh.patches.len());
+        }
+        bvh
+    }
+
+    ///
+    /// Builds the node of the patches in order[start..end], returning its index.
+    /// 
+    fn build(&mut self, start: usize, end: usize) -> usize {
+        let bbox = self.order[start..end].iter().fold(BoundingBox::empty(), |b, i| b.merge(&self.boxes[*i]));
+        let index = self.nodes.len();
+        self.nodes.push(BvhNode { bbox, content: BvhContent::Leaf { start, end } });
+        if end - start <= LEAF_PATCHES {
+            return index;
+        }
+        let centers = BoundingBox::from_points(&self.order[start..end].iter().map(|i| self.boxes[*i].center()).collect::<Vec<RealPoint3d>>());
+        let axis = centers.longest_axis();
+        let boxes = &self.boxes;
+        self.order[start..end].sort_by(|a, b| boxes[*a].center().value(axis).total_cmp(&boxes[*b].center().value(axis)));
+        let mid = (start + end)/2;
+        let left = self.build(start, mid);
+        let right = self.build(mid, end);
+        self.nodes[index].content = BvhContent::Inner { left, right };
+        index
+    }
+
+    pub fn patches(&self) -> &[BezierSurf<3>] {
+        &self.patches
+    }
+
+    ///
+    /// Returns the box containing all the patches.
+    /// 
+    pub fn bounding_box(&self) -> BoundingBox<3> {
+        self.nodes.first().map(|n| n.bbox).unwrap_or_else(BoundingBox::empty)
+    }
+
+    ///
+    /// Visits the leaves whose node boxes are accepted by the filter, calling visit with
+    /// the index of each patch in them.
+    /// 
+    fn traverse(&self, mut accept: impl FnMut(&BoundingBox<3>) -> bool, mut visit: impl FnMut(usize)) {
+        let mut stack = if self.nodes.is_empty() { vec![] } else { vec![0] };
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if !accept(&node.bbox) {
+                continue;
+            }
+            match node.content {
+                BvhContent::Leaf { start, end } => self.order[start..end].iter().for_each(|i| visit(*i)),
+                BvhContent::Inner { left, right } => {
+                    stack.push(right);
+                    stack.push(left);
+                }
+            }
+        }
+    }
+
+    ///
+    /// Returns the sorted indices of the patches whose boxes intersect b.
+    /// 
+    pub fn query_box(&self, b: &BoundingBox<3>) -> Vec<usize> {
+        let mut found = Vec::new();
+        self.traverse(|node| node.intersects(b), |i| if self.boxes[i].intersects(b) { found.push(i) });
+        found.sort_unstable();
+        found
+    }
+
+    ///
+    /// Returns the pairs of patches (i, j), i < j, whose boxes are closer than tolerance.
+    /// 
+    pub fn overlapping_pairs(&self, tolerance: f64) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for (i, b) in self.boxes.iter().enumerate() {
+            pairs.extend(self.query_box(&b.inflated(tolerance)).into_iter().filter(|j| *j > i).map(|j| (i, j)));
+        }
+        pairs
+    }
+
+    ///
+    /// Returns all the intersections of the ray with the patches, sorted by the ray parameter.
+    /// 
+    pub fn intersect_ray(&self, ray: &Ray) -> Vec<(usize, RayHit)> {
+        let mut hits = Vec::new();
+        self.traverse(|node| node.clip_ray(&ray.origin, &ray.direction).is_some(), |i| {
+            if self.boxes[i].clip_ray(&ray.origin, &ray.direction).is_some() {
+                hits.extend(self.patches[i].intersect_ray(ray).into_iter().map(|h| (i, h)));
+            }
+        });
+        hits.sort_by(|a, b| a.1.t.total_cmp(&b.1.t));
+        hits
+    }
+
+    ///
+    /// Returns the first intersection of the ray with the patches, skipping the nodes
+    /// farther than the best hit found so far.
+    /// 
+    pub fn first_hit(&self, ray: &Ray) -> Option<(usize, RayHit)> {
+        let mut best: Option<(usize, RayHit)> = None;
+        let mut stack = if self.nodes.is_empty() { vec![] } else { vec![0] };
+        let closer = |b: &BoundingBox<3>, best: &Option<(usize, RayHit)>|
+            b.clip_ray(&ray.origin, &ray.direction).map(|(t0, _)| best.map(|h| t0 <= h.1.t).unwrap_or(true)).unwrap_or(false);
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if !closer(&node.bbox, &best) {
+                continue;
+            }
+            match node.content {
+                BvhContent::Leaf { start, end } => {
+                    for i in &self.order[start..end] {
+                        if !closer(&self.boxes[*i], &best) {
+                            continue;
+                        }
+                        if let Some(hit) = self.patches[*i].intersect_ray(ray).first() {
+                            if best.map(|b| hit.t < b.1.t).unwrap_or(true) {
+                                best = Some((*i, *hit));
+                            }
+                        }
+                    }
+                },
+                BvhContent::Inner { left, right } => {
+                    stack.push(right);
+                    stack.push(left);
+                }
+            }
+        }
+        best
+    }
+
+    ///
+    /// Returns the point of the patches closest to p, visiting first the nodes nearer to
+    /// it and skipping those farther than the best point found so far.
+    /// 
+    pub fn closest_point(&self, p: &RealPoint3d) -> Option<(usize, Projection<2, 3>)> {
+        let mut best: Option<(usize, Projection<2, 3>)> = None;
+        let mut stack = if self.nodes.is_empty() { vec![] } else { vec![0] };
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if best.map(|b| node.bbox.distance(p) >= b.1.distance).unwrap_or(false) {
+                continue;
+            }
+            match node.content {
+                BvhContent::Leaf { start, end } => {
+                    for i in &self.order[start..end] {
+                        if best.map(|b| self.boxes[*i].distance(p) >= b.1.distance).unwrap_or(false) {
+                            continue;
+                        }
+                        let proj = self.patches[*i].project(p);
+                        if best.map(|b| proj.distance < b.1.distance).unwrap_or(true) {
+                            best = Some((*i, proj));
+                        }
+                    }
+                },
+                BvhContent::Inner { left, right } => {
+                    // The nearer child is visited first.
+                    if self.nodes[left].bbox.distance(p) <= self.nodes[right].bbox.distance(p) {
+                        stack.push(right);
+                        stack.push(left);
+                    }
+                    else {
+                        stack.push(left);
+                        stack.push(right);
+                    }
+                }
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bezier::{BezierTeapot, PatchBvh, Ray};
+    use crate::core::{BoundingBox, p3};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_queries() {
+        let bvh = PatchBvh::new(BezierTeapot::build_patches());
+        let patches = bvh.patches();
+        assert_eq!(bvh.bounding_box(), patches.iter().fold(BoundingBox::empty(), |b, p| b.merge(&p.bounding_box())));
+
+        // Box queries against a linear scan.
+        for k in 0..10 {
+            let c = p3(-3. + 0.6*(k as f64), 0.2*(k as f64) - 1., 0.3*(k as f64));
+            let b = BoundingBox::from_points(&[c, c + p3(0.5, 0.5, 0.5)]);
+            let linear = (0..patches.len()).filter(|i| patches[*i].bounding_box().intersects(&b)).collect::<Vec<usize>>();
+            assert_eq!(bvh.query_box(&b), linear);
+        }
+        let pairs = bvh.overlapping_pairs(0.);
+        assert!(pairs.contains(&(0, 1)));
+        assert!(pairs.iter().all(|(i, j)| i < j && patches[*i].bounding_box().intersects(&patches[*j].bounding_box())));
+
+        // Rays against a linear scan.
+        for k in 0..6 {
+            let ray = Ray { origin: p3(-10., -0.7 + 0.3*(k as f64), 0.4 + 0.4*(k as f64)), direction: p3(1., 0.05, 0.) };
+            let linear = patches.iter().flat_map(|p| p.intersect_ray(&ray)).map(|h| h.t).fold(f64::MAX, f64::min);
+            let all = bvh.intersect_ray(&ray);
+            match bvh.first_hit(&ray) {
+                Some((_, hit)) => {
+                    assert_approx_eq!(f64, hit.t, linear);
+                    assert_approx_eq!(f64, all[0].1.t, linear);
+                },
+                None => assert!(linear == f64::MAX && all.is_empty())
+            }
+        }
+
+        // Closest points against a linear scan.
+        for k in 0..4 {
+            let p = p3(2. - (k as f64), 0.5*(k as f64), 0.8*(k as f64));
+            let linear = patches.iter().map(|s| s.project(&p).distance).fold(f64::MAX, f64::min);
+            let (i, proj) = bvh.closest_point(&p).unwrap();
+            assert_approx_eq!(f64, proj.distance, linear, epsilon = 1E-12);
+            assert_approx_eq!(f64, patches[i].project(&p).distance, linear, epsilon = 1E-12);
+        }
+        assert!(PatchBvh::new(Vec::new()).closest_point(&p3(0., 0., 0.)).is_none());
+    }
+}
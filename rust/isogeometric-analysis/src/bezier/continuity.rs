@@ -0,0 +1,376 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::bezier::{BezierSurf, PatchModel};
+use crate::bezier::model::canonical_edge;
+use crate::core::{ParametricSurface, RealPoint3d};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+///
+/// Boundary of a bicubic patch. Control points of an indexed patch are stored row by row
+/// with rows along Xi, so XiMin is made of indices 0..4 and EtaMin of 0, 4, 8, 12.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PatchEdge {
+    XiMin,
+    XiMax,
+    EtaMin,
+    EtaMax
+}
+
+impl PatchEdge {
+    pub const ALL: [PatchEdge; 4] = [PatchEdge::XiMin, PatchEdge::XiMax, PatchEdge::EtaMin, PatchEdge::EtaMax];
+
+    ///
+    /// Returns the positions in the 16-element index array of the control points on the
+    /// edge, in the order of increasing parameter.
+    /// 
+    pub fn indices(&self) -> [usize; 4] {
+        match self {
+            PatchEdge::XiMin => [0, 1, 2, 3],
+            PatchEdge::XiMax => [12, 13, 14, 15],
+            PatchEdge::EtaMin => [0, 4, 8, 12],
+            PatchEdge::EtaMax => [3, 7, 11, 15]
+        }
+    }
+
+    ///
+    /// Returns the parameters (xi, eta) of the point at t ∈ [0, 1] along the edge.
+    /// 
+    pub fn param(&self, t: f64) -> (f64, f64) {
+        match self {
+            PatchEdge::XiMin => (0f64, t),
+            PatchEdge::XiMax => (1f64, t),
+            PatchEdge::EtaMin => (t, 0f64),
+            PatchEdge::EtaMax => (t, 1f64)
+        }
+    }
+
+    ///
+    /// Returns true if increasing parameters run counterclockwise around the domain, the
+    /// boundary orientation induced by the normal ∂ξ × ∂η.
+    /// 
+    fn is_counterclockwise(&self) -> bool {
+        matches!(self, PatchEdge::XiMax | PatchEdge::EtaMin)
+    }
+}
+
+///
+/// Order of continuity across an edge, from the weakest.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Continuity {
+    /// The patches do not meet along the edge.
+    Gap,
+    /// The patches meet but their tangent planes differ.
+    C0,
+    /// Tangent planes agree but the cross-boundary derivatives do not.
+    G1,
+    /// Cross-boundary derivatives agree.
+    C1
+}
+
+impl Display for Continuity {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Continuity::Gap => write!(f, "gap"),
+            Continuity::C0 => write!(f, "C0"),
+            Continuity::G1 => write!(f, "G1"),
+            Continuity::C1 => write!(f, "C1")
+        }
+    }
+}
+
+///
+/// Tolerances of the continuity analysis.
+/// 
+#[derive(Debug, Clone)]
+pub struct ContinuityOptions {
+    /// Maximum distance between points of the two patches along an edge for C0.
+    pub position: f64,
+    /// Maximum angle between the tangent planes for G1, in radians.
+    pub angle: f64,
+    /// Maximum mismatch of the cross-boundary derivatives for C1, relative to their size.
+    pub derivative: f64,
+    /// Number of points sampled along each edge.
+    pub samples: usize
+}
+
+impl Default for ContinuityOptions {
+    fn default() -> Self {
+        ContinuityOptions {
+            position: 1E-9,
+            angle: 1E-6,
+            derivative: 1E-6,
+            samples: 16
+        }
+    }
+}
+
+///
+/// Continuity across an edge shared by two patches. When reversed is true, the edge is
+/// traversed in opposite directions by the two patches.
+/// 
+#[derive(Debug, Clone)]
+pub struct EdgeContinuity {
+    pub patches: (usize, usize),
+    pub edges: (PatchEdge, PatchEdge),
+    pub reversed: bool,
+    /// Maximum distance between the two patches along the edge.
+    pub gap: f64,
+    /// Maximum angle between the normals, oriented consistently across the edge, in
+    /// radians: it reaches π where a patch folds back onto the other.
+    pub angle: f64,
+    /// Maximum relative mismatch of the cross-boundary derivatives.
+    pub derivative_gap: f64,
+    pub continuity: Continuity
+}
+
+///
+/// Edge of a patch collapsed into a single point, e.g. at the poles of the teapot lid
+/// and bottom. Normals are not defined there and such edges are not paired.
+/// 
+#[derive(Debug, Clone)]
+pub struct DegenerateEdge {
+    pub patch: usize,
+    pub edge: PatchEdge,
+    pub point: RealPoint3d
+}
+
+///
+/// Adjacency and continuity of bicubic patches given by an index table, as in the Utah
+/// format: two patches are adjacent when an edge of one refers to the same four vertices
+/// as an edge of the other, in either order. Vertices closer than the position tolerance
+/// count as the same vertex even when the table indexes them separately.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use isogeometric_analysis::bezier::{ContinuityOptions, ContinuityReport, TEAPOT_PACTHES, TEAPOT_VERTICES};
+/// let report = ContinuityReport::from_indexed_vertices(&TEAPOT_PACTHES, &TEAPOT_VERTICES, &ContinuityOptions::default()).unwrap();
+/// for e in &report.edges {
+///     println!("{} {:?} - {} {:?}: {}", e.patches.0, e.edges.0, e.patches.1, e.edges.1, e.continuity);
+/// }
+/// ```
+/// 
+#[derive(Debug, Clone)]
+pub struct ContinuityReport {
+    pub edges: Vec<EdgeContinuity>,
+    pub degenerate: Vec<DegenerateEdge>,
+    /// Edges that are neither shared nor degenerate, i.e. the open boundary of the model.
+    pub boundary: Vec<(usize, PatchEdge)>
+}
+
+impl ContinuityReport {
+    ///
+    /// Analyses the patches of an index table with 1-based indices. Returns None if an
+    /// index is out of range.
+    /// 
+    pub fn from_indexed_vertices(patch_array: &[[usize; 16]], vertex_array: &[[f64; 3]], options: &ContinuityOptions) -> Option<ContinuityReport> {
+        let model = PatchModel::from_indexed_vertices(patch_array, vertex_array, options.position)?;
+        Some(ContinuityReport::from_model(&model, options))
+    }
+
+    ///
+    /// Analyses the patches of a model.
+    /// 
+    pub fn from_model(model: &PatchModel, options: &ContinuityOptions) -> ContinuityReport {
+        let patches = model.surfaces();
+
+        // Group the edges by their vertices, keyed in the smaller of the two orders.
+        let mut shared = HashMap::<[usize; 4], Vec<(usize, PatchEdge, bool)>>::new();
+        let mut order = Vec::<[usize; 4]>::new();
+        let mut degenerate = Vec::new();
+        for i in 0..model.patches.len() {
+            for edge in PatchEdge::ALL {
+                let vertices = model.edge(i, edge);
+                if model.is_degenerate(i, edge) {
+                    degenerate.push(DegenerateEdge { patch: i, edge, point: model.vertices[vertices[0]] });
+                    continue;
+                }
+                let (key, reversed) = canonical_edge(vertices);
+                let entry = shared.entry(key).or_default();
+                if entry.is_empty() {
+                    order.push(key);
+                }
+                entry.push((i, edge, reversed));
+            }
+        }
+
+        let mut edges = Vec::new();
+        let mut boundary = Vec::new();
+        for key in order {
+            let group = &shared[&key];
+            if group.len() == 1 {
+                boundary.push((group[0].0, group[0].1));
+                continue;
+            }
+            for (k, a) in group.iter().enumerate() {
+                for b in &group[k + 1..] {
+                    edges.push(analyze(&patches, *a, *b, options));
+                }
+            }
+        }
+        ContinuityReport { edges, degenerate, boundary }
+    }
+
+    ///
+    /// Returns the weakest continuity over the shared edges, if any.
+    /// 
+    pub fn weakest(&self) -> Option<Continuity> {
+        self.edges.iter().map(|e| e.continuity).min()
+    }
+}
+
+///
+/// Samples the shared edge at interior parameters, where normals exist even next to a
+/// degenerate edge, and compares positions, tangent planes and cross-boundary derivatives.
+/// 
+fn analyze(patches: &[BezierSurf<3>], a: (usize, PatchEdge, bool), b: (usize, PatchEdge, bool), options: &ContinuityOptions) -> EdgeContinuity {
+    let reversed = a.2 != b.2;
+    // Consistently oriented patches run along the shared edge in opposite directions.
+    let orientation = if (a.1.is_counterclockwise() == b.1.is_counterclockwise()) == reversed { 1f64 } else { -1f64 };
+    let (mut gap, mut angle, mut derivative_gap) = (0f64, 0f64, 0f64);
+    for s in 0..options.samples {
+        let t = (s as f64 + 0.5)/(options.samples as f64);
+        let (pa, na, da) = sample(&patches[a.0], a.1, t);
+        let (pb, nb, db) = sample(&patches[b.0], b.1, if reversed { 1f64 - t } else { t });
+        gap = gap.max(pa.dist(&pb));
+        angle = angle.max(na.cross(&nb).norm().atan2(orientation*na.dot(&nb)));
+        // Outward derivatives of C1 patches are opposite.
+        let scale = da.norm().max(db.norm());
+        if scale > 0f64 {
+            derivative_gap = derivative_gap.max((da + db).norm()/scale);
+        }
+    }
+
+    let continuity = if gap > options.position {
+        Continuity::Gap
+    }
+    else if angle > options.angle {
+        Continuity::C0
+    }
+    else if derivative_gap > options.derivative {
+        Continuity::G1
+    }
+    else {
+        Continuity::C1
+    };
+    EdgeContinuity {
+        patches: (a.0, b.0),
+        edges: (a.1, b.1),
+        reversed,
+        gap,
+        angle,
+        derivative_gap,
+        continuity
+    }
+}
+
+///
+/// Returns the point, the normal and the outward cross-boundary derivative at t along an
+/// edge.
+/// 
+fn sample(patch: &BezierSurf<3>, edge: PatchEdge, t: f64) -> (RealPoint3d, RealPoint3d, RealPoint3d) {
+    let (xi, eta) = edge.param(t);
+    let d = patch.derivatives(xi, eta, 1);
+    let outward = match edge {
+        PatchEdge::XiMin => d[1][0]*(-1f64),
+        PatchEdge::XiMax => d[1][0],
+        PatchEdge::EtaMin => d[0][1]*(-1f64),
+        PatchEdge::EtaMax => d[0][1]
+    };
+    (d[0][0], d[1][0].cross(&d[0][1]), outward)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bezier::{Continuity, ContinuityOptions, ContinuityReport, PatchEdge, TEAPOT_PACTHES, TEAPOT_VERTICES};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_teapot() {
+        let report = ContinuityReport::from_indexed_vertices(&TEAPOT_PACTHES, &TEAPOT_VERTICES, &ContinuityOptions::default()).unwrap();
+        assert_eq!(report.edges.len(), 52);
+        assert_eq!(report.boundary.len(), 16);
+        assert_eq!(report.weakest(), Some(Continuity::C0));
+        assert!(report.edges.iter().all(|e| e.gap == 0.));
+
+        // Lid top and bottom are collapsed into poles.
+        assert_eq!(report.degenerate.len(), 8);
+        assert!(report.degenerate.iter().all(|d| d.edge == PatchEdge::XiMin && d.point.x() == 0. && d.point.y() == 0.));
+
+        // The rim meets the body with a slight crease; the bottom, indexed separately,
+        // joins the body smoothly.
+        let rim = report.edges.iter().find(|e| e.patches == (0, 4)).unwrap();
+        assert_eq!(rim.continuity, Continuity::C0);
+        assert_approx_eq!(f64, rim.angle, 1.483E-4, epsilon = 1E-7);
+        let bottom = report.edges.iter().find(|e| e.patches == (8, 31)).unwrap();
+        assert_eq!(bottom.continuity, Continuity::C1);
+        assert!(bottom.reversed);
+        assert_eq!(report.edges.iter().filter(|e| e.continuity == Continuity::C1).count(), 46);
+    }
+
+    #[test]
+    fn test_classification() {
+        // Three bilinear-like patches in a row: flat, then folded, then detached.
+        let mut vertices = Vec::new();
+        for i in 0..13 {
+            for j in 0..4 {
+                let x = i as f64/3.;
+                let z = if i > 6 { (x - 2.)*0.5 } else { 0. };
+                vertices.push([x, j as f64/3., z]);
+            }
+        }
+        vertices.extend((0..16).map(|k| [5. + (k/4) as f64, (k%4) as f64/3., 1.]));
+        let patch = |first: usize| {
+            let mut p = [0usize; 16];
+            for (k, v) in p.iter_mut().enumerate() {
+                *v = first + k + 1;
+            }
+            p
+        };
+        let patches = [patch(0), patch(12), patch(24), patch(52)];
+        let report = ContinuityReport::from_indexed_vertices(&patches, &vertices, &ContinuityOptions::default()).unwrap();
+        assert_eq!(report.edges.len(), 2);
+        assert_eq!(report.edges[0].continuity, Continuity::C1);
+        assert_eq!(report.edges[1].continuity, Continuity::C0);
+        assert_approx_eq!(f64, report.edges[1].angle, 0.5f64.atan(), epsilon = 1E-12);
+        assert_eq!(report.boundary.len(), 4*4 - 2*2);
+        assert!(report.degenerate.is_empty());
+    }
+
+    #[test]
+    fn test_cusp() {
+        // A flat patch and one folded back over it, slightly lifted.
+        let mut vertices = (0..16).map(|k| [(k/4) as f64/3., (k%4) as f64/3., 0.]).collect::<Vec<[f64; 3]>>();
+        vertices.extend((4..16).map(|k| [1. - (k/4) as f64/3., (k%4) as f64/3., 1E-7*(k/4) as f64]));
+        // The folded patch starts from the last row of the flat one.
+        let flat = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+        let folded = flat.map(|i| i + 12);
+        let report = ContinuityReport::from_indexed_vertices(&[flat, folded], &vertices, &ContinuityOptions::default()).unwrap();
+        assert_eq!(report.edges.len(), 1);
+        assert_approx_eq!(f64, report.edges[0].angle, std::f64::consts::PI, epsilon = 1E-6);
+        assert_eq!(report.edges[0].continuity, Continuity::C0);
+    }
+}
@@ -0,0 +1,495 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::bezier::{BezierCurve, BezierSurf, RatBezierCurve};
+use crate::bezier::bezier::split_points;
+use crate::core::{BoundingBox, Mapping, RealPoint2d, RealPoint3d, p1, p2, p3};
+use std::f64::consts::PI;
+
+///
+/// Size of a subdivided piece, relative to the whole, below which Newton iteration is used.
+///
+const LEAF_SIZE: f64 = 1E-3;
+
+///
+/// Maximum subdivision depth.
+///
+const MAX_DEPTH: u32 = 40;
+
+///
+/// Maximum number of Newton iterations.
+///
+const MAX_ITERATIONS: usize = 30;
+
+///
+/// Parametric distance below which two solutions are the same.
+///
+const MERGE_TOLERANCE: f64 = 1E-7;
+
+///
+/// Intersection of two curves: parameters on the first and on the second curve, and the
+/// point itself.
+/// 
+#[derive(Debug, Clone, Copy)]
+pub struct CurveIntersection {
+    pub t1: f64,
+    pub t2: f64,
+    pub point: RealPoint2d
+}
+
+///
+/// Ray starting at origin and travelling along direction.
+/// 
+#[derive(Debug, Clone, Copy)]
+pub struct Ray {
+    pub origin: RealPoint3d,
+    pub direction: RealPoint3d
+}
+
+///
+/// Intersection of a ray with a surface: the ray parameter t, the surface parameters xi and
+/// eta, and the point itself.
+/// 
+#[derive(Debug, Clone, Copy)]
+pub struct RayHit {
+    pub t: f64,
+    pub xi: f64,
+    pub eta: f64,
+    pub point: RealPoint3d
+}
+
+///
+/// Planar curve in homogeneous coordinates with its derivative, for Newton iteration.
+/// 
+struct HomCurve {
+    bez: BezierCurve<3>,
+    derivative: BezierCurve<3>
+}
+
+impl HomCurve {
+    fn from_rational(c: &RatBezierCurve<2, 3>) -> HomCurve {
+        let bez = BezierCurve::create(c.pw.clone());
+        let derivative = bez.derivative();
+        HomCurve { bez, derivative }
+    }
+
+    ///
+    /// Evaluates the curve and its first derivative in t.
+    /// 
+    fn evaluate(&self, t: f64) -> (RealPoint2d, RealPoint2d) {
+        let a0 = self.bez.evaluate(&p1(t));
+        let a1 = self.derivative.evaluate(&p1(t));
+        let c = p2(a0.x(), a0.y())*(1./a0.z());
+        (c, (p2(a1.x(), a1.y()) - c*a1.z())*(1./a0.z()))
+    }
+}
+
+///
+/// Homogeneous and cartesian control points of a piece of a curve, over the given part of
+/// its parametric interval. For positive weights the piece lies in the convex hull of the
+/// cartesian control points.
+/// 
+#[derive(Clone)]
+struct Piece {
+    pw: Vec<RealPoint3d>,
+    p: Vec<RealPoint2d>,
+    a: f64,
+    b: f64
+}
+
+impl Piece {
+    fn new(pw: Vec<RealPoint3d>, a: f64, b: f64) -> Piece {
+        let p = pw.iter().map(|p| p.to_cartesian::<2>()).collect();
+        Piece { pw, p, a, b }
+    }
+
+    fn whole(c: &HomCurve) -> Piece {
+        Piece::new(c.bez.p.clone(), 0., 1.)
+    }
+
+    fn bbox(&self) -> BoundingBox<2> {
+        BoundingBox::from_points(&self.p)
+    }
+
+    ///
+    /// Tests whether the other piece lies outside the fat line of this one, the strip
+    /// bounded by the lines parallel to the chord through the extreme control points.
+    /// 
+    fn separates(&self, other: &Piece, tolerance: f64) -> bool {
+        let (first, last) = (self.p[0], self.p[self.p.len() - 1]);
+        let chord = last - first;
+        if chord.norm() <= tolerance {
+            return false;
+        }
+        let n = p2(-chord.y(), chord.x())*(1./chord.norm());
+        let distance = |q: &RealPoint2d| (*q - first).dot(&n);
+        let (d_min, d_max) = self.p.iter().map(distance).fold((0f64, 0f64), |(a, b), d| (a.min(d), b.max(d)));
+        other.p.iter().all(|q| distance(q) > d_max + tolerance) || other.p.iter().all(|q| distance(q) < d_min - tolerance)
+    }
+
+    fn split(&self) -> (Piece, Piece) {
+        let (left, right) = split_points(&self.pw, 0.5);
+        let m = 0.5*(self.a + self.b);
+        (Piece::new(left, self.a, m), Piece::new(right, m, self.b))
+    }
+}
+
+///
+/// Tests whether the convex hull of the points contains the origin, or comes closer to it
+/// than tolerance. The origin is outside when the directions of the points span less
+/// than a half-turn, so that a line through the origin leaves them all on one side.
+/// 
+fn hull_contains_origin(points: &[RealPoint2d], tolerance: f64) -> bool {
+    if points.iter().any(|q| q.norm() <= tolerance) {
+        return true;
+    }
+    let mut angles = points.iter().map(|q| q.y().atan2(q.x())).collect::<Vec<f64>>();
+    angles.sort_by(|a, b| a.total_cmp(b));
+    // Largest angular gap between consecutive directions, around the full turn too.
+    let mut gap = (angles[0] + 2.*PI - angles[angles.len() - 1], angles[angles.len() - 1]);
+    for w in angles.windows(2) {
+        if w[1] - w[0] > gap.0 {
+            gap = (w[1] - w[0], w[0]);
+        }
+    }
+    if gap.0 <= PI {
+        return true;
+    }
+    // The points lie within a half-turn around the direction opposite the gap.
+    let middle = gap.1 + 0.5*gap.0 + PI;
+    let n = p2(middle.cos(), middle.sin());
+    points.iter().map(|q| q.dot(&n)).fold(f64::INFINITY, f64::min) <= tolerance
+}
+
+///
+/// Finds all the intersections of two planar curves by subdivision: pieces whose bounding
+/// boxes do not overlap, or either of which lies outside the fat line of the other, are
+/// discarded, the others are split until they are small enough for Newton iteration.
+/// Overlapping curves produce a sample of their common points.
+/// 
+fn intersect_curves(c1: &HomCurve, c2: &HomCurve) -> Vec<CurveIntersection> {
+    let (w1, w2) = (Piece::whole(c1), Piece::whole(c2));
+    let size = w1.bbox().diagonal().max(w2.bbox().diagonal()).max(f64::MIN_POSITIVE);
+    let tolerance = 1E-12*size;
+    let mut stack = vec![(w1, w2, 0)];
+    let mut found = Vec::<CurveIntersection>::new();
+    while let Some((a, b, depth)) = stack.pop() {
+        let (ba, bb) = (a.bbox(), b.bbox());
+        if !ba.inflated(tolerance).intersects(&bb) || a.separates(&b, tolerance) || b.separates(&a, tolerance) {
+            continue;
+        }
+        let (da, db) = (ba.diagonal(), bb.diagonal());
+        if depth >= MAX_DEPTH || da.max(db) < LEAF_SIZE*size {
+            if let Some(i) = refine_curves(c1, c2, 0.5*(a.a + a.b), 0.5*(b.a + b.b), tolerance) {
+                if !found.iter().any(|f| (f.t1 - i.t1).abs() < MERGE_TOLERANCE && (f.t2 - i.t2).abs() < MERGE_TOLERANCE) {
+                    found.push(i);
+                }
+            }
+            continue;
+        }
+        if da >= db {
+            let (l, r) = a.split();
+            stack.push((l, b.clone(), depth + 1));
+            stack.push((r, b, depth + 1));
+        }
+        else {
+            let (l, r) = b.split();
+            stack.push((a.clone(), l, depth + 1));
+            stack.push((a, r, depth + 1));
+        }
+    }
+    found.sort_by(|x, y| x.t1.total_cmp(&y.t1));
+    found
+}
+
+///
+/// Solves c1(s) = c2(t) with Newton iteration.
+/// 
+fn refine_curves(c1: &HomCurve, c2: &HomCurve, mut s: f64, mut t: f64, tolerance: f64) -> Option<CurveIntersection> {
+    for _ in 0..MAX_ITERATIONS {
+        let ((p, dp), (q, dq)) = (c1.evaluate(s), c2.evaluate(t));
+        let f = p - q;
+        if f.norm() <= tolerance {
+            break;
+        }
+        let det = -dp.x()*dq.y() + dq.x()*dp.y();
+        if det.abs() < f64::MIN_POSITIVE {
+            break;
+        }
+        // Solves [dp, -dq] (ds, dt) = -f.
+        let ds = (f.x()*dq.y() - dq.x()*f.y())/det;
+        let dt = (f.x()*dp.y() - dp.x()*f.y())/det;
+        s = (s + ds).clamp(0., 1.);
+        t = (t + dt).clamp(0., 1.);
+        if ds.abs() + dt.abs() < 1E-15 {
+            break;
+        }
+    }
+    let (p, q) = (c1.evaluate(s).0, c2.evaluate(t).0);
+    if p.dist(&q) <= 1E-9*(1. + p.norm()) {
+        Some(CurveIntersection { t1: s, t2: t, point: (p + q)*0.5 })
+    }
+    else {
+        None
+    }
+}
+
+impl BezierCurve<2> {
+    ///
+    /// Returns all the intersections with another planar Bezier curve, sorted by the
+    /// parameter on this curve.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use isogeometric_analysis::bezier::BezierCurve;
+    /// use isogeometric_analysis::core::p2;
+    /// let c1 = BezierCurve::create(vec![p2(0., 0.), p2(1., 2.), p2(2., 0.)]);
+    /// let c2 = BezierCurve::create(vec![p2(0., 0.5), p2(2., 0.5)]);
+    /// assert_eq!(c1.intersect(&c2).len(), 2);
+    /// ```
+    /// 
+    pub fn intersect(&self, other: &BezierCurve<2>) -> Vec<CurveIntersection> {
+        let unit = |c: &BezierCurve<2>| RatBezierCurve::<2, 3>::create(c.p.clone(), vec![1.; c.p.len()]);
+        unit(self).intersect(&unit(other))
+    }
+}
+
+impl RatBezierCurve<2, 3> {
+    ///
+    /// Returns all the intersections with another planar rational Bezier curve, sorted by
+    /// the parameter on this curve. Weights must be positive.
+    /// 
+    pub fn intersect(&self, other: &RatBezierCurve<2, 3>) -> Vec<CurveIntersection> {
+        intersect_curves(&HomCurve::from_rational(self), &HomCurve::from_rational(other))
+    }
+}
+
+impl Ray {
+    pub fn at(&self, t: f64) -> RealPoint3d {
+        self.origin + self.direction*t
+    }
+}
+
+impl BezierSurf<3> {
+    ///
+    /// Returns the intersections with the ray, sorted by the ray parameter t >= 0. The
+    /// patch is subdivided while the ray crosses both the bounding box and the convex hull
+    /// of the control points of the pieces, then the hits are refined with Newton
+    /// iteration.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use isogeometric_analysis::bezier::{BezierTeapot, Ray};
+    /// use isogeometric_analysis::core::p3;
+    /// let ray = Ray { origin: p3(0., -10., 1.5), direction: p3(0., 1., 0.) };
+    /// let hits = BezierTeapot::build_patches().iter().flat_map(|p| p.intersect_ray(&ray)).count();
+    /// assert!(hits >= 2);
+    /// ```
+    /// 
+    pub fn intersect_ray(&self, ray: &Ray) -> Vec<RayHit> {
+        let size = self.bounding_box().diagonal().max(f64::MIN_POSITIVE);
+        let tolerance = 1E-12*size;
+        let su = self.derivative_xi();
+        let sv = self.derivative_eta();
+
+        // Two planes meeting along the ray, onto whose normals the control points are
+        // projected: the ray meets the hull of a piece if the projection contains the origin.
+        let d = ray.direction;
+        let axis = if d.x().abs() <= d.y().abs() && d.x().abs() <= d.z().abs() { p3(1., 0., 0.) }
+            else if d.y().abs() <= d.z().abs() { p3(0., 1., 0.) } else { p3(0., 0., 1.) };
+        let n1 = d.cross(&axis);
+        let n1 = n1*(1./n1.norm());
+        let n2 = d.cross(&n1);
+        let n2 = n2*(1./n2.norm());
+        let project = |s: &BezierSurf<3>| s.data.elements_row_major_iter()
+            .map(|p| p2((*p - ray.origin).dot(&n1), (*p - ray.origin).dot(&n2)))
+            .collect::<Vec<RealPoint2d>>();
+
+        let mut found = Vec::<RayHit>::new();
+        let mut stack = vec![(self.clone(), (0f64, 1f64), (0f64, 1f64), 0)];
+        while let Some((piece, u, v, depth)) = stack.pop() {
+            let bbox = piece.bounding_box();
+            if bbox.inflated(tolerance).clip_ray(&ray.origin, &ray.direction).is_none() || !hull_contains_origin(&project(&piece), tolerance) {
+                continue;
+            }
+            if depth >= MAX_DEPTH || bbox.diagonal() < LEAF_SIZE*size {
+                if let Some(hit) = self.refine_ray(&su, &sv, ray, 0.5*(u.0 + u.1), 0.5*(v.0 + v.1), tolerance) {
+                    if !found.iter().any(|f| (f.xi - hit.xi).abs() < MERGE_TOLERANCE && (f.eta - hit.eta).abs() < MERGE_TOLERANCE) {
+                        found.push(hit);
+                    }
+                }
+                continue;
+            }
+            // Splits along the longer side of the control net.
+            let rows = piece.data.as_rows();
+            let len_xi = rows[0][0].dist(&rows[rows.len() - 1][0]) + rows[0][rows[0].len() - 1].dist(&rows[rows.len() - 1][rows[0].len() - 1]);
+            let len_eta = rows[0][0].dist(&rows[0][rows[0].len() - 1]) + rows[rows.len() - 1][0].dist(&rows[rows.len() - 1][rows[0].len() - 1]);
+            if len_xi >= len_eta {
+                let (l, r) = piece.split_xi(0.5);
+                let m = 0.5*(u.0 + u.1);
+                stack.push((l, (u.0, m), v, depth + 1));
+                stack.push((r, (m, u.1), v, depth + 1));
+            }
+            else {
+                let (l, r) = piece.split_eta(0.5);
+                let m = 0.5*(v.0 + v.1);
+                stack.push((l, u, (v.0, m), depth + 1));
+                stack.push((r, u, (m, v.1), depth + 1));
+            }
+        }
+        found.sort_by(|a, b| a.t.total_cmp(&b.t));
+        found
+    }
+
+    ///
+    /// Solves S(xi, eta) = o + t d with Newton iteration.
+    /// 
+    fn refine_ray(&self, su: &BezierSurf<3>, sv: &BezierSurf<3>, ray: &Ray, mut u: f64, mut v: f64, tolerance: f64) -> Option<RayHit> {
+        let det3 = |a: &RealPoint3d, b: &RealPoint3d, c: &RealPoint3d| a.dot(&b.cross(c));
+        let d = ray.direction*-1.;
+        let project = |p: &RealPoint3d| (*p - ray.origin).dot(&ray.direction)/ray.direction.dot(&ray.direction);
+        let mut t = project(&self.evaluate(&p2(u, v)));
+        for _ in 0..MAX_ITERATIONS {
+            let xi = p2(u, v);
+            let f = self.evaluate(&xi) - ray.at(t);
+            if f.norm() <= tolerance {
+                break;
+            }
+            let (a, b) = (su.evaluate(&xi), sv.evaluate(&xi));
+            let det = det3(&a, &b, &d);
+            if det.abs() < f64::MIN_POSITIVE {
+                break;
+            }
+            // Cramer's rule for [a, b, d] (du, dv, dt) = -f.
+            let g = f*-1.;
+            let du = det3(&g, &b, &d)/det;
+            let dv = det3(&a, &g, &d)/det;
+            let dt = det3(&a, &b, &g)/det;
+            u = (u + du).clamp(0., 1.);
+            v = (v + dv).clamp(0., 1.);
+            t += dt;
+            if du.abs() + dv.abs() < 1E-15 {
+                break;
+            }
+        }
+        let point = self.evaluate(&p2(u, v));
+        let t = project(&point);
+        if t >= 0. && point.dist(&ray.at(t)) <= 1E-9*(1. + point.norm()) {
+            Some(RayHit { t, xi: u, eta: v, point })
+        }
+        else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bezier::{BezierCircle, BezierCurve, BezierSurf, BezierTeapot, RatBezierCurve, Ray};
+    use crate::bezier::intersection::{HomCurve, Piece, hull_contains_origin};
+    use crate::core::{Mapping, RealPoint2d, RealPoint3d, p1, p2, p3};
+    use array2d::Array2D;
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_curves() {
+        // A parabola and a line.
+        let c1 = BezierCurve::create(vec![p2(0., 0.), p2(1., 2.), p2(2., 0.)]);
+        let c2 = BezierCurve::create(vec![p2(0., 0.5), p2(2., 0.5)]);
+        let hits = c1.intersect(&c2);
+        assert_eq!(hits.len(), 2);
+        let s = 0.5 - 0.5f64.sqrt()*0.5;
+        assert_approx_eq!(f64, hits[0].t1, s, epsilon = 1E-12);
+        assert_approx_eq!(f64, hits[1].t1, 1. - s, epsilon = 1E-12);
+        for h in &hits {
+            assert_approx_eq!(RealPoint2d, c1.evaluate(&p1(h.t1)), c2.evaluate(&p1(h.t2)), epsilon = 1E-12);
+        }
+
+        // Intersections at the ends and none at all.
+        let c3 = BezierCurve::create(vec![p2(2., 0.), p2(3., 1.), p2(4., 0.)]);
+        let hits = c1.intersect(&c3);
+        assert_eq!(hits.len(), 1);
+        assert_approx_eq!(f64, hits[0].t1, 1., epsilon = 1E-12);
+        assert_approx_eq!(f64, hits[0].t2, 0., epsilon = 1E-12);
+        assert!(c1.intersect(&BezierCurve::create(vec![p2(0., 3.), p2(2., 3.)])).is_empty());
+
+        // Two circles of radius 2 whose centers are 2 apart meet at 60 degrees.
+        let circle = BezierCircle { radius: 2, segments: 3 }.compute().unwrap();
+        let moved = circle.iter().map(|c| crate::bezier::RatBezierCurve::<2, 3>::create(
+            c.p.iter().map(|p| *p + p2(2., 0.)).collect(), c.weights.clone())).collect::<Vec<_>>();
+        let mut points = Vec::new();
+        for a in &circle {
+            for b in &moved {
+                points.extend(a.intersect(b).iter().map(|h| h.point));
+            }
+        }
+        points.sort_by(|a, b| a.y().total_cmp(&b.y()));
+        points.dedup_by(|a, b| a.dist(b) < 1E-9);
+        assert_eq!(points.len(), 2);
+        assert_approx_eq!(RealPoint2d, points[0], p2(1., -3f64.sqrt()), epsilon = 1E-9);
+        assert_approx_eq!(RealPoint2d, points[1], p2(1., 3f64.sqrt()), epsilon = 1E-9);
+    }
+
+    #[test]
+    fn test_ray() {
+        let plane = BezierSurf { data: Array2D::from_rows(&[
+            vec![p3(0., 0., 0.), p3(0., 1., 0.)],
+            vec![p3(1., 0., 0.), p3(1., 1., 0.)]
+        ]) };
+        let hits = plane.intersect_ray(&Ray { origin: p3(0.25, 0.75, 2.), direction: p3(0., 0., -2.) });
+        assert_eq!(hits.len(), 1);
+        assert_approx_eq!(f64, hits[0].t, 1.);
+        assert_approx_eq!(f64, hits[0].xi, 0.25);
+        assert_approx_eq!(f64, hits[0].eta, 0.75);
+        assert!(plane.intersect_ray(&Ray { origin: p3(0.25, 0.75, 2.), direction: p3(0., 0., 1.) }).is_empty());
+
+        // A ray through the body of the teapot, which is symmetric in y, enters and
+        // leaves it.
+        let patches = BezierTeapot::build_patches();
+        let ray = Ray { origin: p3(0.3, -10., 1.2), direction: p3(0., 1., 0.) };
+        let mut hits = patches.iter().flat_map(|p| p.intersect_ray(&ray)).collect::<Vec<_>>();
+        hits.sort_by(|a, b| a.t.total_cmp(&b.t));
+        hits.dedup_by(|a, b| a.point.dist(&b.point) < 1E-9);
+        assert_eq!(hits.len(), 2);
+        assert_approx_eq!(f64, hits[0].point.y(), -hits[1].point.y(), epsilon = 1E-9);
+        for h in &hits {
+            assert_approx_eq!(RealPoint3d, h.point, ray.at(h.t), epsilon = 1E-9);
+        }
+    }
+
+    #[test]
+    fn test_culling() {
+        // Nearly parallel segments with overlapping boxes are separated by their fat lines.
+        let line = |a, b| HomCurve::from_rational(&RatBezierCurve::create(vec![a, b], vec![1., 1.]));
+        let (c1, c2) = (line(p2(0., 0.), p2(1., 1.)), line(p2(0., 1E-3), p2(1., 1. + 1E-3)));
+        let (w1, w2) = (Piece::whole(&c1), Piece::whole(&c2));
+        assert!(w1.bbox().intersects(&w2.bbox()));
+        assert!(w1.separates(&w2, 1E-12) && w2.separates(&w1, 1E-12));
+        assert!(!w1.separates(&Piece::whole(&line(p2(0., 1.), p2(1., 0.))), 1E-12));
+
+        assert!(hull_contains_origin(&[p2(1., 0.), p2(-1., 1.), p2(-1., -1.)], 1E-12));
+        assert!(!hull_contains_origin(&[p2(1., 1.), p2(2., -1.), p2(1., -2.)], 1E-12));
+        assert!(hull_contains_origin(&[p2(1., 1.), p2(2., -1.), p2(1E-13, 0.)], 1E-12));
+    }
+}
@@ -27,6 +27,14 @@ pub use self::bezier::RatBezierCurve;
 pub use self::bezier::BezierCircle;
 pub use self::bezier::BezierCurveDemo1;
 pub use self::bezier::BezierFactory;
+pub use self::bezier::IndexedVertices;
+pub use self::projection::Projection;
+pub use self::intersection::CurveIntersection;
+pub use self::intersection::Ray;
+pub use self::intersection::RayHit;
+pub use self::bvh::PatchBvh;
+pub use self::model::PatchModel;
+pub use self::continuity::{PatchEdge, Continuity, ContinuityOptions, EdgeContinuity, DegenerateEdge, ContinuityReport};
 pub use self::teapot::TEAPOT_PACTHES;
 pub use self::teapot::TEAPOT_VERTICES;
 pub use self::teapot::BezierTeapot;
@@ -37,8 +45,16 @@ pub use self::teaspoon::TEASPOON_PACTHES;
 pub use self::teaspoon::TEASPOON_VERTICES;
 pub use self::teaspoon::BezierTeaspoon;
 pub use self::utahdata::read_utah_format;
+pub use self::utahdata::parse_utah_format;
+pub use self::utahdata::UtahFormatError;
+pub use self::utahdata::write_utah_format;
 mod bezier;
 mod teapot;
 mod teaspoon;
 mod teacup;
 mod utahdata;
+mod projection;
+mod intersection;
+mod bvh;
+mod continuity;
+mod model;
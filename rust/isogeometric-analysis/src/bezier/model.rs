@@ -0,0 +1,241 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::bezier::{BezierFactory, BezierSurf, PatchEdge};
+use crate::bezier::bezier::VertexWelder;
+use crate::core::{Mapping, RealPoint3d, TriangleMesh, p2};
+use array2d::Array2D;
+use std::collections::HashMap;
+
+///
+/// Model made of bicubic Bezier patches sharing their control points, as described by
+/// the index tables of the Utah format. Unlike a plain Vec<BezierSurf<3>>, the model knows
+/// which patches meet, so its tessellation is welded.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use isogeometric_analysis::bezier::{PatchModel, TEAPOT_PACTHES, TEAPOT_VERTICES};
+/// let model = PatchModel::from_indexed_vertices(&TEAPOT_PACTHES, &TEAPOT_VERTICES, 1E-9).unwrap();
+/// let mesh = model.tessellate(8);
+/// assert!(mesh.is_manifold());
+/// let holes = mesh.boundary_loops();
+/// ```
+/// 
+#[derive(Debug, Clone)]
+pub struct PatchModel {
+    pub vertices: Vec<RealPoint3d>,
+    /// 0-based indices of the control points of each patch, row by row with rows along Xi.
+    pub patches: Vec<[usize; 16]>
+}
+
+///
+/// Identifies a vertex of the tessellation independently of the patch it is generated by.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SampleKey {
+    Corner(usize),
+    Edge([usize; 4], usize),
+    Interior(usize, usize, usize)
+}
+
+impl PatchModel {
+    ///
+    /// Builds a model from an index table with 1-based indices. Vertices closer than
+    /// tolerance are merged, so that patches indexing repeated vertices are still joined.
+    /// Returns None if an index is 0 or exceeds the number of vertices.
+    /// 
+    pub fn from_indexed_vertices(patch_array: &[[usize; 16]], vertex_array: &[[f64; 3]], tolerance: f64) -> Option<PatchModel> {
+        if let Some(i) = patch_array.iter().flatten().find(|i| **i == 0 || **i > vertex_array.len()) {
+            log::warn!("Vertex index {} out of range 1..={}", i, vertex_array.len());
+            return None;
+        }
+        let mut welder = VertexWelder::new(tolerance);
+        let ids = vertex_array.iter()
+            .map(|v| welder.index_of(&RealPoint3d::point3d(v[0], v[1], v[2])))
+            .collect::<Vec<usize>>();
+        Some(PatchModel {
+            vertices: welder.vertices,
+            patches: patch_array.iter().map(|p| p.map(|i| ids[i - 1])).collect()
+        })
+    }
+
+    ///
+    /// Builds a model from bicubic patches, merging control points closer than tolerance.
+    /// Returns None if a patch is not bicubic.
+    /// 
+    pub fn from_patches(patches: &[BezierSurf<3>], tolerance: f64) -> Option<PatchModel> {
+        let (patch_array, vertex_array) = BezierFactory::to_indexed_vertices(patches, tolerance)?;
+        PatchModel::from_indexed_vertices(&patch_array, &vertex_array, 0f64)
+    }
+
+    ///
+    /// Returns the patches as independent surfaces.
+    /// 
+    pub fn surfaces(&self) -> Vec<BezierSurf<3>> {
+        self.patches.iter()
+            .map(|p| BezierSurf { data: Array2D::from_row_major(&p.map(|i| self.vertices[i]), 4, 4) })
+            .collect()
+    }
+
+    ///
+    /// Returns the vertex indices of an edge of a patch in the order of increasing parameter.
+    /// 
+    pub fn edge(&self, patch: usize, edge: PatchEdge) -> [usize; 4] {
+        edge.indices().map(|k| self.patches[patch][k])
+    }
+
+    ///
+    /// Returns true if the edge of the patch is collapsed into a single vertex.
+    /// 
+    pub fn is_degenerate(&self, patch: usize, edge: PatchEdge) -> bool {
+        let e = self.edge(patch, edge);
+        e.iter().all(|v| *v == e[0])
+    }
+
+    ///
+    /// Tessellates each patch with segments × segments quads split into triangles.
+    /// Vertices on shared edges and corners are generated once, degenerate edges collapse
+    /// into a single vertex and the triangles they would flatten are dropped, so that
+    /// the mesh has no cracks nor duplicate vertices.
+    /// 
+    pub fn tessellate(&self, segments: usize) -> TriangleMesh {
+        let n = segments.max(1);
+        let surfaces = self.surfaces();
+        let mut mesh = TriangleMesh::default();
+        let mut index = HashMap::<SampleKey, usize>::new();
+        for (p, surf) in surfaces.iter().enumerate() {
+            let mut grid = vec![vec![0usize; n + 1]; n + 1];
+            for (a, row) in grid.iter_mut().enumerate() {
+                for (b, v) in row.iter_mut().enumerate() {
+                    let key = self.sample_key(p, n, a, b);
+                    *v = *index.entry(key).or_insert_with(|| {
+                        mesh.vertices.push(surf.evaluate(&p2(a as f64/n as f64, b as f64/n as f64)));
+                        mesh.vertices.len() - 1
+                    });
+                }
+            }
+            for a in 0..n {
+                for b in 0..n {
+                    let quad = [grid[a][b], grid[a + 1][b], grid[a + 1][b + 1], grid[a][b + 1]];
+                    for tri in [[quad[0], quad[1], quad[2]], [quad[0], quad[2], quad[3]]] {
+                        if tri[0] != tri[1] && tri[1] != tri[2] && tri[2] != tri[0] {
+                            mesh.triangles.push(tri);
+                        }
+                    }
+                }
+            }
+        }
+        mesh
+    }
+
+    fn sample_key(&self, p: usize, n: usize, a: usize, b: usize) -> SampleKey {
+        let cp = &self.patches[p];
+        let (on_xi, on_eta) = (a == 0 || a == n, b == 0 || b == n);
+        if on_xi && on_eta {
+            return SampleKey::Corner(cp[if a == 0 { 0 } else { 12 } + if b == 0 { 0 } else { 3 }]);
+        }
+        let (edge, t) = match (a, b) {
+            (0, _) => (PatchEdge::XiMin, b),
+            (a, _) if a == n => (PatchEdge::XiMax, b),
+            (_, 0) => (PatchEdge::EtaMin, a),
+            (_, b) if b == n => (PatchEdge::EtaMax, a),
+            _ => return SampleKey::Interior(p, a, b)
+        };
+        let vertices = self.edge(p, edge);
+        if vertices.iter().all(|v| *v == vertices[0]) {
+            return SampleKey::Corner(vertices[0]);
+        }
+        let (key, reversed) = canonical_edge(vertices);
+        SampleKey::Edge(key, if reversed { n - t } else { t })
+    }
+}
+
+///
+/// Returns the smaller of the vertex sequence and its reverse, and whether it is the reverse,
+/// so that the two patches sharing an edge agree on its key.
+/// 
+pub(crate) fn canonical_edge(vertices: [usize; 4]) -> ([usize; 4], bool) {
+    let mut reversed = vertices;
+    reversed.reverse();
+    if reversed < vertices { (reversed, true) } else { (vertices, false) }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bezier::{BezierSurf, BezierTeapot, PatchModel, TEAPOT_PACTHES, TEAPOT_VERTICES};
+    use crate::bezier::bezier::VertexWelder;
+    use crate::core::{RealPoint3d, p3};
+    use array2d::Array2D;
+
+    #[test]
+    fn test_teapot() {
+        let model = PatchModel::from_indexed_vertices(&TEAPOT_PACTHES, &TEAPOT_VERTICES, 1E-9).unwrap();
+        assert_eq!(model.surfaces().len(), 32);
+        let mesh = model.tessellate(6);
+        assert!(mesh.is_manifold());
+
+        // No two vertices coincide.
+        let mut welder = VertexWelder::new(1E-9);
+        for v in &mesh.vertices {
+            welder.index_of(v);
+        }
+        assert_eq!(welder.vertices.len(), mesh.vertices.len());
+
+        // Open rim, lid border and the two ends of handle and spout.
+        let loops = mesh.boundary_loops();
+        assert_eq!(loops.len(), 6);
+        assert!(loops.iter().all(|l| l.len() == 2*6 || l.len() == 4*6));
+
+        // Patches without topology give the same mesh once welded.
+        let welded = PatchModel::from_patches(&BezierTeapot::build_patches(), 1E-9).unwrap().tessellate(6);
+        assert_eq!(welded.vertices.len(), mesh.vertices.len());
+        assert_eq!(welded.triangles.len(), mesh.triangles.len());
+
+        // Indices are 1-based and must refer to a vertex.
+        let mut patches = TEAPOT_PACTHES.to_vec();
+        patches[3][5] = 0;
+        assert!(PatchModel::from_indexed_vertices(&patches, &TEAPOT_VERTICES, 1E-9).is_none());
+        patches[3][5] = TEAPOT_VERTICES.len() + 1;
+        assert!(PatchModel::from_indexed_vertices(&patches, &TEAPOT_VERTICES, 1E-9).is_none());
+    }
+
+    #[test]
+    fn test_closed() {
+        // Cube from six flat bicubic patches.
+        let face = |o: RealPoint3d, u: RealPoint3d, v: RealPoint3d| {
+            let rows = (0..4).map(|i| (0..4).map(|j| o + u*(i as f64/3.) + v*(j as f64/3.)).collect())
+                .collect::<Vec<Vec<RealPoint3d>>>();
+            BezierSurf { data: Array2D::from_rows(&rows) }
+        };
+        let (x, y, z) = (p3(1., 0., 0.), p3(0., 1., 0.), p3(0., 0., 1.));
+        let cube = vec![
+            face(p3(0., 0., 0.), y, x), face(p3(0., 0., 1.), x, y),
+            face(p3(0., 0., 0.), x, z), face(p3(0., 1., 0.), z, x),
+            face(p3(0., 0., 0.), z, y), face(p3(1., 0., 0.), y, z)
+        ];
+        let mesh = PatchModel::from_patches(&cube, 1E-9).unwrap().tessellate(4);
+        assert!(mesh.is_watertight());
+        assert_eq!(mesh.euler_characteristic(), 2);
+        assert_eq!(mesh.vertices.len(), 6*9 + 12*3 + 8);
+    }
+}
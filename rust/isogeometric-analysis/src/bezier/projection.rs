@@ -0,0 +1,296 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::bezier::{BezierCurve, BezierSurf, RatBezierCurve};
+use crate::core::{Mapping, ParametricCurve, RealPoint, p1, p2};
+
+///
+/// Maximum number of Newton iterations from each seed.
+///
+const MAX_ITERATIONS: usize = 50;
+
+///
+/// Parametric step below which Newton iteration stops.
+///
+const TOLERANCE: f64 = 1E-14;
+
+///
+/// Maximum number of local minima of the sampled distance used as Newton seeds.
+///
+const MAX_SEEDS: usize = 8;
+
+///
+/// Result of the projection of a point onto a curve (D = 1) or a surface (D = 2): the
+/// parameter of the closest point, the closest point itself and its distance.
+/// 
+#[derive(Debug, Clone, Copy)]
+pub struct Projection<const D: usize, const S: usize> {
+    pub param: RealPoint<D>,
+    pub point: RealPoint<S>,
+    pub distance: f64
+}
+
+impl<const S: usize> BezierCurve<S> {
+    ///
+    /// Finds the point of the curve closest to p.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use isogeometric_analysis::bezier::BezierCurve;
+    /// use isogeometric_analysis::core::p2;
+    /// let bez = BezierCurve::create(vec![p2(0., 0.), p2(1., 2.), p2(2., 0.)]);
+    /// let proj = bez.project(&p2(1., 3.));
+    /// assert!((proj.param.x() - 0.5).abs() < 1E-9);
+    /// ```
+    /// 
+    pub fn project(&self, p: &RealPoint<S>) -> Projection<1, S> {
+        let d1 = self.derivative();
+        let d2 = d1.derivative();
+        project_curve(|t| self.evaluate(&p1(t)), |t| {
+            let xi = p1(t);
+            [self.evaluate(&xi), d1.evaluate(&xi), d2.evaluate(&xi)]
+        }, p, 8*(self.degree() as usize + 1))
+    }
+}
+
+impl<const S: usize, const H: usize> RatBezierCurve<S, H> {
+    ///
+    /// Finds the point of the curve closest to p.
+    /// 
+    pub fn project(&self, p: &RealPoint<S>) -> Projection<1, S> {
+        project_curve(|t| self.evaluate(&p1(t)), |t| {
+            let d = self.derivatives(t, 2);
+            [d[0], d[1], d[2]]
+        }, p, 8*(self.p.len()))
+    }
+}
+
+impl<const S: usize> BezierSurf<S> {
+    ///
+    /// Finds the point of the surface closest to p.
+    /// 
+    pub fn project(&self, p: &RealPoint<S>) -> Projection<2, S> {
+        let su = self.derivative_xi();
+        let sv = self.derivative_eta();
+        let (suu, suv, svv) = (su.derivative_xi(), su.derivative_eta(), sv.derivative_eta());
+        let eval = |u: f64, v: f64| {
+            let xi = p2(u, v);
+            [self.evaluate(&xi), su.evaluate(&xi), sv.evaluate(&xi), suu.evaluate(&xi), suv.evaluate(&xi), svv.evaluate(&xi)]
+        };
+        let samples = 4*(self.degree_xi().max(self.degree_eta()) as usize + 1);
+        project_surf(|u, v| self.evaluate(&p2(u, v)), eval, p, samples)
+    }
+}
+
+///
+/// Returns the indices of the local minima of the values, the best first.
+/// 
+fn local_minima(values: &[f64], neighbours: impl Fn(usize) -> Vec<usize>) -> Vec<usize> {
+    let mut minima = (0..values.len())
+        .filter(|i| neighbours(*i).iter().all(|j| values[*i] <= values[*j]))
+        .collect::<Vec<usize>>();
+    minima.sort_by(|a, b| values[*a].total_cmp(&values[*b]));
+    minima.truncate(MAX_SEEDS);
+    minima
+}
+
+///
+/// Projects p onto a curve over [0, 1], given by a function returning the point and
+/// another returning the point and its first two derivatives. Newton iteration on the
+/// orthogonality of the tangent is seeded with the local minima of the distance sampled
+/// in the given number of intervals; the parameter is clamped to the domain so minima at
+/// the ends are found as well.
+/// 
+pub(crate) fn project_curve<P, F, const S: usize>(point: P, eval: F, p: &RealPoint<S>, samples: usize) -> Projection<1, S>
+    where P: Fn(f64) -> RealPoint<S>, F: Fn(f64) -> [RealPoint<S>; 3] {
+    let ts = (0..=samples).map(|i| (i as f64)/(samples as f64)).collect::<Vec<f64>>();
+    let dists = ts.iter().map(|t| point(*t).dist(p)).collect::<Vec<f64>>();
+    let seeds = local_minima(&dists, |i| {
+        let mut n = Vec::new();
+        if i > 0 { n.push(i - 1); }
+        if i < samples { n.push(i + 1); }
+        n
+    });
+
+    let mut best: Option<Projection<1, S>> = None;
+    for seed in seeds {
+        let mut t = ts[seed];
+        for _ in 0..MAX_ITERATIONS {
+            let [c, d1, d2] = eval(t);
+            let r = c - *p;
+            let df = d2.dot(&r) + d1.dot(&d1);
+            if df <= 0. {
+                break;
+            }
+            let next = (t - d1.dot(&r)/df).clamp(0., 1.);
+            let step = (next - t).abs();
+            t = next;
+            if step < TOLERANCE {
+                break;
+            }
+        }
+        let c = point(t);
+        let candidate = if c.dist(p) <= dists[seed] {
+            Projection { param: p1(t), point: c, distance: c.dist(p) }
+        }
+        else {
+            Projection { param: p1(ts[seed]), point: point(ts[seed]), distance: dists[seed] }
+        };
+        if best.map(|b| candidate.distance < b.distance).unwrap_or(true) {
+            best = Some(candidate);
+        }
+    }
+    best.unwrap()
+}
+
+///
+/// Projects p onto a surface over [0, 1]², given by a function returning the point and
+/// another returning the point, the first derivatives along u and v and the second
+/// derivatives uu, uv and vv. Interior minima are found by Newton iteration from the
+/// local minima of the sampled distance, those on the boundary by projecting onto the
+/// four boundary curves.
+/// 
+pub(crate) fn project_surf<P, F, const S: usize>(point: P, eval: F, p: &RealPoint<S>, samples: usize) -> Projection<2, S>
+    where P: Fn(f64, f64) -> RealPoint<S>, F: Fn(f64, f64) -> [RealPoint<S>; 6] {
+    let n = samples + 1;
+    let params = (0..n*n).map(|k| ((k/n) as f64/(samples as f64), (k%n) as f64/(samples as f64))).collect::<Vec<(f64, f64)>>();
+    let dists = params.iter().map(|(u, v)| point(*u, *v).dist(p)).collect::<Vec<f64>>();
+    let seeds = local_minima(&dists, |k| {
+        let (i, j) = ((k/n) as i64, (k%n) as i64);
+        let mut neighbours = Vec::new();
+        for di in -1..=1 {
+            for dj in -1..=1 {
+                let (a, b) = (i + di, j + dj);
+                if (di, dj) != (0, 0) && a >= 0 && b >= 0 && a < n as i64 && b < n as i64 {
+                    neighbours.push((a as usize)*n + b as usize);
+                }
+            }
+        }
+        neighbours
+    });
+
+    let mut best: Option<Projection<2, S>> = None;
+    let mut consider = |candidate: Projection<2, S>| {
+        if best.map(|b| candidate.distance < b.distance).unwrap_or(true) {
+            best = Some(candidate);
+        }
+    };
+    for seed in seeds {
+        let (mut u, mut v) = params[seed];
+        for _ in 0..MAX_ITERATIONS {
+            let [s, su, sv, suu, suv, svv] = eval(u, v);
+            let r = s - *p;
+            let (f, g) = (su.dot(&r), sv.dot(&r));
+            let (a, b, d) = (su.dot(&su) + r.dot(&suu), su.dot(&sv) + r.dot(&suv), sv.dot(&sv) + r.dot(&svv));
+            let det = a*d - b*b;
+            if det <= 0. || a <= 0. {
+                break;
+            }
+            let next_u = (u - (d*f - b*g)/det).clamp(0., 1.);
+            let next_v = (v - (a*g - b*f)/det).clamp(0., 1.);
+            let step = (next_u - u).abs() + (next_v - v).abs();
+            u = next_u;
+            v = next_v;
+            if step < TOLERANCE {
+                break;
+            }
+        }
+        let s = point(u, v);
+        consider(Projection { param: p2(u, v), point: s, distance: s.dist(p) });
+        let (u, v) = params[seed];
+        consider(Projection { param: p2(u, v), point: point(u, v), distance: dists[seed] });
+    }
+
+    // Minima on the boundary.
+    for fixed in [0., 1.] {
+        let c = project_curve(|t| point(fixed, t), |t| { let e = eval(fixed, t); [e[0], e[2], e[5]] }, p, samples);
+        consider(Projection { param: p2(fixed, c.param.x()), point: c.point, distance: c.distance });
+        let c = project_curve(|t| point(t, fixed), |t| { let e = eval(t, fixed); [e[0], e[1], e[3]] }, p, samples);
+        consider(Projection { param: p2(c.param.x(), fixed), point: c.point, distance: c.distance });
+    }
+    best.unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bezier::{BezierCircle, BezierCurve, BezierCurveDemo1, BezierTeapot};
+    use crate::core::{Mapping, RealPoint2d, RealPoint3d, p1, p2};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_curve() {
+        let bez = BezierCurveDemo1::create();
+        let fine = (0..=10000).map(|i| bez.evaluate(&p1((i as f64)/10000.))).collect::<Vec<RealPoint2d>>();
+        for i in 0..=8 {
+            for j in 0..=6 {
+                // The projection is never farther than the closest of many samples.
+                let p = p2(-0.5 + 0.5*(i as f64), -0.5 + 0.4*(j as f64));
+                let proj = bez.project(&p);
+                let brute = fine.iter().map(|q| q.dist(&p)).fold(f64::MAX, f64::min);
+                assert!(proj.distance <= brute + 1E-12);
+                assert!(brute - proj.distance < 1E-6);
+                assert_approx_eq!(RealPoint2d, proj.point, bez.evaluate(&proj.param));
+            }
+        }
+
+        // Points beyond the ends project onto them.
+        let line = BezierCurve::create(vec![p2(0., 0.), p2(1., 0.), p2(2., 0.), p2(3., 0.)]);
+        assert_eq!(line.project(&p2(-1., 1.)).param.x(), 0.);
+        assert_eq!(line.project(&p2(4., -1.)).param.x(), 1.);
+        assert_approx_eq!(f64, line.project(&p2(1.5, 2.)).param.x(), 0.5, epsilon = 1E-12);
+    }
+
+    #[test]
+    fn test_rational() {
+        let arcs = BezierCircle { radius: 1, segments: 4 }.compute().unwrap();
+        for arc in &arcs {
+            let mid = arc.evaluate(&p1(0.3));
+            let proj = arc.project(&(mid*2.5));
+            assert_approx_eq!(f64, proj.param.x(), 0.3, epsilon = 1E-9);
+            assert_approx_eq!(f64, proj.distance, 1.5, epsilon = 1E-12);
+        }
+    }
+
+    #[test]
+    fn test_surf() {
+        let patches = BezierTeapot::build_patches();
+        for (k, patch) in patches.iter().enumerate().step_by(3) {
+            let xi = p2(0.37, 0.61);
+            let s = patch.evaluate(&xi);
+            let n = patch.derivative_xi().evaluate(&xi).cross(&patch.derivative_eta().evaluate(&xi));
+            if n.norm() < 1E-9 {
+                continue;
+            }
+            let p = s + n*(0.01/n.norm());
+            let proj = patch.project(&p);
+            assert_approx_eq!(f64, proj.distance, 0.01, epsilon = 1E-9);
+            assert_approx_eq!(RealPoint2d, proj.param, xi, epsilon = 1E-6);
+            assert_approx_eq!(RealPoint3d, proj.point, s, epsilon = 1E-9);
+
+            // Corners, where Newton iteration is clamped, are found exactly.
+            let corner = patch.evaluate(&p2(1., 1.));
+            let proj = patch.project(&corner);
+            assert!(proj.distance < 1E-9, "patch {}", k);
+        }
+    }
+}
@@ -22,102 +22,308 @@
 
 use crate::bezier::BezierSurf;
 use crate::bezier::BezierFactory;
+use std::fmt::{Display, Formatter};
 use std::fs::File;
 use std::io::BufRead;
 use std::io::BufReader;
+use std::io::{Error, ErrorKind, Write};
+use std::path::Path;
 
 ///
-/// Parser a line containing a patch.
+/// Error raised when parsing data in the Utah format. Each variant carries the
+/// 1-based number of the line where the problem was found, or 0 if the problem
+/// is not related to a line.
 /// 
-fn parse_patch(line: &String) -> [usize; 16] {
-    let v = line.split(",").collect::<Vec<&str>>();
-    let mut ret = [0usize; 16];
-    let mut index = 0usize;
-    for i in v {
-        ret[index] = i.parse().unwrap();
-        index += 1;
-    }
-    ret
+#[derive(Debug)]
+pub enum UtahFormatError {
+    /// The data could not be read, or ended before all the declared lines were found.
+    Io { line: usize, error: Error },
+    /// A line expected to contain the number of patches or vertices is not a valid count.
+    BadCount { line: usize, token: String },
+    /// A token in a patch or vertex line is not a valid number.
+    NonNumeric { line: usize, token: String },
+    /// A patch or vertex line does not contain the expected number of tokens.
+    WrongArity { line: usize, expected: usize, found: usize },
+    /// A patch refers to a vertex that does not exist. Indices are 1-based.
+    IndexOutOfRange { line: usize, index: usize, vertex_count: usize }
+}
+
+impl UtahFormatError {
+    ///
+    /// Returns the line where the error was found.
+    /// 
+    pub fn line(&self) -> usize {
+        match self {
+            UtahFormatError::Io { line, .. } => *line,
+            UtahFormatError::BadCount { line, .. } => *line,
+            UtahFormatError::NonNumeric { line, .. } => *line,
+            UtahFormatError::WrongArity { line, .. } => *line,
+            UtahFormatError::IndexOutOfRange { line, .. } => *line
+        }
+    }
+}
+
+impl Display for UtahFormatError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            UtahFormatError::Io { line, error } =>
+                write!(f, "line {}: {}", line, error),
+            UtahFormatError::BadCount { line, token } =>
+                write!(f, "line {}: invalid count \"{}\"", line, token),
+            UtahFormatError::NonNumeric { line, token } =>
+                write!(f, "line {}: invalid number \"{}\"", line, token),
+            UtahFormatError::WrongArity { line, expected, found } =>
+                write!(f, "line {}: expected {} values, found {}", line, expected, found),
+            UtahFormatError::IndexOutOfRange { line, index, vertex_count } =>
+                write!(f, "line {}: vertex index {} is not in [1, {}]", line, index, vertex_count)
+        }
+    }
+}
+
+impl std::error::Error for UtahFormatError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            UtahFormatError::Io { error, .. } => Some(error),
+            _ => None
+        }
+    }
 }
 
 ///
-/// Parses a line containing a vertex.
+/// Iterates over lines, keeping track of the line number.
 /// 
-fn parse_vertex(line: &String) -> [f64; 3] {
-    let v = line.split(",").collect::<Vec<&str>>();
-
-    //{
-    //    let mut iter = v.iter();
-    //    log::info!("[ {:+.9}, {:+.9}, {:+.9} ],",
-    //iter.next().unwrap().parse::<f64>().unwrap(),
-    //iter.next().unwrap().parse::<f64>().unwrap(),
-    //iter.next().unwrap().parse::<f64>().unwrap());
-    //}
-
-    let mut iter = v.iter();
-    [
-        iter.next().unwrap().parse().unwrap(),
-        iter.next().unwrap().parse().unwrap(),
-        iter.next().unwrap().parse().unwrap()
-    ]
+struct LineReader<R: BufRead> {
+    lines: std::io::Lines<R>,
+    line: usize
+}
+
+impl<R: BufRead> LineReader<R> {
+    ///
+    /// Returns the next line or an error if the data ended.
+    /// 
+    fn next_line(&mut self) -> Result<String, UtahFormatError> {
+        self.line += 1;
+        match self.lines.next() {
+            None => Err(UtahFormatError::Io {
+                line: self.line,
+                error: Error::new(ErrorKind::UnexpectedEof, "unexpected end of data")
+            }),
+            Some(Err(error)) => Err(UtahFormatError::Io { line: self.line, error }),
+            Some(Ok(line)) => Ok(line)
+        }
+    }
+
+    ///
+    /// Parses a line containing a count.
+    /// 
+    fn next_count(&mut self) -> Result<usize, UtahFormatError> {
+        let line = self.next_line()?;
+        line.trim().parse().map_err(|_| UtahFormatError::BadCount {
+            line: self.line,
+            token: line.trim().to_string()
+        })
+    }
+
+    ///
+    /// Parses a line containing N comma separated values.
+    /// 
+    fn next_values<T: std::str::FromStr + Default + Copy, const N: usize>(&mut self) -> Result<[T; N], UtahFormatError> {
+        let line = self.next_line()?;
+        let tokens = line.split(',').map(|t| t.trim()).collect::<Vec<&str>>();
+        if tokens.len() != N {
+            return Err(UtahFormatError::WrongArity { line: self.line, expected: N, found: tokens.len() });
+        }
+        let mut ret = [T::default(); N];
+        for (value, token) in ret.iter_mut().zip(tokens) {
+            *value = token.parse().map_err(|_| UtahFormatError::NonNumeric {
+                line: self.line,
+                token: token.to_string()
+            })?;
+        }
+        Ok(ret)
+    }
 }
 
 ///
 /// Reads a file containing bezier patches into usable structures.
 /// 
-pub fn read_utah_format(file_path: String) -> Result<Vec<BezierSurf<3>>, ()> {
-    let file = File::open(file_path);
-    match file {
-        Err(_) => { return Err(()); }
-        Ok(file) => {
-            let reader = BufReader::new(file);
-            let mut lines = reader.lines();
-
-            // Number of patches.
-            let patch_count;
-            match lines.next() {
-                None => { return Err(()); }
-                Some(line) => { patch_count = line.unwrap().parse().unwrap(); }
-            }
+pub fn read_utah_format(file_path: impl AsRef<Path>) -> Result<Vec<BezierSurf<3>>, UtahFormatError> {
+    match File::open(file_path) {
+        Err(error) => Err(UtahFormatError::Io { line: 0, error }),
+        Ok(file) => parse_utah_format(BufReader::new(file))
+    }
+}
 
-            // Parse patches.
-            let mut patches = Vec::<[usize; 16]>::new();
-            for _ in 0..patch_count {
-                match lines.next() {
-                    None => { return Err(()); }
-                    Some(line) => { patches.push(parse_patch(&line.unwrap())); }
-                }
-            }
+///
+/// Parses bezier patches in the Utah format from any reader: the number of patches,
+/// one line per patch with 16 1-based vertex indices, the number of vertices and one
+/// line per vertex with 3 coords.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use isogeometric_analysis::bezier::{parse_utah_format, UtahFormatError};
+/// let data = "1\n1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,2\n1\n0.0,0.0,0.0\n";
+/// match parse_utah_format(data.as_bytes()) {
+///     Err(UtahFormatError::IndexOutOfRange { line, index, .. }) => assert_eq!((line, index), (2, 2)),
+///     _ => panic!()
+/// }
+/// ```
+/// 
+pub fn parse_utah_format<R: BufRead>(reader: R) -> Result<Vec<BezierSurf<3>>, UtahFormatError> {
+    let mut reader = LineReader { lines: reader.lines(), line: 0 };
 
-            // Number of vertices.
-            let vertex_count;
-            match lines.next() {
-                None => { return Err(()); }
-                Some(line) => { vertex_count = line.unwrap().parse().unwrap(); }
-            }
+    // Parse patches.
+    let patch_count = reader.next_count()?;
+    let mut patches = Vec::<([usize; 16], usize)>::new();
+    for _ in 0..patch_count {
+        let patch = reader.next_values::<usize, 16>()?;
+        patches.push((patch, reader.line));
+    }
 
-            // Parse vertices.
-            let mut vertices = Vec::<[f64; 3]>::new();
-            for _ in 0..vertex_count {
-                match lines.next() {
-                    None => { return Err(()); }
-                    Some(line) => { vertices.push(parse_vertex(&line.unwrap())); }
-                }
-            }
+    // Parse vertices.
+    let vertex_count = reader.next_count()?;
+    let mut vertices = Vec::<[f64; 3]>::new();
+    for _ in 0..vertex_count {
+        vertices.push(reader.next_values::<f64, 3>()?);
+    }
 
-            return Ok(BezierFactory::from_indexed_vertices(patches, vertices));
+    // Indices are 1-based.
+    for (patch, line) in &patches {
+        if let Some(index) = patch.iter().find(|i| **i == 0 || **i > vertex_count) {
+            return Err(UtahFormatError::IndexOutOfRange { line: *line, index: *index, vertex_count });
         }
     }
+
+    Ok(BezierFactory::from_indexed_vertices(patches.iter().map(|(p, _)| *p).collect(), vertices))
+}
+
+///
+/// Writes bicubic bezier patches in the format read by read_utah_format. Control
+/// points closer than tolerance are written once and shared among patches.
+/// 
+pub fn write_utah_format(patches: &[BezierSurf<3>], writer: &mut impl Write, tolerance: f64) -> std::io::Result<()> {
+    let (patch_array, vertex_array) = match BezierFactory::to_indexed_vertices(patches, tolerance) {
+        None => { return Err(Error::new(ErrorKind::InvalidInput, "only bicubic patches can be written")); }
+        Some(arrays) => arrays
+    };
+
+    writeln!(writer, "{}", patch_array.len())?;
+    for patch in &patch_array {
+        let line = patch.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(",");
+        writeln!(writer, "{}", line)?;
+    }
+    writeln!(writer, "{}", vertex_array.len())?;
+    for vertex in &vertex_array {
+        writeln!(writer, "{},{},{}", vertex[0], vertex[1], vertex[2])?;
+    }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::bezier::read_utah_format;
+    use crate::bezier::{read_utah_format, parse_utah_format, write_utah_format, UtahFormatError};
+    use crate::bezier::BezierSurf;
+    use crate::core::RealPoint3d;
+    use array2d::Array2D;
+    use std::fs::File;
 
     #[test]
     fn test_eq() {
-        assert_eq!(read_utah_format("utah_teapot_data/teapot".to_string()).unwrap().len(), 32);
-        assert_eq!(read_utah_format("utah_teapot_data/teacup".to_string()).unwrap().len(), 26);
-        assert_eq!(read_utah_format("utah_teapot_data/teaspoon".to_string()).unwrap().len(), 16);
+        assert_eq!(read_utah_format("utah_teapot_data/teapot").unwrap().len(), 32);
+        assert_eq!(read_utah_format("utah_teapot_data/teacup").unwrap().len(), 26);
+        assert_eq!(read_utah_format("utah_teapot_data/teaspoon").unwrap().len(), 16);
+    }
+
+    #[test]
+    fn test_errors() {
+        let patch = "1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16";
+        let vertices = "0,0,0\n".repeat(16);
+        let valid = format!("1\n{}\n16\n{}", patch, vertices);
+        assert_eq!(parse_utah_format(valid.as_bytes()).unwrap().len(), 1);
+
+        match parse_utah_format("x\n".as_bytes()) {
+            Err(UtahFormatError::BadCount { line: 1, token }) => assert_eq!(token, "x"),
+            e => panic!("{:?}", e.err())
+        }
+        match parse_utah_format(format!("1\n{}\n-16\n", patch).as_bytes()) {
+            Err(UtahFormatError::BadCount { line: 3, token }) => assert_eq!(token, "-16"),
+            e => panic!("{:?}", e.err())
+        }
+        match parse_utah_format(format!("1\n{},17\n", patch).as_bytes()) {
+            Err(UtahFormatError::WrongArity { line: 2, expected: 16, found: 17 }) => {},
+            e => panic!("{:?}", e.err())
+        }
+        match parse_utah_format(format!("1\n{}\n16\n0,0\n", patch).as_bytes()) {
+            Err(UtahFormatError::WrongArity { line: 4, expected: 3, found: 2 }) => {},
+            e => panic!("{:?}", e.err())
+        }
+        match parse_utah_format(format!("1\n{}\n16\n0,a,0\n", patch).as_bytes()) {
+            Err(UtahFormatError::NonNumeric { line: 4, token }) => assert_eq!(token, "a"),
+            e => panic!("{:?}", e.err())
+        }
+        match parse_utah_format(format!("1\n{}\n16\n{}", patch.replace("16", "0"), vertices).as_bytes()) {
+            Err(UtahFormatError::IndexOutOfRange { line: 2, index: 0, vertex_count: 16 }) => {},
+            e => panic!("{:?}", e.err())
+        }
+        match parse_utah_format(format!("1\n{}\n15\n{}", patch, vertices).as_bytes()) {
+            Err(UtahFormatError::IndexOutOfRange { line: 2, index: 16, vertex_count: 15 }) => {},
+            e => panic!("{:?}", e.err())
+        }
+        match parse_utah_format(format!("2\n{}\n", patch).as_bytes()) {
+            Err(e) => {
+                assert_eq!(e.line(), 3);
+                assert_eq!(e.to_string(), "line 3: unexpected end of data");
+            },
+            Ok(_) => panic!()
+        }
+        match read_utah_format("utah_teapot_data/missing") {
+            Err(UtahFormatError::Io { line: 0, error }) => assert_eq!(error.kind(), std::io::ErrorKind::NotFound),
+            e => panic!("{:?}", e.err())
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for name in ["teapot", "teacup", "teaspoon"].iter() {
+            let patches = read_utah_format(format!("utah_teapot_data/{}", name)).unwrap();
+            let path = std::env::temp_dir().join(format!("isogeometric_analysis_{}", name));
+            write_utah_format(&patches, &mut File::create(&path).unwrap(), 1E-9).unwrap();
+            let read = read_utah_format(&path).unwrap();
+            std::fs::remove_file(&path).unwrap();
+
+            assert_eq!(read.len(), patches.len());
+            for (p1, p2) in patches.iter().zip(read.iter()) {
+                assert_eq!(p1.data, p2.data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_welding() {
+        let patches = read_utah_format("utah_teapot_data/teapot").unwrap();
+        let mut out = Vec::<u8>::new();
+        write_utah_format(&patches, &mut out, 1E-9).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines = text.lines().collect::<Vec<&str>>();
+        assert_eq!(lines[0], "32");
+        assert_eq!(lines[1], "1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16");
+        // The degenerate lid top shares a single vertex.
+        assert!(lines[21].starts_with("204,204,204,204,"));
+
+        let vertex_count: usize = lines[33].parse().unwrap();
+        assert!(vertex_count < 16*32);
+        assert_eq!(lines.len(), 34 + vertex_count);
+
+        let shifted = patches.iter().map(|p| {
+            let points = p.data.elements_row_major_iter().map(|v| *v + RealPoint3d::point3d(1E-4, 0f64, 0f64)).collect::<Vec<_>>();
+            BezierSurf::<3> { data: Array2D::from_row_major(&points, 4, 4) }
+        }).collect::<Vec<_>>();
+        let mut all = read_utah_format("utah_teapot_data/teapot").unwrap();
+        all.extend(shifted);
+        let mut coarse = Vec::<u8>::new();
+        write_utah_format(&all, &mut coarse, 1E-3).unwrap();
+        let coarse = String::from_utf8(coarse).unwrap();
+        assert_eq!(coarse.lines().nth(65).unwrap().parse::<usize>().unwrap(), vertex_count);
     }
 }
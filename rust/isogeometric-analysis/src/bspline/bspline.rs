@@ -0,0 +1,511 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::core::{RealPoint, RealPoint1d, RealPoint2d, RealPoint3d};
+use crate::core::RealRange;
+use crate::core::Mapping;
+use crate::core::{ParametricCurve, ParametricSurface};
+use array2d::Array2D;
+
+///
+/// Represents the i-th B-spline basis function of degree p defined on a knot vector.
+/// B-spline basis functions are functions f:ℝ→ℝ.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use isogeometric_analysis::bspline::BsplineBasis;
+/// use isogeometric_analysis::core::Evaluator;
+/// let b = BsplineBasis::create(vec![0., 0., 0., 1., 2., 3., 3., 3.], 2, 2).unwrap();
+/// let (xpoints, ypoints) = Evaluator::<1, 1, 1000>::evaluate_parametric_range1d(&b, &0f64, &3f64);
+/// ```
+/// 
+pub struct BsplineBasis {
+    knots: Vec<f64>,
+    p: usize,
+    i: usize
+}
+
+impl Mapping<f64, f64, 1, 1> for BsplineBasis {
+    ///
+    /// Evaluate without creating a new object.
+    ///
+    #[inline(always)]
+    fn evaluate_fill<'a>(&self, input: &RealPoint1d, output: &'a mut RealPoint1d) -> &'a mut RealPoint1d {
+        output.set_x(BsplineBasis::compute(&self.knots, self.i, self.p, input.x()));
+        output
+    }
+}
+
+impl BsplineBasis {
+    ///
+    /// Creates the `i`-th B-spline basis function of degree `p` on the knot vector.
+    /// 
+    pub fn create(knots: Vec<f64>, p: usize, i: usize) -> Option<BsplineBasis> {
+        if !is_knot_vector(&knots) {
+            log::warn!("Knots must be a non-decreasing sequence");
+            return None;
+        }
+        if i + p + 1 >= knots.len() {
+            log::warn!("Index and degree are not compatible with the knot vector");
+            return None;
+        }
+        Some(BsplineBasis { knots, p, i })
+    }
+
+    ///
+    /// Finds the index i of the knot span [ξ_i, ξ_i+1) containing xi, where n + 1 is the
+    /// number of basis functions. Values outside of the domain are clamped to the first
+    /// or last non-empty span.
+    /// 
+    pub fn find_span(knots: &[f64], p: usize, n: usize, xi: f64) -> usize {
+        if xi >= knots[n + 1] {
+            return n;
+        }
+        if xi <= knots[p] {
+            return p;
+        }
+        let mut low = p;
+        let mut high = n + 1;
+        let mut i = (low + high)/2;
+        while xi < knots[i] || xi >= knots[i + 1] {
+            if xi < knots[i] {
+                high = i;
+            }
+            else {
+                low = i;
+            }
+            i = (low + high)/2;
+        }
+        i
+    }
+
+    ///
+    /// Computes the p + 1 basis functions N_span-p, ..., N_span that do not vanish in xi.
+    /// 
+    pub fn nonvanishing(knots: &[f64], span: usize, p: usize, xi: f64) -> Vec<f64> {
+        let mut n = vec![0f64; p + 1];
+        let mut left = vec![0f64; p + 1];
+        let mut right = vec![0f64; p + 1];
+        n[0] = 1f64;
+        for j in 1..=p {
+            left[j] = xi - knots[span + 1 - j];
+            right[j] = knots[span + j] - xi;
+            let mut saved = 0f64;
+            for r in 0..j {
+                let temp = n[r]/(right[r + 1] + left[j - r]);
+                n[r] = saved + right[r + 1]*temp;
+                saved = left[j - r]*temp;
+            }
+            n[j] = saved;
+        }
+        n
+    }
+
+    ///
+    /// Computes the derivatives of order 0 to n of the p + 1 basis functions that do not
+    /// vanish in xi. Row k of the result holds the k-th derivatives of N_span-p, ..., N_span;
+    /// derivatives of order higher than p are zero.
+    /// 
+    pub fn ders_nonvanishing(knots: &[f64], span: usize, p: usize, xi: f64, n: usize) -> Vec<Vec<f64>> {
+        let mut ndu = vec![vec![0f64; p + 1]; p + 1];
+        let mut left = vec![0f64; p + 1];
+        let mut right = vec![0f64; p + 1];
+        ndu[0][0] = 1f64;
+        for j in 1..=p {
+            left[j] = xi - knots[span + 1 - j];
+            right[j] = knots[span + j] - xi;
+            let mut saved = 0f64;
+            for r in 0..j {
+                // Lower triangle stores the knot differences, upper triangle the functions.
+                ndu[j][r] = right[r + 1] + left[j - r];
+                let temp = ndu[r][j - 1]/ndu[j][r];
+                ndu[r][j] = saved + right[r + 1]*temp;
+                saved = left[j - r]*temp;
+            }
+            ndu[j][j] = saved;
+        }
+
+        let mut ders = vec![vec![0f64; p + 1]; n + 1];
+        for j in 0..=p {
+            ders[0][j] = ndu[j][p];
+        }
+        let mut a = vec![vec![0f64; p + 1]; 2];
+        for r in 0..=p {
+            let (mut s1, mut s2) = (0usize, 1usize);
+            a[0][0] = 1f64;
+            for k in 1..=n.min(p) {
+                let mut d = 0f64;
+                let rk = r as i64 - k as i64;
+                let pk = p - k;
+                if rk >= 0 {
+                    let rk = rk as usize;
+                    a[s2][0] = a[s1][0]/ndu[pk + 1][rk];
+                    d = a[s2][0]*ndu[rk][pk];
+                }
+                let j1 = if rk >= -1 { 1 } else { (-rk) as usize };
+                let j2 = if r as i64 - 1 <= pk as i64 { k - 1 } else { p - r };
+                for j in j1..=j2 {
+                    let rkj = (rk + j as i64) as usize;
+                    a[s2][j] = (a[s1][j] - a[s1][j - 1])/ndu[pk + 1][rkj];
+                    d += a[s2][j]*ndu[rkj][pk];
+                }
+                if r <= pk {
+                    a[s2][k] = -a[s1][k - 1]/ndu[pk + 1][r];
+                    d += a[s2][k]*ndu[r][pk];
+                }
+                ders[k][r] = d;
+                std::mem::swap(&mut s1, &mut s2);
+            }
+        }
+
+        let mut factor = p as f64;
+        for (k, row) in ders.iter_mut().enumerate().take(n.min(p) + 1).skip(1) {
+            for d in row.iter_mut() {
+                *d *= factor;
+            }
+            factor *= (p - k) as f64;
+        }
+        ders
+    }
+
+    ///
+    /// Computes the i-th basis function of degree p in xi through the triangular table.
+    /// 
+    pub fn compute(knots: &[f64], i: usize, p: usize, xi: f64) -> f64 {
+        let m = knots.len() - 1;
+
+        // First and last functions are 1 at the boundaries of the knot vector.
+        if (i == 0 && xi == knots[0]) || (i == m - p - 1 && xi == knots[m]) {
+            return 1f64;
+        }
+        if xi < knots[i] || xi >= knots[i + p + 1] {
+            return 0f64;
+        }
+
+        let mut n = (0..=p)
+            .map(|j| if xi >= knots[i + j] && xi < knots[i + j + 1] { 1f64 } else { 0f64 })
+            .collect::<Vec<f64>>();
+        for k in 1..=p {
+            let mut saved = if n[0] == 0f64 { 0f64 } else { ((xi - knots[i])*n[0])/(knots[i + k] - knots[i]) };
+            for j in 0..(p - k + 1) {
+                let left = knots[i + j + 1];
+                let right = knots[i + j + k + 1];
+                if n[j + 1] == 0f64 {
+                    n[j] = saved;
+                    saved = 0f64;
+                }
+                else {
+                    let temp = n[j + 1]/(right - left);
+                    n[j] = saved + (right - xi)*temp;
+                    saved = (xi - left)*temp;
+                }
+            }
+        }
+        n[0]
+    }
+}
+
+///
+/// Implements B-spline curves, i.e. functions f:ℝ→ℝ^SIZE defined by control points,
+/// a knot vector and a degree.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use isogeometric_analysis::core::p2;
+/// use isogeometric_analysis::bspline::BsplineCurve;
+/// use isogeometric_analysis::core::Evaluator;
+/// let cpoints = vec![p2(0., 0.), p2(1., 1.), p2(2., 0.5), p2(3., 0.5), p2(0.5, 1.5), p2(1.5, 0.)];
+/// let knots = vec![0., 0., 0., 1., 2., 3., 4., 4., 4.];
+/// let bsp = BsplineCurve::create(cpoints, knots, 2).unwrap();
+/// let (xpoints, ypoints) = Evaluator::<1, 2, 10>::evaluate_parametric_range1d(&bsp, &0f64, &4f64);
+/// ```
+/// 
+#[derive(Debug, Clone)]
+pub struct BsplineCurve<const SIZE: usize> {
+    pub p: Vec<RealPoint<SIZE>>,
+    pub knots: Vec<f64>,
+    pub degree: usize
+}
+
+impl<const SIZE: usize> Mapping<f64, f64, 1, SIZE> for BsplineCurve<SIZE> {
+    ///
+    /// Evaluates the B-spline curve in point xi. Point xi exists in the parametric space.
+    ///
+    #[inline(always)]
+    fn evaluate_fill<'a>(&self, xi: &RealPoint1d, output: &'a mut RealPoint<SIZE>) -> &'a mut RealPoint<SIZE> {
+        let n = self.p.len() - 1;
+        let span = BsplineBasis::find_span(&self.knots, self.degree, n, xi.x());
+        let basis = BsplineBasis::nonvanishing(&self.knots, span, self.degree, xi.x());
+        output.reset();
+        for (k, b) in basis.iter().enumerate() {
+            *output += self.p[span - self.degree + k]*(*b);
+        }
+        output
+    }
+}
+
+impl<const SIZE: usize> BsplineCurve<SIZE> {
+    ///
+    /// Creates a B-spline curve. The knot vector must be non-decreasing and must contain
+    /// as many knots as control points plus degree plus one.
+    /// 
+    pub fn create(cpoints: Vec<RealPoint<SIZE>>, knots: Vec<f64>, degree: usize) -> Option<BsplineCurve<SIZE>> {
+        if !is_valid_spline(cpoints.len(), &knots, degree) {
+            return None;
+        }
+        Some(BsplineCurve {
+            p: cpoints,
+            knots,
+            degree
+        })
+    }
+
+    ///
+    /// Returns the parametric domain [ξ_p, ξ_n+1] of the curve.
+    /// 
+    pub fn knot_range(&self) -> RealRange {
+        RealRange {
+            a: self.knots[self.degree],
+            b: self.knots[self.p.len()]
+        }
+    }
+}
+
+impl<const SIZE: usize> ParametricCurve<SIZE> for BsplineCurve<SIZE> {
+    fn domain(&self) -> RealRange {
+        self.knot_range()
+    }
+
+    fn derivatives(&self, xi: f64, n: usize) -> Vec<RealPoint<SIZE>> {
+        let span = BsplineBasis::find_span(&self.knots, self.degree, self.p.len() - 1, xi);
+        let ders = BsplineBasis::ders_nonvanishing(&self.knots, span, self.degree, xi, n);
+        ders.iter().map(|row| {
+            let mut d = RealPoint::<SIZE>::origin();
+            for (k, b) in row.iter().enumerate() {
+                d += self.p[span - self.degree + k]*(*b);
+            }
+            d
+        }).collect()
+    }
+
+    ///
+    /// Returns the distinct knots in the domain.
+    ///
+    fn breakpoints(&self) -> Vec<f64> {
+        distinct_knots(&self.knots[self.degree..=self.p.len()])
+    }
+}
+
+///
+/// Returns the knots without repetitions.
+///
+pub(crate) fn distinct_knots(knots: &[f64]) -> Vec<f64> {
+    let mut ret = knots.to_vec();
+    ret.dedup();
+    ret
+}
+
+///
+/// Represents a B-spline surface. Control points are stored in a matrix whose rows
+/// run along the Xi direction.
+/// 
+#[derive(Debug, Clone)]
+pub struct BsplineSurf<const S: usize> {
+    pub data: Array2D<RealPoint<S>>,
+    pub knots_xi: Vec<f64>,
+    pub knots_eta: Vec<f64>,
+    pub degree_xi: usize,
+    pub degree_eta: usize
+}
+
+impl<const S: usize> Mapping<f64, f64, 2, S> for BsplineSurf<S> {
+    ///
+    /// Evaluates the B-spline surface in point (xi, eta) of the parametric space.
+    ///
+    #[inline(always)]
+    fn evaluate_fill<'a>(&self, input: &RealPoint2d, output: &'a mut RealPoint<S>) -> &'a mut RealPoint<S> {
+        let (p, q) = (self.degree_xi, self.degree_eta);
+        let span_xi = BsplineBasis::find_span(&self.knots_xi, p, self.data.num_rows() - 1, input.x());
+        let span_eta = BsplineBasis::find_span(&self.knots_eta, q, self.data.num_columns() - 1, input.y());
+        let n_xi = BsplineBasis::nonvanishing(&self.knots_xi, span_xi, p, input.x());
+        let n_eta = BsplineBasis::nonvanishing(&self.knots_eta, span_eta, q, input.y());
+        output.reset();
+        for (k, bk) in n_xi.iter().enumerate() {
+            for (l, bl) in n_eta.iter().enumerate() {
+                *output += self.data[(span_xi - p + k, span_eta - q + l)]*(bk*bl);
+            }
+        }
+        output
+    }
+}
+
+impl<const S: usize> BsplineSurf<S> {
+    ///
+    /// Creates a B-spline surface, checking that knot vectors and degrees are compatible
+    /// with the matrix of control points.
+    /// 
+    pub fn create(data: Array2D<RealPoint<S>>, knots_xi: Vec<f64>, knots_eta: Vec<f64>,
+                  degree_xi: usize, degree_eta: usize) -> Option<BsplineSurf<S>> {
+        if !is_valid_spline(data.num_rows(), &knots_xi, degree_xi) ||
+            !is_valid_spline(data.num_columns(), &knots_eta, degree_eta) {
+            return None;
+        }
+        Some(BsplineSurf { data, knots_xi, knots_eta, degree_xi, degree_eta })
+    }
+
+    ///
+    /// Returns the parametric domain along Xi.
+    /// 
+    pub fn knot_range_xi(&self) -> RealRange {
+        RealRange { a: self.knots_xi[self.degree_xi], b: self.knots_xi[self.data.num_rows()] }
+    }
+
+    ///
+    /// Returns the parametric domain along Eta.
+    /// 
+    pub fn knot_range_eta(&self) -> RealRange {
+        RealRange { a: self.knots_eta[self.degree_eta], b: self.knots_eta[self.data.num_columns()] }
+    }
+}
+
+impl ParametricSurface for BsplineSurf<3> {
+    fn domain(&self) -> (RealRange, RealRange) {
+        (self.knot_range_xi(), self.knot_range_eta())
+    }
+
+    fn derivatives(&self, xi: f64, eta: f64, n: usize) -> Vec<Vec<RealPoint3d>> {
+        let (p, q) = (self.degree_xi, self.degree_eta);
+        let span_xi = BsplineBasis::find_span(&self.knots_xi, p, self.data.num_rows() - 1, xi);
+        let span_eta = BsplineBasis::find_span(&self.knots_eta, q, self.data.num_columns() - 1, eta);
+        let d_xi = BsplineBasis::ders_nonvanishing(&self.knots_xi, span_xi, p, xi, n);
+        let d_eta = BsplineBasis::ders_nonvanishing(&self.knots_eta, span_eta, q, eta, n);
+        let mut ret = vec![vec![RealPoint3d::origin(); n + 1]; n + 1];
+        for k in 0..=n {
+            for l in 0..=n - k {
+                for (r, br) in d_xi[k].iter().enumerate() {
+                    for (s, bs) in d_eta[l].iter().enumerate() {
+                        ret[k][l] += self.data[(span_xi - p + r, span_eta - q + s)]*(br*bs);
+                    }
+                }
+            }
+        }
+        ret
+    }
+
+    ///
+    /// Returns the distinct knots in the domain along both directions.
+    ///
+    fn breakpoints(&self) -> (Vec<f64>, Vec<f64>) {
+        (distinct_knots(&self.knots_xi[self.degree_xi..=self.data.num_rows()]),
+         distinct_knots(&self.knots_eta[self.degree_eta..=self.data.num_columns()]))
+    }
+}
+
+///
+/// Returns true iif the knots form a non-decreasing sequence.
+/// 
+pub(crate) fn is_knot_vector(knots: &[f64]) -> bool {
+    knots.windows(2).all(|w| w[0] <= w[1])
+}
+
+///
+/// Checks that count control points, the knots and the degree define a spline.
+/// 
+pub(crate) fn is_valid_spline(count: usize, knots: &[f64], degree: usize) -> bool {
+    if count <= degree {
+        log::warn!("At least degree + 1 control points are needed");
+        return false;
+    }
+    if knots.len() != count + degree + 1 {
+        log::warn!("The knot vector must contain control points + degree + 1 knots");
+        return false;
+    }
+    if !is_knot_vector(knots) {
+        log::warn!("Knots must be a non-decreasing sequence");
+        return false;
+    }
+    if knots[degree] >= knots[count] {
+        log::warn!("The parametric domain is empty");
+        return false;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bspline::{BsplineBasis, BsplineCurve, BsplineSurf};
+    use crate::bezier::{BezierCurveDemo1, BezierTeapot};
+    use crate::core::{Mapping, RealPoint2d, p1, p2};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_basis() {
+        let knots = vec![0., 0., 0., 1., 2., 3., 4., 4., 5., 5., 5.];
+        let p = 2;
+        let n = knots.len() - p - 2;
+        assert_eq!(BsplineBasis::find_span(&knots, p, n, 0.), 2);
+        assert_eq!(BsplineBasis::find_span(&knots, p, n, 2.5), 4);
+        assert_eq!(BsplineBasis::find_span(&knots, p, n, 4.), 7);
+        assert_eq!(BsplineBasis::find_span(&knots, p, n, 5.), 7);
+        assert!(BsplineBasis::create(knots.clone(), p, n + 1).is_none());
+        for k in 0..=100 {
+            let xi = 5.*(k as f64)/100.;
+            let span = BsplineBasis::find_span(&knots, p, n, xi);
+            let nonvanishing = BsplineBasis::nonvanishing(&knots, span, p, xi);
+            assert_approx_eq!(f64, nonvanishing.iter().sum::<f64>(), 1., epsilon = 1E-12);
+            let mut sum = 0f64;
+            for i in 0..=n {
+                let b = BsplineBasis::create(knots.clone(), p, i).unwrap().evaluate(&p1(xi)).x();
+                if i + p >= span && i <= span {
+                    assert_approx_eq!(f64, b, nonvanishing[i + p - span], epsilon = 1E-12);
+                }
+                sum += b;
+            }
+            assert_approx_eq!(f64, sum, 1., epsilon = 1E-12);
+        }
+        assert_approx_eq!(f64, BsplineBasis::compute(&knots, 4, p, 2.5), 0.125, epsilon = 1E-12);
+    }
+
+    #[test]
+    fn test_bezier() {
+        let bez = BezierCurveDemo1::create();
+        let knots = vec![0., 0., 0., 0., 0., 0., 1., 1., 1., 1., 1., 1.];
+        let bsp = BsplineCurve::create(bez.p.clone(), knots.clone(), 5).unwrap();
+        assert!(BsplineCurve::create(bez.p.clone(), knots[1..].to_vec(), 5).is_none());
+        for i in 0..=100 {
+            let xi = p1((i as f64)/100.);
+            assert_approx_eq!(RealPoint2d, bsp.evaluate(&xi), bez.evaluate_de_casteljau(&xi), epsilon = 1E-12);
+        }
+
+        let patch = &BezierTeapot::build_patches()[4];
+        let cubic = vec![0., 0., 0., 0., 1., 1., 1., 1.];
+        let surf = BsplineSurf::create(patch.data.clone(), cubic.clone(), cubic, 3, 3).unwrap();
+        for i in 0..=10 {
+            for j in 0..=10 {
+                let xi = p2((i as f64)/10., (j as f64)/10.);
+                assert_approx_eq!(crate::core::RealPoint3d, surf.evaluate(&xi), patch.evaluate(&xi), epsilon = 1E-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,233 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::bezier::{BezierCurve, BezierSurf};
+use crate::bspline::{BsplineCurve, BsplineSurf};
+use crate::core::RealPoint;
+use array2d::Array2D;
+
+///
+/// Bezier extraction operators of a clamped knot vector (Borden et al., 2011). For the
+/// element e, i.e. the e-th non-empty knot span, the p + 1 basis functions that do not
+/// vanish on it are N = C^e B, where B are the Bernstein polynomials of degree p mapped to
+/// the span. The Bezier control points of the element are therefore (C^e)ᵀ P^e.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use isogeometric_analysis::bspline::BezierExtraction;
+/// let ext = BezierExtraction::new(&[0., 0., 0., 1., 2., 3., 4., 4., 4.], 2);
+/// assert_eq!(ext.operators.len(), 4);
+/// assert_eq!(ext.operators[0][(1, 2)], 0.5);
+/// ```
+/// 
+#[derive(Debug, Clone)]
+pub struct BezierExtraction {
+    pub degree: usize,
+    /// Index of the knot span of each element.
+    pub spans: Vec<usize>,
+    /// Parametric interval of each element.
+    pub ranges: Vec<(f64, f64)>,
+    pub operators: Vec<Array2D<f64>>
+}
+
+impl BezierExtraction {
+    ///
+    /// Computes the operators by inserting each interior knot until its multiplicity is p.
+    /// 
+    pub fn new(knots: &[f64], p: usize) -> BezierExtraction {
+        let m = knots.len();
+        let identity = || {
+            let mut c = Array2D::filled_with(0f64, p + 1, p + 1);
+            for i in 0..=p {
+                c[(i, i)] = 1f64;
+            }
+            c
+        };
+        let mut operators = vec![identity()];
+        let mut spans = Vec::new();
+        let mut ranges = Vec::new();
+        // a and b index the knots at the start and the end of the current element.
+        let (mut a, mut b) = (p, p + 1);
+        while b < m - p {
+            let i = b;
+            while b + 1 < m && knots[b + 1] == knots[b] {
+                b += 1;
+            }
+            spans.push(a);
+            ranges.push((knots[a], knots[i]));
+            let last = b >= m - p - 1;
+            if !last {
+                operators.push(identity());
+            }
+            let mult = b - i + 1;
+            if mult < p {
+                let numer = knots[b] - knots[a];
+                let mut alphas = vec![0f64; p];
+                for j in (mult + 1..=p).rev() {
+                    alphas[j - mult - 1] = numer/(knots[a + j] - knots[a]);
+                }
+                let r = p - mult;
+                let nb = operators.len() - if last { 1 } else { 2 };
+                for j in 1..=r {
+                    let save = r - j;
+                    let s = mult + j;
+                    for k in (s..=p).rev() {
+                        let alpha = alphas[k - s];
+                        for row in 0..=p {
+                            let v = alpha*operators[nb][(row, k)] + (1f64 - alpha)*operators[nb][(row, k - 1)];
+                            operators[nb][(row, k)] = v;
+                        }
+                    }
+                    if !last {
+                        for l in 0..=j {
+                            let v = operators[nb][(p - j + l, p)];
+                            operators[nb + 1][(save + l, save)] = v;
+                        }
+                    }
+                }
+            }
+            a = b;
+            b += 1;
+        }
+        BezierExtraction { degree: p, spans, ranges, operators }
+    }
+
+    ///
+    /// Returns the Bezier control points of element e from the control points of the
+    /// spline, which may be in homogeneous coordinates.
+    /// 
+    pub fn extract<const S: usize>(&self, e: usize, p: &[RealPoint<S>]) -> Vec<RealPoint<S>> {
+        let first = self.spans[e] - self.degree;
+        let c = &self.operators[e];
+        (0..=self.degree).map(|j| {
+            let mut q = RealPoint::<S>::origin();
+            for i in 0..=self.degree {
+                q += p[first + i]*c[(i, j)];
+            }
+            q
+        }).collect()
+    }
+}
+
+impl<const S: usize> BsplineCurve<S> {
+    ///
+    /// Returns the Bezier segments of the curve, one per non-empty knot span. Each segment
+    /// is parametrised over [0, 1] instead of its knot span.
+    /// 
+    pub fn bezier_segments(&self) -> Vec<BezierCurve<S>> {
+        let ext = BezierExtraction::new(&self.knots, self.degree);
+        (0..ext.spans.len()).map(|e| BezierCurve::create(ext.extract(e, &self.p))).collect()
+    }
+}
+
+impl<const S: usize> BsplineSurf<S> {
+    ///
+    /// Returns the Bezier patches of the surface, one per pair of non-empty knot spans,
+    /// with the elements along Eta varying fastest. Each patch is parametrised over [0, 1]².
+    /// 
+    pub fn bezier_patches(&self) -> Vec<BezierSurf<S>> {
+        extract_patches(&self.data, &self.knots_xi, &self.knots_eta, self.degree_xi, self.degree_eta)
+            .into_iter()
+            .map(|data| BezierSurf { data })
+            .collect()
+    }
+}
+
+///
+/// Returns the Bezier control nets of a tensor product spline given by its control
+/// points, which may be in homogeneous coordinates.
+/// 
+pub(crate) fn extract_patches<const S: usize>(data: &Array2D<RealPoint<S>>, knots_xi: &[f64], knots_eta: &[f64],
+                                              p: usize, q: usize) -> Vec<Array2D<RealPoint<S>>> {
+    let ext_xi = BezierExtraction::new(knots_xi, p);
+    let ext_eta = BezierExtraction::new(knots_eta, q);
+    let mut patches = Vec::new();
+    for e in 0..ext_xi.spans.len() {
+        // Extract along Xi for every column, then along Eta for every row.
+        let columns = data.as_columns().iter().map(|c| ext_xi.extract(e, c)).collect::<Vec<Vec<RealPoint<S>>>>();
+        let partial = Array2D::from_columns(&columns);
+        for f in 0..ext_eta.spans.len() {
+            let rows = partial.as_rows().iter().map(|r| ext_eta.extract(f, r)).collect::<Vec<Vec<RealPoint<S>>>>();
+            patches.push(Array2D::from_rows(&rows));
+        }
+    }
+    patches
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bspline::{BezierExtraction, BsplineCurve, BsplineSurf};
+    use crate::bezier::BezierTeapot;
+    use crate::core::{Mapping, p1, p2};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_operators() {
+        // Borden et al., quadratic example with four elements.
+        let ext = BezierExtraction::new(&[0., 0., 0., 1., 2., 3., 4., 4., 4.], 2);
+        let expected = [
+            [[1., 0., 0.], [0., 1., 0.5], [0., 0., 0.5]],
+            [[0.5, 0., 0.], [0.5, 1., 0.5], [0., 0., 0.5]],
+            [[0.5, 0., 0.], [0.5, 1., 0.5], [0., 0., 0.5]],
+            [[0.5, 0., 0.], [0.5, 1., 0.], [0., 0., 1.]]
+        ];
+        assert_eq!(ext.spans, vec![2, 3, 4, 5]);
+        assert_eq!(ext.ranges[3], (3., 4.));
+        for (c, e) in ext.operators.iter().zip(expected.iter()) {
+            for i in 0..3 {
+                for j in 0..3 {
+                    assert_approx_eq!(f64, c[(i, j)], e[i][j], epsilon = 1E-15);
+                }
+            }
+        }
+
+        // Repeated knots and an empty span.
+        let ext = BezierExtraction::new(&[0., 0., 0., 0., 1., 1., 2., 2., 2., 3., 3., 3., 3.], 3);
+        assert_eq!(ext.spans, vec![3, 5, 8]);
+    }
+
+    #[test]
+    fn test_segments() {
+        let cpoints = vec![p2(0., 0.), p2(1., 1.), p2(2., 0.5), p2(3., 0.5), p2(0.5, 1.5), p2(1.5, 0.), p2(2., 2.)];
+        let knots = vec![0., 0., 0., 0., 1., 1., 2.5, 4., 4., 4., 4.];
+        let bsp = BsplineCurve::create(cpoints, knots, 3).unwrap();
+        let segments = bsp.bezier_segments();
+        let ext = BezierExtraction::new(&bsp.knots, 3);
+        assert_eq!(segments.len(), 3);
+        for (seg, (a, b)) in segments.iter().zip(ext.ranges.iter()) {
+            for k in 0..=10 {
+                let t = k as f64/10.;
+                let (x, y) = (seg.evaluate(&p1(t)), bsp.evaluate(&p1(a + (b - a)*t)));
+                assert_approx_eq!(f64, x.dist(&y), 0., epsilon = 1E-12);
+            }
+        }
+
+        // A single-element spline is a Bezier patch already.
+        let patch = &BezierTeapot::build_patches()[5];
+        let knots = vec![0., 0., 0., 0., 1., 1., 1., 1.];
+        let bsp = BsplineSurf::create(patch.data.clone(), knots.clone(), knots, 3, 3).unwrap();
+        let patches = bsp.bezier_patches();
+        assert_eq!(patches.len(), 1);
+        assert_eq!(patches[0].data, patch.data);
+    }
+}
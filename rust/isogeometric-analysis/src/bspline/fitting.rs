@@ -0,0 +1,344 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::bspline::{BsplineBasis, BsplineCurve, BsplineSurf, Parametrization, basis_row};
+use crate::core::{LupDecomposition, Mapping, RealPoint, RealPoint2d, gauss_legendre, p1};
+use array2d::Array2D;
+
+///
+/// Distances between the data and a fitted geometry.
+/// 
+#[derive(Debug, Clone)]
+pub struct FitResidual {
+    /// Distance of each data point from its image on the geometry.
+    pub errors: Vec<f64>,
+    pub max: f64,
+    pub rms: f64
+}
+
+impl FitResidual {
+    fn new(errors: Vec<f64>) -> FitResidual {
+        let max = errors.iter().fold(0f64, |m, e| m.max(*e));
+        let rms = (errors.iter().map(|e| e*e).sum::<f64>()/(errors.len().max(1) as f64)).sqrt();
+        FitResidual { errors, max, rms }
+    }
+}
+
+///
+/// Returns the clamped knot vector of degree p for count control points approximating
+/// data with the given parameters, so that every knot span contains at least one
+/// parameter (Piegl and Tiller, eq. 9.68 and 9.69).
+/// 
+fn approximation_knots(params: &[f64], p: usize, count: usize) -> Vec<f64> {
+    let d = params.len() as f64/((count - p) as f64);
+    let mut knots = vec![0f64; p + 1];
+    for j in 1..count - p {
+        let i = ((j as f64)*d).floor() as usize;
+        let alpha = (j as f64)*d - i as f64;
+        knots.push((1f64 - alpha)*params[i - 1] + alpha*params[i]);
+    }
+    knots.extend(vec![1f64; p + 1]);
+    knots
+}
+
+///
+/// Returns the matrix of the integrals over the domain of the products of the derivatives
+/// of a given order of the count basis functions.
+/// 
+pub(crate) fn gram(knots: &[f64], p: usize, count: usize, order: usize) -> Vec<Vec<f64>> {
+    let (nodes, weights) = gauss_legendre(p + 1);
+    let mut g = vec![vec![0f64; count]; count];
+    for span in p..count {
+        let (a, b) = (knots[span], knots[span + 1]);
+        if b <= a {
+            continue;
+        }
+        for (x, w) in nodes.iter().zip(weights.iter()) {
+            let u = 0.5*(a + b) + 0.5*(b - a)*x;
+            let ders = BsplineBasis::ders_nonvanishing(knots, span, p, u, order);
+            for (i, ni) in ders[order].iter().enumerate() {
+                for (j, nj) in ders[order].iter().enumerate() {
+                    g[span - p + i][span - p + j] += 0.5*(b - a)*w*ni*nj;
+                }
+            }
+        }
+    }
+    g
+}
+
+impl<const S: usize> BsplineCurve<S> {
+    ///
+    /// Fits a B-spline curve of degree p with count control points to the points in the
+    /// least-squares sense, solving the normal equations. A positive smoothing adds the
+    /// bending energy ∫|C''|² weighted by smoothing to the functional, trading accuracy for
+    /// fairness; polylines (p = 1) use the stretching energy ∫|C'|² instead, while p = 0
+    /// ignores smoothing. Needs at least count points, unless smoothing makes the system
+    /// regular.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use isogeometric_analysis::bspline::{BsplineCurve, Parametrization};
+    /// use isogeometric_analysis::core::p2;
+    /// let points = (0..50).map(|i| p2(i as f64, (i as f64*0.2).sin())).collect::<Vec<_>>();
+    /// let (bsp, residual) = BsplineCurve::fit(&points, 3, 10, Parametrization::ChordLength, 0.).unwrap();
+    /// println!("Maximum error: {}", residual.max);
+    /// ```
+    /// 
+    pub fn fit(points: &[RealPoint<S>], p: usize, count: usize, method: Parametrization, smoothing: f64) -> Option<(BsplineCurve<S>, FitResidual)> {
+        if count <= p || points.len() < count.min(2) || (points.len() < count && smoothing <= 0f64) {
+            log::warn!("At least degree + 1 control points and as many points as control points are needed");
+            return None;
+        }
+        let params = method.params(points)?;
+        let knots = if points.len() >= count { approximation_knots(&params, p, count) } else { uniform_knots(p, count) };
+        let rows = params.iter().map(|u| basis_row(&knots, p, count, *u)).collect::<Vec<Vec<f64>>>();
+
+        let mut a = Array2D::filled_with(0f64, count, count);
+        let mut b = vec![RealPoint::<S>::origin(); count];
+        for (row, q) in rows.iter().zip(points.iter()) {
+            for (i, ni) in row.iter().enumerate().filter(|(_, n)| **n != 0f64) {
+                b[i] += *q*(*ni);
+                for (j, nj) in row.iter().enumerate() {
+                    a[(i, j)] += ni*nj;
+                }
+            }
+        }
+        if smoothing > 0f64 && p >= 1 {
+            let k = gram(&knots, p, count, p.min(2));
+            for i in 0..count {
+                for j in 0..count {
+                    a[(i, j)] += smoothing*k[i][j];
+                }
+            }
+        }
+
+        let bsp = BsplineCurve::create(LupDecomposition::new(&a)?.solve_points(&b), knots, p)?;
+        let errors = params.iter().zip(points.iter()).map(|(u, q)| bsp.evaluate(&p1(*u)).dist(q)).collect();
+        Some((bsp, FitResidual::new(errors)))
+    }
+}
+
+fn uniform_knots(p: usize, count: usize) -> Vec<f64> {
+    let mut knots = vec![0f64; p + 1];
+    knots.extend((1..count - p).map(|j| j as f64/((count - p) as f64)));
+    knots.extend(vec![1f64; p + 1]);
+    knots
+}
+
+impl<const S: usize> BsplineSurf<S> {
+    ///
+    /// Fits a B-spline surface to scattered points with the given parameters in [0, 1]²,
+    /// with degrees and numbers of control points given along Xi and Eta. A positive
+    /// smoothing adds the thin-plate energy ∫∫ |Sξξ|² + 2|Sξη|² + |Sηη|² to the functional,
+    /// which also regularises regions without data.
+    /// 
+    pub fn fit_scattered(points: &[RealPoint<S>], params: &[RealPoint2d], degrees: (usize, usize), counts: (usize, usize),
+                         smoothing: f64) -> Option<(BsplineSurf<S>, FitResidual)> {
+        let ((p, q), (n, m)) = (degrees, counts);
+        if n <= p || m <= q || points.len() != params.len() {
+            log::warn!("At least degree + 1 control points per direction and a parameter per point are needed");
+            return None;
+        }
+        let knots_xi = uniform_knots(p, n);
+        let knots_eta = uniform_knots(q, m);
+        BsplineSurf::fit_with_knots(points, params, degrees, counts, knots_xi, knots_eta, smoothing)
+    }
+
+    ///
+    /// Fits a B-spline surface to a grid of points whose rows run along Xi. Parameters are
+    /// averaged over rows and columns of the grid with the given method, skipping lines
+    /// with coincident consecutive points such as poles, and knots follow their
+    /// distribution.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use isogeometric_analysis::bspline::{BsplineSurf, Parametrization};
+    /// use isogeometric_analysis::core::{RealPoint3d, p3};
+    /// use array2d::Array2D;
+    /// let rows = (0..10).map(|i| (0..10).map(|j| p3(i as f64, j as f64, ((i*j) as f64).sqrt())).collect())
+    ///     .collect::<Vec<Vec<RealPoint3d>>>();
+    /// let grid = Array2D::from_rows(&rows);
+    /// let (surf, residual) = BsplineSurf::fit_grid(&grid, (3, 3), (6, 6), Parametrization::ChordLength, 0.).unwrap();
+    /// ```
+    /// 
+    pub fn fit_grid(points: &Array2D<RealPoint<S>>, degrees: (usize, usize), counts: (usize, usize), method: Parametrization,
+                    smoothing: f64) -> Option<(BsplineSurf<S>, FitResidual)> {
+        let ((p, q), (n, m)) = (degrees, counts);
+        let (rows, cols) = (points.num_rows(), points.num_columns());
+        if n <= p || m <= q || rows < n || cols < m {
+            log::warn!("At least degree + 1 control points per direction and as many points as control points are needed");
+            return None;
+        }
+        let average = |lines: Vec<Vec<RealPoint<S>>>| -> Option<Vec<f64>> {
+            let mut sum = vec![0f64; lines[0].len()];
+            let mut count = 0;
+            for params in lines.iter().filter_map(|line| method.params(line)) {
+                sum.iter_mut().zip(params.iter()).for_each(|(s, u)| *s += u);
+                count += 1;
+            }
+            if count == 0 {
+                return Parametrization::Uniform.params(&lines[0]);
+            }
+            sum.iter_mut().for_each(|s| *s /= count as f64);
+            Some(sum)
+        };
+        let u = average(points.as_columns())?;
+        let v = average(points.as_rows())?;
+        let knots_xi = approximation_knots(&u, p, n);
+        let knots_eta = approximation_knots(&v, q, m);
+        let mut data = Vec::with_capacity(rows*cols);
+        let mut params = Vec::with_capacity(rows*cols);
+        for i in 0..rows {
+            for j in 0..cols {
+                data.push(points[(i, j)]);
+                params.push(RealPoint2d::point2d(u[i], v[j]));
+            }
+        }
+        BsplineSurf::fit_with_knots(&data, &params, degrees, counts, knots_xi, knots_eta, smoothing)
+    }
+
+    fn fit_with_knots(points: &[RealPoint<S>], params: &[RealPoint2d], degrees: (usize, usize), counts: (usize, usize),
+                      knots_xi: Vec<f64>, knots_eta: Vec<f64>, smoothing: f64) -> Option<(BsplineSurf<S>, FitResidual)> {
+        let ((p, q), (n, m)) = (degrees, counts);
+        // Unknown i*m + j is the control point in row i along Xi and column j.
+        let size = n*m;
+        let mut a = Array2D::filled_with(0f64, size, size);
+        let mut b = vec![RealPoint::<S>::origin(); size];
+        for (x, uv) in points.iter().zip(params.iter()) {
+            let span_xi = BsplineBasis::find_span(&knots_xi, p, n - 1, uv.x());
+            let span_eta = BsplineBasis::find_span(&knots_eta, q, m - 1, uv.y());
+            let n_xi = BsplineBasis::nonvanishing(&knots_xi, span_xi, p, uv.x());
+            let n_eta = BsplineBasis::nonvanishing(&knots_eta, span_eta, q, uv.y());
+            let mut support = Vec::with_capacity((p + 1)*(q + 1));
+            for (k, bk) in n_xi.iter().enumerate() {
+                for (l, bl) in n_eta.iter().enumerate() {
+                    support.push(((span_xi - p + k)*m + span_eta - q + l, bk*bl));
+                }
+            }
+            for (i, bi) in &support {
+                b[*i] += *x*(*bi);
+                for (j, bj) in &support {
+                    a[(*i, *j)] += bi*bj;
+                }
+            }
+        }
+        if smoothing > 0f64 {
+            let g_xi = [gram(&knots_xi, p, n, 0), gram(&knots_xi, p, n, 1), gram(&knots_xi, p, n, 2)];
+            let g_eta = [gram(&knots_eta, q, m, 0), gram(&knots_eta, q, m, 1), gram(&knots_eta, q, m, 2)];
+            for i in 0..size {
+                let (i1, j1) = (i/m, i%m);
+                for j in 0..size {
+                    let (i2, j2) = (j/m, j%m);
+                    let energy = g_xi[2][i1][i2]*g_eta[0][j1][j2]
+                        + 2f64*g_xi[1][i1][i2]*g_eta[1][j1][j2]
+                        + g_xi[0][i1][i2]*g_eta[2][j1][j2];
+                    a[(i, j)] += smoothing*energy;
+                }
+            }
+        }
+
+        let cpoints = LupDecomposition::new(&a)?.solve_points(&b);
+        let data = Array2D::from_row_major(&cpoints, n, m);
+        let surf = BsplineSurf::create(data, knots_xi, knots_eta, p, q)?;
+        let errors = params.iter().zip(points.iter()).map(|(uv, x)| surf.evaluate(uv).dist(x)).collect();
+        Some((surf, FitResidual::new(errors)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bspline::{BsplineCurve, BsplineSurf, Parametrization};
+    use crate::core::{Mapping, ParametricCurve, RealPoint3d, p1, p2, p3};
+    use array2d::Array2D;
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_curve() {
+        // Cubic polynomials are reproduced exactly.
+        let points = (0..=40).map(|k| {
+            let t = k as f64/40.;
+            p2(t, t*t*t - t)
+        }).collect::<Vec<_>>();
+        let (bsp, residual) = BsplineCurve::fit(&points, 3, 7, Parametrization::Uniform, 0.).unwrap();
+        assert_eq!(bsp.p.len(), 7);
+        assert_eq!(residual.errors.len(), 41);
+        assert_approx_eq!(f64, residual.max, 0., epsilon = 1E-12);
+        assert_approx_eq!(f64, bsp.evaluate(&p1(0.3)).y(), 0.027 - 0.3, epsilon = 1E-12);
+
+        // Smoothing noisy samples of a line flattens the curve.
+        let noisy = (0..=40).map(|k| p2(k as f64/40., if k%2 == 0 { 0.01 } else { -0.01 })).collect::<Vec<_>>();
+        let (rough, r0) = BsplineCurve::fit(&noisy, 3, 20, Parametrization::Uniform, 0.).unwrap();
+        let (smooth, r1) = BsplineCurve::fit(&noisy, 3, 20, Parametrization::Uniform, 1E-2).unwrap();
+        assert!(r1.rms > r0.rms);
+        let bending = |c: &BsplineCurve<2>| (0..100).map(|k| c.derivatives(k as f64/99., 2)[2].norm()).fold(0f64, f64::max);
+        assert!(bending(&smooth) < 0.1*bending(&rough));
+        assert!(r1.max < 0.012);
+
+        assert!(BsplineCurve::fit(&points[..5], 3, 7, Parametrization::Uniform, 0.).is_none());
+        assert!(BsplineCurve::fit(&points[..5], 3, 7, Parametrization::Uniform, 1E-3).is_some());
+
+        // Polylines are smoothed by their length.
+        let (polyline, r2) = BsplineCurve::fit(&noisy, 1, 20, Parametrization::Uniform, 1E-2).unwrap();
+        let (_, r3) = BsplineCurve::fit(&noisy, 1, 20, Parametrization::Uniform, 0.).unwrap();
+        assert!(r2.rms > r3.rms);
+        assert!(polyline.p.iter().all(|p| p.y().abs() < 0.01));
+    }
+
+    #[test]
+    fn test_surface() {
+        // z = x² + y² is biquadratic and reproduced exactly from a grid.
+        let rows = (0..12).map(|i| (0..9).map(|j| {
+            let (x, y) = (i as f64/11., j as f64/8.);
+            p3(x, y, x*x + y*y)
+        }).collect()).collect::<Vec<Vec<RealPoint3d>>>();
+        let grid = Array2D::from_rows(&rows);
+        let (surf, residual) = BsplineSurf::fit_grid(&grid, (2, 2), (5, 4), Parametrization::Uniform, 0.).unwrap();
+        assert_eq!((surf.data.num_rows(), surf.data.num_columns()), (5, 4));
+        assert_approx_eq!(f64, residual.max, 0., epsilon = 1E-12);
+
+        // The pole of a cone is skipped when averaging parameters along Eta.
+        let rows = (0..8).map(|i| (0..8).map(|j| {
+            let (r, a) = (i as f64/7., j as f64/7.);
+            p3(r*a.cos(), r*a.sin(), r)
+        }).collect()).collect::<Vec<Vec<RealPoint3d>>>();
+        let (cone, residual) = BsplineSurf::fit_grid(&Array2D::from_rows(&rows), (3, 3), (5, 5), Parametrization::ChordLength, 0.).unwrap();
+        assert!(residual.max < 1E-3);
+        assert_approx_eq!(RealPoint3d, cone.evaluate(&p2(0., 0.5)), p3(0., 0., 0.), epsilon = 1E-12);
+
+        // Scattered samples of a plane, with a hole in the data filled by smoothing.
+        let mut points = Vec::new();
+        let mut params = Vec::new();
+        for k in 0..200 {
+            let (u, v) = ((k as f64*0.618034).fract(), (k as f64*0.414214).fract());
+            if (u - 0.5).abs() < 0.2 && (v - 0.5).abs() < 0.2 {
+                continue;
+            }
+            points.push(p3(u, v, u + 2.*v));
+            params.push(p2(u, v));
+        }
+        let (surf, residual) = BsplineSurf::fit_scattered(&points, &params, (3, 3), (8, 8), 1E-6).unwrap();
+        assert!(residual.max < 1E-6);
+        assert_approx_eq!(f64, surf.evaluate(&p2(0.5, 0.5)).z(), 1.5, epsilon = 1E-6);
+    }
+}
@@ -0,0 +1,218 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::bspline::{BsplineBasis, BsplineCurve};
+use crate::core::{LupDecomposition, RealPoint};
+use array2d::Array2D;
+
+///
+/// Choice of the parameters assigned to data points.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Parametrization {
+    /// Evenly spaced parameters.
+    Uniform,
+    /// Parameters proportional to the distance between consecutive points.
+    ChordLength,
+    /// Parameters proportional to the square root of the distance, which behaves better
+    /// at sharp turns.
+    Centripetal
+}
+
+impl Parametrization {
+    ///
+    /// Returns the parameters in [0, 1] of the points, or None if consecutive points
+    /// coincide and the parametrisation is not uniform.
+    /// 
+    pub fn params<const S: usize>(&self, points: &[RealPoint<S>]) -> Option<Vec<f64>> {
+        let n = points.len();
+        if n < 2 {
+            return Some(vec![0f64; n]);
+        }
+        let steps = points.windows(2).map(|w| match self {
+            Parametrization::Uniform => 1f64,
+            Parametrization::ChordLength => w[0].dist(&w[1]),
+            Parametrization::Centripetal => w[0].dist(&w[1]).sqrt()
+        }).collect::<Vec<f64>>();
+        if steps.contains(&0f64) {
+            log::warn!("Consecutive data points must be distinct");
+            return None;
+        }
+        let total = steps.iter().sum::<f64>();
+        let mut params = vec![0f64];
+        let mut acc = 0f64;
+        for d in &steps[..n - 2] {
+            acc += d;
+            params.push(acc/total);
+        }
+        params.push(1f64);
+        Some(params)
+    }
+}
+
+///
+/// Returns the clamped knot vector of degree p for count control points whose inner knots
+/// average p consecutive parameters, starting from params[first]. With first = 1 and
+/// count = params.len() this is the knot vector of Piegl and Tiller, eq. 9.8.
+/// 
+pub(crate) fn averaged_knots(params: &[f64], p: usize, count: usize, first: usize) -> Vec<f64> {
+    let mut knots = vec![0f64; p + 1];
+    for j in 0..count - p - 1 {
+        knots.push(params[first + j..first + j + p].iter().sum::<f64>()/(p as f64));
+    }
+    knots.extend(vec![1f64; p + 1]);
+    knots
+}
+
+impl<const S: usize> BsplineCurve<S> {
+    ///
+    /// Builds the B-spline curve of degree p interpolating the points, with averaged
+    /// knots. At least p + 1 points are needed. Returns None if the data cannot be
+    /// interpolated.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use isogeometric_analysis::bspline::{BsplineCurve, Parametrization};
+    /// use isogeometric_analysis::core::p2;
+    /// let points = vec![p2(0., 0.), p2(1., 2.), p2(3., 2.), p2(4., 0.), p2(6., 1.)];
+    /// let bsp = BsplineCurve::interpolate(&points, 3, Parametrization::Centripetal).unwrap();
+    /// ```
+    /// 
+    pub fn interpolate(points: &[RealPoint<S>], p: usize, method: Parametrization) -> Option<BsplineCurve<S>> {
+        if points.len() <= p {
+            log::warn!("At least degree + 1 points are needed");
+            return None;
+        }
+        let params = method.params(points)?;
+        let n = points.len();
+        let knots = averaged_knots(&params, p, n, 1);
+        let rows = params.iter().map(|u| basis_row(&knots, p, n, *u)).collect::<Vec<Vec<f64>>>();
+        let cpoints = LupDecomposition::new(&Array2D::from_rows(&rows))?.solve_points(points);
+        BsplineCurve::create(cpoints, knots, p)
+    }
+
+    ///
+    /// Builds the B-spline curve of degree p interpolating the points with the first
+    /// derivatives d0 and dn at the ends, following Piegl and Tiller, section 9.2.2. The
+    /// derivatives refer to the parameter in [0, 1]. At least p - 1 points are needed.
+    /// 
+    pub fn interpolate_with_derivatives(points: &[RealPoint<S>], p: usize, method: Parametrization,
+                                        d0: RealPoint<S>, dn: RealPoint<S>) -> Option<BsplineCurve<S>> {
+        if p == 0 || points.len() + 1 < p {
+            log::warn!("At least degree - 1 points and a positive degree are needed");
+            return None;
+        }
+        let params = method.params(points)?;
+        let count = points.len() + 2;
+        let knots = averaged_knots(&params, p, count, 0);
+
+        // Rows: Q_0, D_0, Q_1, ..., Q_n-1, D_n, Q_n.
+        let mut rows = Vec::with_capacity(count);
+        let mut rhs = Vec::with_capacity(count);
+        for (k, (u, q)) in params.iter().zip(points.iter()).enumerate() {
+            if k == points.len() - 1 {
+                let mut row = vec![0f64; count];
+                row[count - 2] = -1f64;
+                row[count - 1] = 1f64;
+                rows.push(row);
+                rhs.push(dn*((1f64 - knots[count - 1])/(p as f64)));
+            }
+            rows.push(basis_row(&knots, p, count, *u));
+            rhs.push(*q);
+            if k == 0 {
+                let mut row = vec![0f64; count];
+                row[0] = -1f64;
+                row[1] = 1f64;
+                rows.push(row);
+                rhs.push(d0*(knots[p + 1]/(p as f64)));
+            }
+        }
+        let cpoints = LupDecomposition::new(&Array2D::from_rows(&rows))?.solve_points(&rhs);
+        BsplineCurve::create(cpoints, knots, p)
+    }
+}
+
+///
+/// Returns the values of the count basis functions of degree p in u.
+/// 
+pub(crate) fn basis_row(knots: &[f64], p: usize, count: usize, u: f64) -> Vec<f64> {
+    let span = BsplineBasis::find_span(knots, p, count - 1, u);
+    let mut row = vec![0f64; count];
+    for (j, b) in BsplineBasis::nonvanishing(knots, span, p, u).iter().enumerate() {
+        row[span - p + j] = *b;
+    }
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bspline::{BsplineCurve, Parametrization};
+    use crate::core::{Mapping, ParametricCurve, p1, p2, p3};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_params() {
+        let points = vec![p2(0., 0.), p2(1., 0.), p2(1., 4.)];
+        assert_eq!(Parametrization::Uniform.params(&points).unwrap(), vec![0., 0.5, 1.]);
+        assert_eq!(Parametrization::ChordLength.params(&points).unwrap(), vec![0., 0.2, 1.]);
+        assert_eq!(Parametrization::Centripetal.params(&points).unwrap(), vec![0., 1./3., 1.]);
+        assert!(Parametrization::ChordLength.params(&[p2(0., 0.), p2(0., 0.)]).is_none());
+    }
+
+    #[test]
+    fn test_interpolate() {
+        // Piegl and Tiller, example 9.1.
+        let points = vec![p2(0., 0.), p2(3., 4.), p2(-1., 4.), p2(-4., 0.), p2(-4., -3.)];
+        let bsp = BsplineCurve::interpolate(&points, 3, Parametrization::ChordLength).unwrap();
+        let expected = [0., 0., 0., 0., 28./51., 1., 1., 1., 1.];
+        for (k, e) in bsp.knots.iter().zip(expected.iter()) {
+            assert_approx_eq!(f64, *k, *e, epsilon = 1E-12);
+        }
+        for method in [Parametrization::Uniform, Parametrization::ChordLength, Parametrization::Centripetal] {
+            let bsp = BsplineCurve::interpolate(&points, 3, method).unwrap();
+            for (u, q) in method.params(&points).unwrap().iter().zip(points.iter()) {
+                let c = bsp.evaluate(&p1(*u));
+                assert_approx_eq!(f64, c.x(), q.x(), epsilon = 1E-12);
+                assert_approx_eq!(f64, c.y(), q.y(), epsilon = 1E-12);
+            }
+        }
+        assert!(BsplineCurve::interpolate(&points[..3], 3, Parametrization::Uniform).is_none());
+    }
+
+    #[test]
+    fn test_derivatives() {
+        let points = vec![p3(0., 0., 0.), p3(1., 1., 0.), p3(2., 0., 1.), p3(3., 1., 1.)];
+        let (d0, dn) = (p3(0., 5., 0.), p3(2., 0., -3.));
+        for p in [2, 3] {
+            let bsp = BsplineCurve::interpolate_with_derivatives(&points, p, Parametrization::Centripetal, d0, dn).unwrap();
+            assert_eq!(bsp.p.len(), points.len() + 2);
+            let params = Parametrization::Centripetal.params(&points).unwrap();
+            for (u, q) in params.iter().zip(points.iter()) {
+                assert_approx_eq!(f64, bsp.evaluate(&p1(*u)).dist(q), 0., epsilon = 1E-12);
+            }
+            let (start, end) = (bsp.derivatives(0., 1), bsp.derivatives(1., 1));
+            assert_approx_eq!(f64, start[1].dist(&d0), 0., epsilon = 1E-12);
+            assert_approx_eq!(f64, end[1].dist(&dn), 0., epsilon = 1E-12);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+pub use self::bspline::BsplineBasis;
+pub use self::bspline::BsplineCurve;
+pub use self::bspline::BsplineSurf;
+pub(crate) use self::bspline::is_valid_spline;
+pub(crate) use self::bspline::distinct_knots;
+pub use self::interpolation::Parametrization;
+pub(crate) use self::interpolation::{basis_row, averaged_knots};
+pub use self::fitting::FitResidual;
+pub(crate) use self::fitting::gram;
+pub use self::extraction::BezierExtraction;
+pub(crate) use self::reduction::{reduce_spline, interior_knots, surface_knots, curve_samples, surface_samples};
+#[allow(clippy::module_inception)]
+mod bspline;
+mod interpolation;
+mod fitting;
+mod extraction;
+mod refinement;
+mod reduction;
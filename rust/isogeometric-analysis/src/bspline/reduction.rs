@@ -0,0 +1,297 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::bspline::{BsplineCurve, BsplineSurf, distinct_knots};
+use crate::core::{Mapping, RealPoint, p1, p2};
+use array2d::Array2D;
+
+impl<const S: usize> BsplineCurve<S> {
+    ///
+    /// Removes the interior knot u num times (Tiller's algorithm). Returns None if u is not
+    /// an interior knot of multiplicity at least num, or if a removal would move the curve
+    /// by more than tol.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use isogeometric_analysis::core::p2;
+    /// use isogeometric_analysis::bspline::BsplineCurve;
+    /// let bsp = BsplineCurve::create(vec![p2(0., 0.), p2(1., 2.), p2(3., 1.)], vec![0., 0., 0., 1., 1., 1.], 2).unwrap();
+    /// let refined = bsp.insert_knots(&[0.5, 0.5]).unwrap();
+    /// let removed = refined.remove_knot(0.5, 2, 1E-12).unwrap();
+    /// assert_eq!(removed.knots, bsp.knots);
+    /// assert!(removed.p.iter().zip(bsp.p.iter()).all(|(a, b)| a.dist(b) < 1E-12));
+    /// ```
+    /// 
+    pub fn remove_knot(&self, u: f64, num: usize, tol: f64) -> Option<BsplineCurve<S>> {
+        let (mut p, mut knots) = (self.p.clone(), self.knots.clone());
+        for _ in 0..num {
+            let (q, k) = remove_knot_points(&p, &knots, self.degree, u, tol)?;
+            p = q;
+            knots = k;
+        }
+        Some(BsplineCurve { p, knots, degree: self.degree })
+    }
+
+    ///
+    /// Removes as many interior knots as possible while the curve moves by at most tol,
+    /// measured at sample points in every knot span.
+    /// 
+    pub fn reduce(&self, tol: f64) -> BsplineCurve<S> {
+        let samples = curve_samples(&self.knots[self.degree..=self.p.len()], self.degree);
+        reduce_spline(self.clone(), &samples, tol,
+                      |c| interior_knots(&c.knots, c.degree, c.p.len()).into_iter().map(|u| (0, u)).collect(),
+                      |c, _, u| c.remove_knot(u, 1, tol))
+    }
+}
+
+impl<const S: usize> BsplineSurf<S> {
+    ///
+    /// Removes the interior knot u num times along Xi. Returns None if a removal would move
+    /// any column of control points by more than tol.
+    /// 
+    pub fn remove_knot_xi(&self, u: f64, num: usize, tol: f64) -> Option<BsplineSurf<S>> {
+        let mut columns = Vec::new();
+        let mut knots_xi = self.knots_xi.clone();
+        for column in self.data.as_columns() {
+            let mut c = BsplineCurve { p: column, knots: self.knots_xi.clone(), degree: self.degree_xi };
+            c = c.remove_knot(u, num, tol)?;
+            knots_xi = c.knots;
+            columns.push(c.p);
+        }
+        Some(BsplineSurf { data: Array2D::from_columns(&columns), knots_xi, ..self.clone() })
+    }
+
+    ///
+    /// Removes the interior knot u num times along Eta. Returns None if a removal would move
+    /// any row of control points by more than tol.
+    /// 
+    pub fn remove_knot_eta(&self, u: f64, num: usize, tol: f64) -> Option<BsplineSurf<S>> {
+        let mut rows = Vec::new();
+        let mut knots_eta = self.knots_eta.clone();
+        for row in self.data.as_rows() {
+            let mut c = BsplineCurve { p: row, knots: self.knots_eta.clone(), degree: self.degree_eta };
+            c = c.remove_knot(u, num, tol)?;
+            knots_eta = c.knots;
+            rows.push(c.p);
+        }
+        Some(BsplineSurf { data: Array2D::from_rows(&rows), knots_eta, ..self.clone() })
+    }
+
+    ///
+    /// Removes as many interior knots as possible along both directions while the surface
+    /// moves by at most tol, measured on a grid of sample points in every knot span.
+    /// 
+    pub fn reduce(&self, tol: f64) -> BsplineSurf<S> {
+        let samples = surface_samples(&self.knots_xi[self.degree_xi..=self.data.num_rows()], self.degree_xi,
+                                      &self.knots_eta[self.degree_eta..=self.data.num_columns()], self.degree_eta);
+        reduce_spline(self.clone(), &samples, tol,
+                      |s| surface_knots(&s.knots_xi, s.degree_xi, &s.knots_eta, s.degree_eta), |s, dir, u| {
+            if dir == 0 { s.remove_knot_xi(u, 1, tol) } else { s.remove_knot_eta(u, 1, tol) }
+        })
+    }
+}
+
+///
+/// Returns the interior knots of both directions of a surface with the given knot vectors
+/// and degrees, tagged by direction.
+/// 
+pub(crate) fn surface_knots(knots_xi: &[f64], p: usize, knots_eta: &[f64], q: usize) -> Vec<(usize, f64)> {
+    let xi = interior_knots(knots_xi, p, knots_xi.len() - p - 1);
+    let eta = interior_knots(knots_eta, q, knots_eta.len() - q - 1);
+    xi.into_iter().map(|u| (0, u)).chain(eta.into_iter().map(|u| (1, u))).collect()
+}
+
+///
+/// Returns the distinct interior knots of a spline with count control points.
+/// 
+pub(crate) fn interior_knots(knots: &[f64], p: usize, count: usize) -> Vec<f64> {
+    let breaks = distinct_knots(&knots[p..=count]);
+    breaks[1..breaks.len() - 1].to_vec()
+}
+
+///
+/// Returns sample parameters in every span of the domain knots, enough to resolve a
+/// polynomial of degree p.
+/// 
+pub(crate) fn curve_samples(domain: &[f64], p: usize) -> Vec<RealPoint<1>> {
+    let breaks = distinct_knots(domain);
+    let per_span = 4*(p + 1);
+    let mut samples = Vec::new();
+    for w in breaks.windows(2) {
+        for i in 0..per_span {
+            samples.push(p1(w[0] + (w[1] - w[0])*(i as f64)/(per_span as f64)));
+        }
+    }
+    samples.push(p1(breaks[breaks.len() - 1]));
+    samples
+}
+
+///
+/// Returns the tensor product of the sample parameters along Xi and Eta.
+/// 
+pub(crate) fn surface_samples(domain_xi: &[f64], p: usize, domain_eta: &[f64], q: usize) -> Vec<RealPoint<2>> {
+    let (xs, es) = (curve_samples(domain_xi, p), curve_samples(domain_eta, q));
+    xs.iter().flat_map(|x| es.iter().map(move |e| p2(x.value(0), e.value(0)))).collect()
+}
+
+///
+/// Greedily removes knots from a spline while the deviation from the original one at the
+/// samples stays below tol. The knots function lists the removal candidates tagged by
+/// direction and remove tries to remove one of them once.
+/// 
+pub(crate) fn reduce_spline<T, K, R, const D: usize, const S: usize>(spline: T, samples: &[RealPoint<D>], tol: f64, knots: K, remove: R) -> T
+    where T: Mapping<f64, f64, D, S>, K: Fn(&T) -> Vec<(usize, f64)>, R: Fn(&T, usize, f64) -> Option<T> {
+    let reference = samples.iter().map(|x| spline.evaluate(x)).collect::<Vec<RealPoint<S>>>();
+    let within = |s: &T| samples.iter().zip(reference.iter()).all(|(x, y)| s.evaluate(x).dist(y) <= tol);
+    let mut current = spline;
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (dir, u) in knots(&current) {
+            if let Some(reduced) = remove(&current, dir, u) {
+                if within(&reduced) {
+                    current = reduced;
+                    changed = true;
+                }
+            }
+        }
+    }
+    current
+}
+
+///
+/// Removes the interior knot u once from the spline given by its control points and knots
+/// (Piegl and Tiller, A5.8), provided that the control points move by at most tol.
+/// 
+pub(crate) fn remove_knot_points<const S: usize>(pw: &[RealPoint<S>], knots: &[f64], p: usize, u: f64, tol: f64) -> Option<(Vec<RealPoint<S>>, Vec<f64>)> {
+    let n = pw.len() - 1;
+    // Index of the last occurrence of u and its multiplicity.
+    let r = knots.iter().rposition(|k| *k == u)?;
+    let s = knots.iter().filter(|k| **k == u).count();
+    if r <= p || r > n {
+        log::warn!("Only interior knots can be removed");
+        return None;
+    }
+    let ord = p + 1;
+    let first = r - p;
+    let last = r - s;
+    let off = first - 1;
+    let mut temp = vec![RealPoint::<S>::origin(); last + 2 - off];
+    temp[0] = pw[off];
+    temp[last + 1 - off] = pw[last + 1];
+    let (mut i, mut j) = (first, last);
+    let (mut ii, mut jj) = (1, last - off);
+    while j > i {
+        let alfi = (u - knots[i])/(knots[i + ord] - knots[i]);
+        let alfj = (u - knots[j])/(knots[j + ord] - knots[j]);
+        temp[ii] = (pw[i] - temp[ii - 1]*(1f64 - alfi))*(1f64/alfi);
+        temp[jj] = (pw[j] - temp[jj + 1]*alfj)*(1f64/(1f64 - alfj));
+        i += 1;
+        ii += 1;
+        j -= 1;
+        jj -= 1;
+    }
+    let error = if j < i {
+        temp[ii - 1].dist(&temp[jj + 1])
+    }
+    else {
+        let alfi = (u - knots[i])/(knots[i + ord] - knots[i]);
+        pw[i].dist(&(temp[ii + 1]*alfi + temp[ii - 1]*(1f64 - alfi)))
+    };
+    if error > tol {
+        return None;
+    }
+
+    // The control points first..=last are replaced by the solved ones, minus the one at fout.
+    let fout = (2*r - s - p)/2;
+    let mut qw = pw.to_vec();
+    let (mut i, mut j) = (first, last);
+    while j > i {
+        qw[i] = temp[i - off];
+        qw[j] = temp[j - off];
+        i += 1;
+        j -= 1;
+    }
+    qw.remove(fout);
+    let mut ubar = knots.to_vec();
+    ubar.remove(r);
+    Some((qw, ubar))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bspline::BsplineSurf;
+    use crate::bspline::refinement::tests::curve;
+    use crate::bezier::BezierTeapot;
+    use crate::core::{Mapping, RealPoint2d, RealPoint3d, p1, p2};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_remove() {
+        let bsp = curve();
+        let refined = bsp.insert_knots(&[0.5, 2., 2., 3.]).unwrap();
+        let removed = refined.remove_knot(2., 2, 1E-10).unwrap()
+            .remove_knot(0.5, 1, 1E-10).unwrap()
+            .remove_knot(3., 1, 1E-10).unwrap();
+        assert_eq!(removed.knots, bsp.knots);
+        for (a, b) in removed.p.iter().zip(bsp.p.iter()) {
+            assert_approx_eq!(RealPoint2d, *a, *b, epsilon = 1E-10);
+        }
+
+        // The original knots cannot be removed exactly.
+        assert!(bsp.remove_knot(2.5, 1, 1E-6).is_none());
+        assert!(bsp.remove_knot(1., 3, 1E6).is_none());
+        assert!(bsp.remove_knot(4., 1, 1E6).is_none());
+
+        let reduced = refined.elevate_degree(1).reduce(1E-9);
+        assert_eq!(reduced.p.len(), bsp.elevate_degree(1).p.len());
+        let mut noisy = refined.clone();
+        noisy.p[5] += p2(1E-3, -1E-3);
+        let loose = noisy.reduce(1E-2);
+        assert!(loose.p.len() < noisy.p.len());
+        for i in 0..=100 {
+            let xi = p1(0.04*(i as f64));
+            assert_approx_eq!(RealPoint2d, reduced.evaluate(&xi), bsp.evaluate(&xi), epsilon = 1E-9);
+            assert!(loose.evaluate(&xi).dist(&noisy.evaluate(&xi)) <= 1E-2);
+        }
+    }
+
+    #[test]
+    fn test_reduce_surface() {
+        let patch = &BezierTeapot::build_patches()[0];
+        let knots = vec![0., 0., 0., 0., 1., 1., 1., 1.];
+        let bsp = BsplineSurf::create(patch.data.clone(), knots.clone(), knots, 3, 3).unwrap();
+        let refined = bsp.k_refine(0, 0, 4, 3);
+        assert_eq!((refined.data.num_rows(), refined.data.num_columns()), (7, 6));
+        assert!(refined.remove_knot_xi(0.5, 1, 1E-10).is_some());
+        let reduced = refined.reduce(1E-10);
+        assert_eq!(reduced.knots_xi, bsp.knots_xi);
+        assert_eq!(reduced.knots_eta, bsp.knots_eta);
+        for i in 0..=10 {
+            for j in 0..=10 {
+                let xi = p2((i as f64)/10., (j as f64)/10.);
+                assert_approx_eq!(RealPoint3d, reduced.evaluate(&xi), patch.evaluate(&xi), epsilon = 1E-10);
+            }
+        }
+    }
+}
@@ -0,0 +1,447 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::bspline::{BsplineBasis, BsplineCurve, BsplineSurf, distinct_knots};
+use crate::core::{RealPoint, fact};
+use array2d::Array2D;
+
+impl<const S: usize> BsplineCurve<S> {
+    ///
+    /// Inserts the knots xs, sorted in non-decreasing order, without changing the curve
+    /// (h-refinement). Returns None if a knot is outside of the interior of the domain or
+    /// would get a multiplicity greater than the degree.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use isogeometric_analysis::core::{Mapping, p1, p2};
+    /// use isogeometric_analysis::bspline::BsplineCurve;
+    /// let bsp = BsplineCurve::create(vec![p2(0., 0.), p2(1., 2.), p2(3., 1.)], vec![0., 0., 0., 1., 1., 1.], 2).unwrap();
+    /// let refined = bsp.insert_knots(&[0.25, 0.5, 0.5]).unwrap();
+    /// assert_eq!(refined.p.len(), 6);
+    /// assert!(refined.evaluate(&p1(0.3)).dist(&bsp.evaluate(&p1(0.3))) < 1E-12);
+    /// ```
+    /// 
+    pub fn insert_knots(&self, xs: &[f64]) -> Option<BsplineCurve<S>> {
+        if !can_insert(&self.knots, self.degree, self.p.len(), xs) {
+            return None;
+        }
+        let (p, knots) = refine_points(&self.p, &self.knots, self.degree, xs);
+        Some(BsplineCurve { p, knots, degree: self.degree })
+    }
+
+    ///
+    /// Returns the knots that split every non-empty knot span into divisions equal parts.
+    /// 
+    pub fn subdivision_knots(&self, divisions: usize) -> Vec<f64> {
+        subdivision_knots(&self.knots[self.degree..=self.p.len()], divisions)
+    }
+
+    ///
+    /// Raises the degree by t without changing the curve (p-refinement). The multiplicity
+    /// of every knot is increased by t, so the continuity at the knots is preserved.
+    /// 
+    pub fn elevate_degree(&self, t: usize) -> BsplineCurve<S> {
+        let (p, knots) = elevate_points(&self.p, &self.knots, self.degree, t);
+        BsplineCurve { p, knots, degree: self.degree + t }
+    }
+
+    ///
+    /// Raises the degree by t and then splits every knot span into divisions equal parts
+    /// (k-refinement). The new knots have multiplicity one, so the continuity at them is
+    /// C^(p + t - 1).
+    /// 
+    pub fn k_refine(&self, t: usize, divisions: usize) -> BsplineCurve<S> {
+        let elevated = self.elevate_degree(t);
+        let xs = elevated.subdivision_knots(divisions);
+        let (p, knots) = refine_points(&elevated.p, &elevated.knots, elevated.degree, &xs);
+        BsplineCurve { p, knots, degree: elevated.degree }
+    }
+}
+
+impl<const S: usize> BsplineSurf<S> {
+    ///
+    /// Inserts the knots xs along Xi and es along Eta, both sorted in non-decreasing
+    /// order, without changing the surface.
+    /// 
+    pub fn insert_knots(&self, xs: &[f64], es: &[f64]) -> Option<BsplineSurf<S>> {
+        let (p, q) = (self.degree_xi, self.degree_eta);
+        if !can_insert(&self.knots_xi, p, self.data.num_rows(), xs) ||
+            !can_insert(&self.knots_eta, q, self.data.num_columns(), es) {
+            return None;
+        }
+        let (data, knots_xi) = map_xi(&self.data, |c| refine_points(c, &self.knots_xi, p, xs));
+        let (data, knots_eta) = map_eta(&data, |r| refine_points(r, &self.knots_eta, q, es));
+        Some(BsplineSurf { data, knots_xi, knots_eta, degree_xi: p, degree_eta: q })
+    }
+
+    ///
+    /// Returns the knots that split every non-empty knot span into divisions equal parts
+    /// along Xi and Eta.
+    /// 
+    pub fn subdivision_knots(&self, divisions_xi: usize, divisions_eta: usize) -> (Vec<f64>, Vec<f64>) {
+        (subdivision_knots(&self.knots_xi[self.degree_xi..=self.data.num_rows()], divisions_xi),
+         subdivision_knots(&self.knots_eta[self.degree_eta..=self.data.num_columns()], divisions_eta))
+    }
+
+    ///
+    /// Raises the degrees by t_xi and t_eta without changing the surface.
+    /// 
+    pub fn elevate_degree(&self, t_xi: usize, t_eta: usize) -> BsplineSurf<S> {
+        let (p, q) = (self.degree_xi, self.degree_eta);
+        let (data, knots_xi) = map_xi(&self.data, |c| elevate_points(c, &self.knots_xi, p, t_xi));
+        let (data, knots_eta) = map_eta(&data, |r| elevate_points(r, &self.knots_eta, q, t_eta));
+        BsplineSurf { data, knots_xi, knots_eta, degree_xi: p + t_xi, degree_eta: q + t_eta }
+    }
+
+    ///
+    /// Raises the degrees by t_xi and t_eta and then splits every knot span into
+    /// divisions_xi and divisions_eta equal parts.
+    /// 
+    pub fn k_refine(&self, t_xi: usize, t_eta: usize, divisions_xi: usize, divisions_eta: usize) -> BsplineSurf<S> {
+        let elevated = self.elevate_degree(t_xi, t_eta);
+        let (xs, es) = elevated.subdivision_knots(divisions_xi, divisions_eta);
+        let (p, q) = (elevated.degree_xi, elevated.degree_eta);
+        let (data, knots_xi) = map_xi(&elevated.data, |c| refine_points(c, &elevated.knots_xi, p, &xs));
+        let (data, knots_eta) = map_eta(&data, |r| refine_points(r, &elevated.knots_eta, q, &es));
+        BsplineSurf { data, knots_xi, knots_eta, degree_xi: p, degree_eta: q }
+    }
+}
+
+///
+/// Applies a curve operation to the control points along Xi, i.e. to every column.
+/// 
+fn map_xi<const S: usize, F>(data: &Array2D<RealPoint<S>>, f: F) -> (Array2D<RealPoint<S>>, Vec<f64>)
+    where F: Fn(&[RealPoint<S>]) -> (Vec<RealPoint<S>>, Vec<f64>) {
+    let (columns, knots): (Vec<_>, Vec<_>) = data.as_columns().iter().map(|c| f(c)).unzip();
+    (Array2D::from_columns(&columns), knots.into_iter().next().unwrap_or_default())
+}
+
+///
+/// Applies a curve operation to the control points along Eta, i.e. to every row.
+/// 
+fn map_eta<const S: usize, F>(data: &Array2D<RealPoint<S>>, f: F) -> (Array2D<RealPoint<S>>, Vec<f64>)
+    where F: Fn(&[RealPoint<S>]) -> (Vec<RealPoint<S>>, Vec<f64>) {
+    let (rows, knots): (Vec<_>, Vec<_>) = data.as_rows().iter().map(|r| f(r)).unzip();
+    (Array2D::from_rows(&rows), knots.into_iter().next().unwrap_or_default())
+}
+
+///
+/// Checks that the sorted knots xs can be inserted in a spline with count control points.
+/// 
+pub(crate) fn can_insert(knots: &[f64], p: usize, count: usize, xs: &[f64]) -> bool {
+    if !xs.windows(2).all(|w| w[0] <= w[1]) {
+        log::warn!("The knots to insert must be sorted");
+        return false;
+    }
+    if xs.iter().any(|x| *x <= knots[p] || *x >= knots[count]) {
+        log::warn!("The knots to insert must be in the interior of the domain");
+        return false;
+    }
+    let multiplicity = |x: f64| knots.iter().chain(xs.iter()).filter(|k| **k == x).count();
+    if xs.iter().any(|x| multiplicity(*x) > p) {
+        log::warn!("The multiplicity of a knot cannot exceed the degree");
+        return false;
+    }
+    true
+}
+
+///
+/// Returns the knots splitting every non-empty span of the domain knots into divisions parts.
+/// 
+fn subdivision_knots(domain: &[f64], divisions: usize) -> Vec<f64> {
+    let breaks = distinct_knots(domain);
+    let mut xs = Vec::new();
+    for w in breaks.windows(2) {
+        for i in 1..divisions {
+            xs.push(w[0] + (w[1] - w[0])*(i as f64)/(divisions as f64));
+        }
+    }
+    xs
+}
+
+///
+/// Inserts the sorted knots xs in the spline given by its control points and knots
+/// (Piegl and Tiller, A5.4), returning the new control points and knots.
+/// 
+pub(crate) fn refine_points<const S: usize>(pw: &[RealPoint<S>], knots: &[f64], p: usize, xs: &[f64]) -> (Vec<RealPoint<S>>, Vec<f64>) {
+    if xs.is_empty() {
+        return (pw.to_vec(), knots.to_vec());
+    }
+    let n = pw.len() - 1;
+    let m = n + p + 1;
+    let r = xs.len() - 1;
+    let a = BsplineBasis::find_span(knots, p, n, xs[0]);
+    let b = BsplineBasis::find_span(knots, p, n, xs[r]) + 1;
+    let mut qw = vec![RealPoint::<S>::origin(); pw.len() + xs.len()];
+    let mut ubar = vec![0f64; knots.len() + xs.len()];
+    qw[..=a - p].copy_from_slice(&pw[..=a - p]);
+    qw[b + r..=n + r + 1].copy_from_slice(&pw[b - 1..=n]);
+    ubar[..=a].copy_from_slice(&knots[..=a]);
+    ubar[b + p + r + 1..=m + r + 1].copy_from_slice(&knots[b + p..=m]);
+    let mut i = b + p - 1;
+    let mut k = b + p + r;
+    for x in xs.iter().rev() {
+        while *x <= knots[i] && i > a {
+            qw[k - p - 1] = pw[i - p - 1];
+            ubar[k] = knots[i];
+            k -= 1;
+            i -= 1;
+        }
+        qw[k - p - 1] = qw[k - p];
+        for l in 1..=p {
+            let ind = k - p + l;
+            let alpha = ubar[k + l] - x;
+            if alpha.abs() == 0f64 {
+                qw[ind - 1] = qw[ind];
+            }
+            else {
+                let alpha = alpha/(ubar[k + l] - knots[i + l - p]);
+                qw[ind - 1] = qw[ind - 1]*alpha + qw[ind]*(1f64 - alpha);
+            }
+        }
+        ubar[k] = *x;
+        k -= 1;
+    }
+    (qw, ubar)
+}
+
+///
+/// Binomial coefficient as a float.
+/// 
+fn binomial(n: usize, k: usize) -> f64 {
+    (fact(n as u32)/(fact(k as u32)*fact((n - k) as u32))) as f64
+}
+
+///
+/// Raises by t the degree of the spline given by its control points and knots (Piegl and
+/// Tiller, A5.9), returning the new control points and knots.
+/// 
+pub(crate) fn elevate_points<const S: usize>(pw: &[RealPoint<S>], knots: &[f64], p: usize, t: usize) -> (Vec<RealPoint<S>>, Vec<f64>) {
+    if t == 0 {
+        return (pw.to_vec(), knots.to_vec());
+    }
+    let zero = RealPoint::<S>::origin();
+    let m = pw.len() + p;
+    let ph = p + t;
+    let ph2 = ph/2;
+
+    // Coefficients of the degree elevation of a Bezier segment.
+    let mut bezalfs = vec![vec![0f64; p + 1]; ph + 1];
+    bezalfs[0][0] = 1f64;
+    bezalfs[ph][p] = 1f64;
+    for (i, row) in bezalfs.iter_mut().enumerate().take(ph2 + 1).skip(1) {
+        let inv = 1f64/binomial(ph, i);
+        for (j, b) in row.iter_mut().enumerate().take(p.min(i) + 1).skip(i.saturating_sub(t)) {
+            *b = inv*binomial(p, j)*binomial(t, i - j);
+        }
+    }
+    for i in ph2 + 1..ph {
+        let mirror = bezalfs[ph - i].clone();
+        for (j, b) in bezalfs[i].iter_mut().enumerate().take(p.min(i) + 1).skip(i.saturating_sub(t)) {
+            *b = mirror[p - j];
+        }
+    }
+
+    let segments = distinct_knots(knots).len() - 1;
+    let mut qw = vec![zero; pw.len() + segments*t];
+    let mut uh = vec![0f64; qw.len() + ph + 1];
+    let mut bpts = pw[..=p].to_vec();
+    let mut next_bpts = vec![zero; p.saturating_sub(1)];
+    let mut ebpts = vec![zero; ph + 1];
+    let mut alfs = vec![0f64; p.saturating_sub(1)];
+    let (mut a, mut b) = (p, p + 1);
+    let mut r: isize = -1;
+    let mut kind = ph + 1;
+    let mut cind = 1;
+    let mut ua = knots[0];
+    qw[0] = pw[0];
+    for u in uh.iter_mut().take(ph + 1) {
+        *u = ua;
+    }
+    while b < m {
+        let i = b;
+        while b < m && knots[b] == knots[b + 1] {
+            b += 1;
+        }
+        let mul = b - i + 1;
+        let ub = knots[b];
+        let oldr = r;
+        r = p as isize - mul as isize;
+        let lbz = if oldr > 0 { (oldr as usize + 2)/2 } else { 1 };
+        let rbz = if r > 0 { ph - (r as usize - r as usize/2) } else { ph };
+
+        // Inserts the knot ub r times to extract the Bezier segment.
+        if r > 0 {
+            let numer = ub - ua;
+            for k in (mul + 1..=p).rev() {
+                alfs[k - mul - 1] = numer/(knots[a + k] - ua);
+            }
+            for j in 1..=r as usize {
+                let save = r as usize - j;
+                let s = mul + j;
+                for k in (s..=p).rev() {
+                    bpts[k] = bpts[k]*alfs[k - s] + bpts[k - 1]*(1f64 - alfs[k - s]);
+                }
+                next_bpts[save] = bpts[p];
+            }
+        }
+
+        // Elevates the degree of the segment.
+        for (i, e) in ebpts.iter_mut().enumerate().skip(lbz) {
+            *e = zero;
+            for j in i.saturating_sub(t)..=p.min(i) {
+                *e += bpts[j]*bezalfs[i][j];
+            }
+        }
+
+        // Removes the knot ua oldr times.
+        if oldr > 1 {
+            let oldr = oldr as usize;
+            let den = ub - ua;
+            let bet = (ub - uh[kind - 1])/den;
+            for tr in 1..oldr {
+                let mut i = kind - 1 - tr;
+                let mut j = kind + tr - 1;
+                let mut kj = j + 1 - kind;
+                while j - i > tr {
+                    if i < cind {
+                        let alf = (ub - uh[i])/(ua - uh[i]);
+                        qw[i] = qw[i]*alf + qw[i - 1]*(1f64 - alf);
+                    }
+                    if j >= lbz {
+                        if j + ph <= tr + kind + oldr {
+                            let gam = (ub - uh[j - tr])/den;
+                            ebpts[kj] = ebpts[kj]*gam + ebpts[kj + 1]*(1f64 - gam);
+                        }
+                        else {
+                            ebpts[kj] = ebpts[kj]*bet + ebpts[kj + 1]*(1f64 - bet);
+                        }
+                    }
+                    i += 1;
+                    j -= 1;
+                    kj = kj.wrapping_sub(1);
+                }
+            }
+        }
+
+        if a != p {
+            let count = (ph as isize - oldr) as usize;
+            for u in uh.iter_mut().skip(kind).take(count) {
+                *u = ua;
+            }
+            kind += count;
+        }
+        for e in ebpts.iter().take(rbz + 1).skip(lbz) {
+            qw[cind] = *e;
+            cind += 1;
+        }
+        if b < m {
+            let r = r.max(0) as usize;
+            bpts[..r].copy_from_slice(&next_bpts[..r]);
+            bpts[r..=p].copy_from_slice(&pw[b + r - p..=b]);
+            a = b;
+            b += 1;
+            ua = ub;
+        }
+        else {
+            for u in uh.iter_mut().skip(kind).take(ph + 1) {
+                *u = ub;
+            }
+        }
+    }
+    qw.truncate(cind);
+    uh.truncate(cind + ph + 1);
+    (qw, uh)
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use crate::bspline::{BsplineCurve, BsplineSurf};
+    use crate::bezier::BezierTeapot;
+    use crate::core::{Mapping, RealPoint2d, RealPoint3d, p1, p2};
+    use float_cmp::assert_approx_eq;
+
+    pub(crate) fn curve() -> BsplineCurve<2> {
+        let cpoints = vec![p2(0., 0.), p2(1., 1.), p2(2., 0.5), p2(3., 0.5), p2(0.5, 1.5), p2(1.5, 0.), p2(2., 2.)];
+        BsplineCurve::create(cpoints, vec![0., 0., 0., 0., 1., 1., 2.5, 4., 4., 4., 4.], 3).unwrap()
+    }
+
+    fn assert_same(a: &BsplineCurve<2>, b: &BsplineCurve<2>) {
+        for i in 0..=100 {
+            let xi = p1(0.04*(i as f64));
+            assert_approx_eq!(RealPoint2d, a.evaluate(&xi), b.evaluate(&xi), epsilon = 1E-12);
+        }
+    }
+
+    #[test]
+    fn test_insert() {
+        let bsp = curve();
+        let refined = bsp.insert_knots(&[0.5, 1., 3., 3., 3.]).unwrap();
+        assert_eq!(refined.knots, vec![0., 0., 0., 0., 0.5, 1., 1., 1., 2.5, 3., 3., 3., 4., 4., 4., 4.]);
+        assert_eq!(refined.p.len(), 12);
+        assert_same(&bsp, &refined);
+        assert_eq!(bsp.subdivision_knots(2), vec![0.5, 1.75, 3.25]);
+        assert!(bsp.insert_knots(&[1., 1.]).is_none());
+        assert!(bsp.insert_knots(&[4.]).is_none());
+        assert!(bsp.insert_knots(&[2., 1.5]).is_none());
+    }
+
+    #[test]
+    fn test_elevate() {
+        let bsp = curve();
+        for t in 1..=3 {
+            let elevated = bsp.elevate_degree(t);
+            assert_eq!(elevated.degree, 3 + t);
+            let mut knots = vec![0.; 4 + t];
+            knots.extend(vec![1.; 2 + t]);
+            knots.extend(vec![2.5; 1 + t]);
+            knots.extend(vec![4.; 4 + t]);
+            assert_eq!(elevated.knots, knots);
+            assert_eq!(elevated.p.len() + elevated.degree + 1, knots.len());
+            assert_same(&bsp, &elevated);
+        }
+
+        // k-refinement keeps the new knots simple.
+        let k = bsp.k_refine(2, 4);
+        assert_eq!(k.degree, 5);
+        assert_eq!(k.knots.iter().filter(|x| **x == 0.25).count(), 1);
+        assert_eq!(k.knots.len(), 6 + 4 + 3 + 6 + 3*3);
+        assert_same(&bsp, &k);
+
+        // Surface refinement of a teapot patch.
+        let patch = &BezierTeapot::build_patches()[0];
+        let knots = vec![0., 0., 0., 0., 1., 1., 1., 1.];
+        let bsp = BsplineSurf::create(patch.data.clone(), knots.clone(), knots, 3, 3).unwrap();
+        let refined = bsp.k_refine(1, 2, 3, 2);
+        assert_eq!((refined.degree_xi, refined.degree_eta), (4, 5));
+        assert_eq!((refined.data.num_rows(), refined.data.num_columns()), (7, 7));
+        let inserted = bsp.insert_knots(&[0.5], &[0.2, 0.2]).unwrap();
+        for i in 0..=10 {
+            for j in 0..=10 {
+                let xi = p2((i as f64)/10., (j as f64)/10.);
+                assert_approx_eq!(RealPoint3d, refined.evaluate(&xi), patch.evaluate(&xi), epsilon = 1E-12);
+                assert_approx_eq!(RealPoint3d, inserted.evaluate(&xi), patch.evaluate(&xi), epsilon = 1E-12);
+            }
+        }
+    }
+}
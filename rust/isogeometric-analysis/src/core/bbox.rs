@@ -0,0 +1,198 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::core::RealPoint;
+
+///
+/// Axis-aligned bounding box. The empty box has min greater than max on every axis.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox<const S: usize> {
+    pub min: RealPoint<S>,
+    pub max: RealPoint<S>
+}
+
+impl<const S: usize> BoundingBox<S> {
+    ///
+    /// Creates the empty box.
+    /// 
+    pub fn empty() -> BoundingBox<S> {
+        let mut min = RealPoint::<S>::origin();
+        let mut max = RealPoint::<S>::origin();
+        for i in 0..S {
+            min.set_value(i, f64::MAX);
+            max.set_value(i, f64::MIN);
+        }
+        BoundingBox { min, max }
+    }
+
+    ///
+    /// Creates the smallest box containing the points.
+    /// 
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a RealPoint<S>>) -> BoundingBox<S> {
+        let mut b = BoundingBox::empty();
+        for p in points {
+            b.extend(p);
+        }
+        b
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (0..S).any(|i| self.min.value(i) > self.max.value(i))
+    }
+
+    ///
+    /// Grows the box to contain p.
+    /// 
+    pub fn extend(&mut self, p: &RealPoint<S>) {
+        for i in 0..S {
+            self.min.set_value(i, self.min.value(i).min(p.value(i)));
+            self.max.set_value(i, self.max.value(i).max(p.value(i)));
+        }
+    }
+
+    ///
+    /// Returns the smallest box containing both boxes.
+    /// 
+    pub fn merge(&self, other: &BoundingBox<S>) -> BoundingBox<S> {
+        let mut b = *self;
+        if !other.is_empty() {
+            b.extend(&other.min);
+            b.extend(&other.max);
+        }
+        b
+    }
+
+    ///
+    /// Returns the box grown by margin on every side.
+    /// 
+    pub fn inflated(&self, margin: f64) -> BoundingBox<S> {
+        let mut b = *self;
+        for i in 0..S {
+            b.min.set_value(i, self.min.value(i) - margin);
+            b.max.set_value(i, self.max.value(i) + margin);
+        }
+        b
+    }
+
+    pub fn center(&self) -> RealPoint<S> {
+        (self.min + self.max)*0.5
+    }
+
+    ///
+    /// Returns the length of the diagonal, zero for the empty box.
+    /// 
+    pub fn diagonal(&self) -> f64 {
+        if self.is_empty() { 0. } else { self.min.dist(&self.max) }
+    }
+
+    ///
+    /// Returns the index of the longest side.
+    /// 
+    pub fn longest_axis(&self) -> usize {
+        (0..S).fold(0, |best, i| {
+            if self.max.value(i) - self.min.value(i) > self.max.value(best) - self.min.value(best) { i } else { best }
+        })
+    }
+
+    pub fn contains(&self, p: &RealPoint<S>) -> bool {
+        (0..S).all(|i| p.value(i) >= self.min.value(i) && p.value(i) <= self.max.value(i))
+    }
+
+    ///
+    /// Tests whether the boxes share at least a point.
+    /// 
+    pub fn intersects(&self, other: &BoundingBox<S>) -> bool {
+        (0..S).all(|i| self.min.value(i) <= other.max.value(i) && other.min.value(i) <= self.max.value(i))
+    }
+
+    ///
+    /// Returns the distance of p from the box, zero inside.
+    /// 
+    pub fn distance(&self, p: &RealPoint<S>) -> f64 {
+        let mut sum = 0.;
+        for i in 0..S {
+            let d = (self.min.value(i) - p.value(i)).max(p.value(i) - self.max.value(i)).max(0.);
+            sum += d*d;
+        }
+        sum.sqrt()
+    }
+
+    ///
+    /// Returns the interval of t >= 0 for which origin + t*direction is inside the box, if
+    /// any.
+    /// 
+    pub fn clip_ray(&self, origin: &RealPoint<S>, direction: &RealPoint<S>) -> Option<(f64, f64)> {
+        let (mut t0, mut t1) = (0f64, f64::MAX);
+        for i in 0..S {
+            let (o, d) = (origin.value(i), direction.value(i));
+            let (lo, hi) = (self.min.value(i), self.max.value(i));
+            if d == 0. {
+                if o < lo || o > hi {
+                    return None;
+                }
+                continue;
+            }
+            let (a, b) = ((lo - o)/d, (hi - o)/d);
+            t0 = t0.max(a.min(b));
+            t1 = t1.min(a.max(b));
+            if t0 > t1 {
+                return None;
+            }
+        }
+        Some((t0, t1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{BoundingBox, RealPoint2d, RealPoint3d, p2, p3};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_bbox() {
+        let b = BoundingBox::from_points(&[p2(1., 2.), p2(-1., 0.), p2(0., 5.)]);
+        assert_eq!(b.min, p2(-1., 0.));
+        assert_eq!(b.max, p2(1., 5.));
+        assert_eq!(b.longest_axis(), 1);
+        assert!(b.contains(&p2(0., 1.)));
+        assert!(BoundingBox::<2>::empty().is_empty());
+        assert_eq!(BoundingBox::empty().merge(&b), b);
+        assert!(b.intersects(&BoundingBox::from_points(&[p2(1., 5.), p2(3., 7.)])));
+        assert!(!b.intersects(&BoundingBox::from_points(&[p2(1.1, 5.), p2(3., 7.)])));
+        assert_approx_eq!(f64, b.distance(&p2(4., 9.)), 5.);
+        assert_approx_eq!(f64, b.distance(&p2(0., 3.)), 0.);
+        assert_approx_eq!(RealPoint2d, b.inflated(1.).max, p2(2., 6.));
+    }
+
+    #[test]
+    fn test_ray() {
+        let b = BoundingBox::from_points(&[p3(0., 0., 0.), p3(1., 1., 1.)]);
+        let (t0, t1) = b.clip_ray(&p3(-1., 0.5, 0.5), &p3(2., 0., 0.)).unwrap();
+        assert_approx_eq!(f64, t0, 0.5);
+        assert_approx_eq!(f64, t1, 1.);
+        assert!(b.clip_ray(&p3(-1., 0.5, 0.5), &p3(-1., 0., 0.)).is_none());
+        assert!(b.clip_ray(&p3(-1., 2., 0.5), &p3(1., 0., 0.)).is_none());
+        assert_eq!(b.clip_ray(&p3(0.5, 0.5, 0.5), &p3(0., 0., 1.)).unwrap().0, 0.);
+        assert_approx_eq!(RealPoint3d, b.center(), p3(0.5, 0.5, 0.5));
+    }
+}
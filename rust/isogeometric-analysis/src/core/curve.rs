@@ -0,0 +1,277 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::core::{RealPoint, RealPoint3d, RealRange};
+use crate::core::{fact, quad_adaptive_gauss};
+
+///
+/// Maximum number of iterations when inverting the arc length.
+///
+const MAX_ITERATIONS: usize = 100;
+
+///
+/// Differential geometry of parametric curves f:ℝ→ℝ^S. Implementors provide the domain
+/// and the derivatives; arc length, its inverse, curvature and torsion are derived from
+/// those.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use isogeometric_analysis::core::{p2, ParametricCurve};
+/// use isogeometric_analysis::bezier::BezierCurve;
+/// let bez = BezierCurve::create(vec![p2(0., 0.), p2(1., 0.), p2(3., 0.)]);
+/// assert!((bez.arc_length(1E-10) - 3.).abs() < 1E-10);
+/// let xi = bez.param_at_length(1.5, 1E-10);
+/// let params = bez.params_by_length(5, 1E-10);
+/// ```
+/// 
+pub trait ParametricCurve<const S: usize> {
+    ///
+    /// Returns the parametric domain of the curve.
+    /// 
+    fn domain(&self) -> RealRange;
+
+    ///
+    /// Returns the derivatives of order 0 to n in xi, the first being the point itself.
+    /// 
+    fn derivatives(&self, xi: f64, n: usize) -> Vec<RealPoint<S>>;
+
+    ///
+    /// Returns the sorted parameters where the curve may lose smoothness, including the
+    /// ends of the domain. Integration is split at these parameters.
+    /// 
+    fn breakpoints(&self) -> Vec<f64> {
+        let domain = self.domain();
+        vec![domain.a, domain.b]
+    }
+
+    ///
+    /// Returns the norm of the first derivative in xi.
+    /// 
+    fn speed(&self, xi: f64) -> f64 {
+        self.derivatives(xi, 1)[1].norm()
+    }
+
+    ///
+    /// Returns the length of the curve.
+    /// 
+    fn arc_length(&self, tolerance: f64) -> f64 {
+        let domain = self.domain();
+        self.arc_length_between(domain.a, domain.b, tolerance)
+    }
+
+    ///
+    /// Returns the length of the curve between the parameters a and b, integrating the
+    /// speed with adaptive Gauss quadrature on each smooth piece. The result is negative
+    /// when b < a.
+    /// 
+    fn arc_length_between(&self, a: f64, b: f64, tolerance: f64) -> f64 {
+        if b < a {
+            return -self.arc_length_between(b, a, tolerance);
+        }
+        let mut cuts = vec![a];
+        cuts.extend(self.breakpoints().into_iter().filter(|t| *t > a && *t < b));
+        cuts.push(b);
+        let pieces = (cuts.len() - 1) as f64;
+        cuts.windows(2)
+            .map(|w| quad_adaptive_gauss(|t| self.speed(t), w[0], w[1], tolerance/pieces))
+            .sum()
+    }
+
+    ///
+    /// Returns the parameter at which the arc length measured from the start of the domain
+    /// equals s. Lengths outside of [0, L] map to the ends of the domain.
+    /// 
+    fn param_at_length(&self, s: f64, tolerance: f64) -> f64 {
+        let domain = self.domain();
+        invert_length(self, domain.a, domain.b, s, tolerance)
+    }
+
+    ///
+    /// Returns count parameters splitting the curve into count - 1 pieces of equal length,
+    /// which is the arc-length reparametrisation sampled uniformly.
+    /// 
+    fn params_by_length(&self, count: usize, tolerance: f64) -> Vec<f64> {
+        let domain = self.domain();
+        if count < 2 {
+            return vec![domain.a; count];
+        }
+        let step = self.arc_length(tolerance)/((count - 1) as f64);
+        let mut params = vec![domain.a];
+        for _ in 1..count - 1 {
+            let from = params[params.len() - 1];
+            params.push(invert_length(self, from, domain.b, step, tolerance));
+        }
+        params.push(domain.b);
+        params
+    }
+
+    ///
+    /// Returns the curvature κ = |C' × C''|/|C'|³ in xi, written through dot products so
+    /// that it holds in any dimension. The curvature is not defined where C' vanishes and
+    /// NaN is returned there.
+    /// 
+    fn curvature(&self, xi: f64) -> f64 {
+        let d = self.derivatives(xi, 2);
+        let (d1, d2) = (d[1].dot(&d[1]), d[2].dot(&d[2]));
+        let d12 = d[1].dot(&d[2]);
+        (d1*d2 - d12*d12).max(0f64).sqrt()/(d1*d1.sqrt())
+    }
+
+    ///
+    /// Returns the torsion τ = (C' × C'')·C'''/|C' × C''|² in xi, computed on the first
+    /// three coordinates. Planar curves have zero torsion; zero is also returned where the
+    /// curvature vanishes and the torsion is not defined.
+    /// 
+    fn torsion(&self, xi: f64) -> f64 {
+        let d = self.derivatives(xi, 3).iter().map(|p| p.resize::<3>()).collect::<Vec<RealPoint3d>>();
+        let c = d[1].cross(&d[2]);
+        let c2 = c.dot(&c);
+        if c2 == 0f64 {
+            return 0f64;
+        }
+        c.dot(&d[3])/c2
+    }
+}
+
+///
+/// Finds the parameter in [a, b] whose arc length from a is s, by Newton iteration on the
+/// length safeguarded by bisection.
+/// 
+fn invert_length<C: ParametricCurve<S> + ?Sized, const S: usize>(curve: &C, a: f64, b: f64, s: f64, tolerance: f64) -> f64 {
+    if s <= 0f64 {
+        return a;
+    }
+    let (mut lo, mut hi) = (a, b);
+    let (mut t, mut length) = (a, 0f64);
+    for _ in 0..MAX_ITERATIONS {
+        let g = length - s;
+        if g.abs() <= tolerance {
+            break;
+        }
+        if g < 0f64 { lo = t } else { hi = t }
+        let speed = curve.speed(t);
+        let mut next = t - g/speed;
+        if !(next > lo && next < hi) {
+            next = 0.5*(lo + hi);
+        }
+        length += curve.arc_length_between(t, next, 0.1*tolerance);
+        t = next;
+        if hi - lo <= f64::EPSILON*(b - a).abs() {
+            break;
+        }
+    }
+    t
+}
+
+///
+/// Returns the derivatives of a rational curve C = A/w from the derivatives of the
+/// weighted numerator A and of the weight w, by the Leibniz rule
+/// C^(k) = (A^(k) - Σ_i=1..k binom(k, i) w^(i) C^(k-i))/w.
+/// 
+pub(crate) fn rational_derivatives<const S: usize>(a: &[RealPoint<S>], w: &[f64]) -> Vec<RealPoint<S>> {
+    let mut c: Vec<RealPoint<S>> = Vec::with_capacity(a.len());
+    for k in 0..a.len() {
+        let mut v = a[k];
+        for i in 1..=k {
+            let binom = (fact(k as u32)/(fact(i as u32)*fact((k - i) as u32))) as f64;
+            v -= c[k - i]*(binom*w[i]);
+        }
+        c.push(v*(1f64/w[0]));
+    }
+    c
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{ParametricCurve, p2, p3};
+    use crate::bezier::{BezierCurve, RatBezierCurve};
+    use crate::bspline::BsplineCurve;
+    use crate::nurbs::NurbsCurve;
+    use float_cmp::assert_approx_eq;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_bezier() {
+        // Twisted cubic (t, t², t³).
+        let bez = BezierCurve::create(vec![p3(0., 0., 0.), p3(1./3., 0., 0.), p3(2./3., 1./3., 0.), p3(1., 1., 1.)]);
+        for t in [0., 0.3, 0.7, 1.] {
+            let d = bez.derivatives(t, 3);
+            assert_approx_eq!(f64, d[1].y(), 2.*t, epsilon = 1E-12);
+            assert_approx_eq!(f64, d[3].z(), 6., epsilon = 1E-12);
+            assert_approx_eq!(f64, bez.torsion(t), 3./(9.*t.powi(4) + 9.*t*t + 1.), epsilon = 1E-12);
+        }
+        assert_approx_eq!(f64, bez.curvature(0.), 2., epsilon = 1E-12);
+
+        // Uneven parametrisation of a segment.
+        let line = BezierCurve::create(vec![p2(0., 0.), p2(0.2, 0.), p2(4., 0.)]);
+        assert_approx_eq!(f64, line.arc_length(1E-12), 4., epsilon = 1E-10);
+        for (i, t) in line.params_by_length(5, 1E-12).iter().enumerate() {
+            assert_approx_eq!(f64, line.derivatives(*t, 0)[0].x(), i as f64, epsilon = 1E-9);
+        }
+        assert_approx_eq!(f64, line.curvature(0.5), 0., epsilon = 1E-12);
+        assert_approx_eq!(f64, line.torsion(0.5), 0., epsilon = 1E-12);
+    }
+
+    #[test]
+    fn test_rational() {
+        let h = 1f64/2f64.sqrt();
+        let arc = RatBezierCurve::<2, 3>::create(vec![p2(2., 0.), p2(2., 2.), p2(0., 2.)], vec![1., h, 1.]);
+        assert_approx_eq!(f64, arc.arc_length(1E-12), PI, epsilon = 1E-10);
+        let circle = NurbsCurve::circle(p2(0., 0.), 1.);
+        assert_approx_eq!(f64, circle.arc_length(1E-12), 2.*PI, epsilon = 1E-10);
+        let params = circle.params_by_length(9, 1E-12);
+        for (i, t) in params.iter().enumerate() {
+            let p = circle.derivatives(*t, 0)[0];
+            assert_approx_eq!(f64, p.x(), ((i as f64)*PI/4.).cos(), epsilon = 1E-9);
+            assert_approx_eq!(f64, p.y(), ((i as f64)*PI/4.).sin(), epsilon = 1E-9);
+        }
+        for t in [0.1, 0.25, 0.6, 0.9] {
+            assert_approx_eq!(f64, arc.curvature(t), 0.5, epsilon = 1E-12);
+            assert_approx_eq!(f64, circle.curvature(t), 1., epsilon = 1E-12);
+            assert_approx_eq!(f64, circle.torsion(t), 0., epsilon = 1E-12);
+        }
+    }
+
+    #[test]
+    fn test_bspline() {
+        let cpoints = vec![p2(0., 0.), p2(1., 1.), p2(2., 0.5), p2(3., 0.5), p2(0.5, 1.5), p2(1.5, 0.)];
+        let knots = vec![0., 0., 0., 1., 2., 3., 4., 4., 4.];
+        let bsp = BsplineCurve::create(cpoints.clone(), knots.clone(), 2).unwrap();
+        let nurbs = NurbsCurve::from_bspline(&bsp);
+        let h = 1E-6;
+        for t in [0.2, 1.5, 2.7, 3.9] {
+            let d = bsp.derivatives(t, 2);
+            let dn = nurbs.derivatives(t, 2);
+            let fd = (bsp.derivatives(t + h, 0)[0] - bsp.derivatives(t - h, 0)[0])*(0.5/h);
+            assert_approx_eq!(f64, d[1].x(), fd.x(), epsilon = 1E-8);
+            assert_approx_eq!(f64, d[1].y(), fd.y(), epsilon = 1E-8);
+            assert_approx_eq!(f64, d[2].x(), dn[2].x(), epsilon = 1E-12);
+            assert_approx_eq!(f64, d[2].y(), dn[2].y(), epsilon = 1E-12);
+        }
+        let length = bsp.arc_length(1E-12);
+        assert_approx_eq!(f64, nurbs.arc_length(1E-12), length, epsilon = 1E-10);
+        assert_approx_eq!(f64, bsp.arc_length_between(0., 2., 1E-12) + bsp.arc_length_between(2., 4., 1E-12), length, epsilon = 1E-10);
+        let t = bsp.param_at_length(0.3*length, 1E-12);
+        assert_approx_eq!(f64, bsp.arc_length_between(0., t, 1E-12), 0.3*length, epsilon = 1E-10);
+    }
+}
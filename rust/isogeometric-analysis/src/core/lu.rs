@@ -0,0 +1,175 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::core::RealPoint;
+use array2d::Array2D;
+
+///
+/// LU decomposition with partial pivoting PA = LU of a square matrix of any size. L has a
+/// unit diagonal and is stored with U in a single matrix; row i of PA is row perm[i] of A.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use isogeometric_analysis::core::LupDecomposition;
+/// use array2d::Array2D;
+/// let a = Array2D::from_rows(&[vec![0., 2.], vec![1., 1.]]);
+/// let x = LupDecomposition::new(&a).unwrap().solve(&[4., 3.]);
+/// assert_eq!(x, vec![1., 2.]);
+/// ```
+/// 
+#[derive(Debug, Clone)]
+pub struct LupDecomposition {
+    lu: Array2D<f64>,
+    perm: Vec<usize>
+}
+
+impl LupDecomposition {
+    ///
+    /// Decomposes the matrix, choosing as pivot the largest element of each column. Returns
+    /// None if the matrix is not square or is singular, i.e. some pivot does not exceed
+    /// n ε max|a_ij|.
+    /// 
+    pub fn new(a: &Array2D<f64>) -> Option<LupDecomposition> {
+        let n = a.num_rows();
+        if a.num_columns() != n {
+            log::warn!("LU decomposition needs a square matrix");
+            return None;
+        }
+        let tolerance = (n as f64)*f64::EPSILON*a.elements_row_major_iter().fold(0f64, |m, v| m.max(v.abs()));
+        let mut lu = a.clone();
+        let mut perm = (0..n).collect::<Vec<usize>>();
+        for k in 0..n {
+            let pivot = (k..n).max_by(|i, j| lu[(*i, k)].abs().total_cmp(&lu[(*j, k)].abs())).unwrap();
+            let value = lu[(pivot, k)].abs();
+            if value.is_nan() || value <= tolerance {
+                log::warn!("Matrix is singular");
+                return None;
+            }
+            if pivot != k {
+                perm.swap(k, pivot);
+                for j in 0..n {
+                    let tmp = lu[(k, j)];
+                    lu[(k, j)] = lu[(pivot, j)];
+                    lu[(pivot, j)] = tmp;
+                }
+            }
+            for i in k + 1..n {
+                let l = lu[(i, k)]/lu[(k, k)];
+                lu[(i, k)] = l;
+                for j in k + 1..n {
+                    lu[(i, j)] -= l*lu[(k, j)];
+                }
+            }
+        }
+        Some(LupDecomposition { lu, perm })
+    }
+
+    ///
+    /// Returns the lower triangular factor.
+    /// 
+    pub fn lower(&self) -> Array2D<f64> {
+        let n = self.perm.len();
+        let rows = (0..n).map(|i| (0..n).map(|j| if i == j { 1f64 } else if j < i { self.lu[(i, j)] } else { 0f64 }).collect())
+            .collect::<Vec<Vec<f64>>>();
+        Array2D::from_rows(&rows)
+    }
+
+    ///
+    /// Returns the upper triangular factor.
+    /// 
+    pub fn upper(&self) -> Array2D<f64> {
+        let n = self.perm.len();
+        let rows = (0..n).map(|i| (0..n).map(|j| if j >= i { self.lu[(i, j)] } else { 0f64 }).collect())
+            .collect::<Vec<Vec<f64>>>();
+        Array2D::from_rows(&rows)
+    }
+
+    ///
+    /// Returns the permutation: row i of PA is row perm()[i] of A.
+    /// 
+    pub fn perm(&self) -> &[usize] {
+        &self.perm
+    }
+
+    ///
+    /// Solves Ax = b by forward and backward substitution.
+    /// 
+    pub fn solve(&self, b: &[f64]) -> Vec<f64> {
+        let x = self.solve_points(&b.iter().map(|v| RealPoint::<1>::point1d(*v)).collect::<Vec<RealPoint<1>>>());
+        x.iter().map(|p| p.x()).collect()
+    }
+
+    ///
+    /// Solves AX = B where each row of X and B is a point, i.e. one system per coordinate.
+    /// 
+    pub fn solve_points<const S: usize>(&self, b: &[RealPoint<S>]) -> Vec<RealPoint<S>> {
+        let n = self.perm.len();
+        let mut x = self.perm.iter().map(|i| b[*i]).collect::<Vec<RealPoint<S>>>();
+        for i in 0..n {
+            for j in 0..i {
+                let xj = x[j];
+                x[i] -= xj*self.lu[(i, j)];
+            }
+        }
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                let xj = x[j];
+                x[i] -= xj*self.lu[(i, j)];
+            }
+            x[i] *= 1f64/self.lu[(i, i)];
+        }
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{LupDecomposition, p2};
+    use array2d::Array2D;
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_decomposition() {
+        let a = Array2D::from_rows(&[vec![2., 1., 1., 0.], vec![4., 3., 3., 1.], vec![8., 7., 9., 5.], vec![6., 7., 9., 8.]]);
+        let lup = LupDecomposition::new(&a).unwrap();
+        assert_eq!(lup.perm(), &[2, 3, 1, 0]);
+        let (l, u) = (lup.lower(), lup.upper());
+        for i in 0..4 {
+            for j in 0..4 {
+                let lu = (0..4).map(|k| l[(i, k)]*u[(k, j)]).sum::<f64>();
+                assert_approx_eq!(f64, lu, a[(lup.perm()[i], j)], epsilon = 1E-12);
+            }
+        }
+
+        let x = lup.solve(&[1., 2., 3., 4.]);
+        for i in 0..4 {
+            assert_approx_eq!(f64, (0..4).map(|j| a[(i, j)]*x[j]).sum::<f64>(), (i + 1) as f64, epsilon = 1E-12);
+        }
+        let points = lup.solve_points(&[p2(1., 0.), p2(2., 0.), p2(3., 0.), p2(4., 1.)]);
+        assert_approx_eq!(f64, points[2].x(), x[2], epsilon = 1E-12);
+
+        assert!(LupDecomposition::new(&Array2D::from_rows(&[vec![1., 2.], vec![2., 4.]])).is_none());
+        assert!(LupDecomposition::new(&Array2D::from_rows(&[vec![1., 1.], vec![1., 1. + f64::EPSILON]])).is_none());
+        assert!(LupDecomposition::new(&Array2D::from_rows(&[vec![3., 1.], vec![f64::NAN, 1.]])).is_none());
+    }
+}
@@ -0,0 +1,183 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::core::RealPoint3d;
+use std::collections::HashMap;
+
+///
+/// Edge of a triangle mesh with the triangles sharing it. Vertices are sorted.
+/// 
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshEdge {
+    pub vertices: [usize; 2],
+    pub triangles: Vec<usize>
+}
+
+///
+/// Indexed triangle mesh. Triangles are counterclockwise around their normal.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use isogeometric_analysis::core::{TriangleMesh, p3};
+/// let mesh = TriangleMesh {
+///     vertices: vec![p3(0., 0., 0.), p3(1., 0., 0.), p3(1., 1., 0.), p3(0., 1., 0.)],
+///     triangles: vec![[0, 1, 2], [0, 2, 3]]
+/// };
+/// assert!(mesh.is_manifold());
+/// assert_eq!(mesh.boundary_loops(), vec![vec![0, 1, 2, 3]]);
+/// ```
+/// 
+#[derive(Debug, Clone, Default)]
+pub struct TriangleMesh {
+    pub vertices: Vec<RealPoint3d>,
+    pub triangles: Vec<[usize; 3]>
+}
+
+impl TriangleMesh {
+    ///
+    /// Returns the edges of the mesh, in order of first appearance.
+    /// 
+    pub fn edges(&self) -> Vec<MeshEdge> {
+        let mut index = HashMap::<[usize; 2], usize>::new();
+        let mut edges = Vec::<MeshEdge>::new();
+        for (t, tri) in self.triangles.iter().enumerate() {
+            for k in 0..3 {
+                let (a, b) = (tri[k], tri[(k + 1)%3]);
+                let key = [a.min(b), a.max(b)];
+                let i = *index.entry(key).or_insert_with(|| {
+                    edges.push(MeshEdge { vertices: key, triangles: Vec::new() });
+                    edges.len() - 1
+                });
+                edges[i].triangles.push(t);
+            }
+        }
+        edges
+    }
+
+    ///
+    /// Returns, for each triangle, the triangle across each of its edges (v0, v1), (v1, v2)
+    /// and (v2, v0), if exactly one exists.
+    /// 
+    pub fn neighbours(&self) -> Vec<[Option<usize>; 3]> {
+        let mut by_edge = HashMap::<[usize; 2], Vec<usize>>::new();
+        for edge in self.edges() {
+            by_edge.insert(edge.vertices, edge.triangles);
+        }
+        self.triangles.iter().enumerate().map(|(t, tri)| {
+            let mut ret = [None; 3];
+            for (k, n) in ret.iter_mut().enumerate() {
+                let (a, b) = (tri[k], tri[(k + 1)%3]);
+                let shared = &by_edge[&[a.min(b), a.max(b)]];
+                if shared.len() == 2 {
+                    *n = shared.iter().copied().find(|o| *o != t);
+                }
+            }
+            ret
+        }).collect()
+    }
+
+    ///
+    /// Returns true if every edge is shared by at most two triangles with opposite
+    /// orientations.
+    /// 
+    pub fn is_manifold(&self) -> bool {
+        let mut directed = HashMap::<(usize, usize), usize>::new();
+        for tri in &self.triangles {
+            for k in 0..3 {
+                *directed.entry((tri[k], tri[(k + 1)%3])).or_default() += 1;
+            }
+        }
+        directed.values().all(|c| *c == 1)
+    }
+
+    ///
+    /// Returns true if the mesh is manifold and has no boundary.
+    /// 
+    pub fn is_watertight(&self) -> bool {
+        self.is_manifold() && self.edges().iter().all(|e| e.triangles.len() == 2)
+    }
+
+    ///
+    /// Returns the loops of boundary edges, i.e. edges of a single triangle, as sequences of
+    /// vertices following the orientation of the triangles. Each loop starts from its
+    /// smallest vertex.
+    /// 
+    pub fn boundary_loops(&self) -> Vec<Vec<usize>> {
+        let mut next = HashMap::<usize, Vec<usize>>::new();
+        for edge in self.edges().iter().filter(|e| e.triangles.len() == 1) {
+            let tri = self.triangles[edge.triangles[0]];
+            let k = (0..3).find(|k| [tri[*k].min(tri[(k + 1)%3]), tri[*k].max(tri[(k + 1)%3])] == edge.vertices).unwrap();
+            next.entry(tri[k]).or_default().push(tri[(k + 1)%3]);
+        }
+
+        let mut starts = next.keys().copied().collect::<Vec<usize>>();
+        starts.sort_unstable();
+        let mut loops = Vec::new();
+        for start in starts {
+            while let Some(mut v) = next.get_mut(&start).and_then(|n| n.pop()) {
+                let mut l = vec![start];
+                while v != start {
+                    l.push(v);
+                    match next.get_mut(&v).and_then(|n| n.pop()) {
+                        Some(n) => v = n,
+                        None => break
+                    }
+                }
+                loops.push(l);
+            }
+        }
+        loops
+    }
+
+    ///
+    /// Returns the Euler characteristic V - E + F.
+    /// 
+    pub fn euler_characteristic(&self) -> i64 {
+        self.vertices.len() as i64 - self.edges().len() as i64 + self.triangles.len() as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{TriangleMesh, p3};
+
+    #[test]
+    fn test_topology() {
+        // Tetrahedron, closed and consistently oriented.
+        let mut mesh = TriangleMesh {
+            vertices: vec![p3(0., 0., 0.), p3(1., 0., 0.), p3(0., 1., 0.), p3(0., 0., 1.)],
+            triangles: vec![[0, 2, 1], [0, 1, 3], [1, 2, 3], [0, 3, 2]]
+        };
+        assert!(mesh.is_watertight());
+        assert_eq!(mesh.euler_characteristic(), 2);
+        assert_eq!(mesh.edges().len(), 6);
+        assert!(mesh.neighbours().iter().all(|n| n.iter().all(|t| t.is_some())));
+        assert!(mesh.boundary_loops().is_empty());
+
+        // Removing a face opens a triangular hole.
+        mesh.triangles.pop();
+        assert!(mesh.is_manifold() && !mesh.is_watertight());
+        assert_eq!(mesh.boundary_loops(), vec![vec![0, 2, 3]]);
+        assert_eq!(mesh.neighbours()[0], [None, Some(2), Some(1)]);
+    }
+}
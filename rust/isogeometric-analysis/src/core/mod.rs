@@ -42,6 +42,15 @@ pub use self::utils::measure_time;
 pub use self::mapping::Mapping;
 pub use self::mapping::Evaluator;
 pub use self::color::HslProvider;
+pub use self::bbox::BoundingBox;
+pub use self::quadrature::{gauss_legendre, quad_gauss, quad_adaptive_gauss};
+pub use self::curve::ParametricCurve;
+pub(crate) use self::curve::rational_derivatives;
+pub use self::surface::{ParametricSurface, FundamentalForms, Curvatures, CurvatureKind, CurvatureMap};
+pub(crate) use self::surface::rational_surface_derivatives;
+pub use self::properties::MassProperties;
+pub use self::mesh::{TriangleMesh, MeshEdge};
+pub use self::lu::LupDecomposition;
 mod size;
 mod point;
 mod range;
@@ -50,3 +59,10 @@ mod factorial;
 mod utils;
 mod mapping;
 mod color;
+mod bbox;
+mod quadrature;
+mod curve;
+mod surface;
+mod properties;
+mod mesh;
+mod lu;
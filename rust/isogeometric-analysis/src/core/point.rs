@@ -203,6 +203,31 @@ impl<T: MatElement, const SIZE: usize> Point<T, SIZE> {
         }
         res
     }
+
+    ///
+    /// Returns the point made of the first coordinates of this one, padded with zeros when
+    /// the new size is larger.
+    ///
+    #[inline(always)]
+    pub fn resize<const NEWSIZE: usize>(&self) -> Point<T, NEWSIZE> {
+        let mut res = Point::<T, NEWSIZE>::origin();
+        for i in 0..SIZE.min(NEWSIZE) {
+            res.set_value(i, self.value(i));
+        }
+        res
+    }
+
+    ///
+    /// Returns the dot product of the position vectors.
+    ///
+    #[inline(always)]
+    pub fn dot(&self, p2: &Point<T, SIZE>) -> T {
+        let mut sum = T::zero();
+        for i in 0..SIZE {
+            sum += self.value(i)*p2.value(i);
+        }
+        sum
+    }
 }
 
 impl<T: MatElement> Point<T, 1> {
@@ -236,6 +261,18 @@ impl<T: MatElement> Point<T, 3> {
     pub fn z(&self) -> T { self.value(2) }
     #[inline(always)]
     pub fn set_z(&mut self, z: T) { self.set_value(2, z); }
+
+    ///
+    /// Returns the cross product of the position vectors.
+    ///
+    #[inline(always)]
+    pub fn cross(&self, p2: &Point<T, 3>) -> Point<T, 3> {
+        Point::<T, 3>::point3d(
+            self.y()*p2.z() - self.z()*p2.y(),
+            self.z()*p2.x() - self.x()*p2.z(),
+            self.x()*p2.y() - self.y()*p2.x()
+        )
+    }
 }
 
 impl<T: MatElement, const SIZE: usize> PartialEq for Point<T, SIZE> {
@@ -336,6 +373,14 @@ impl<T: Float + MatElement, const SIZE: usize> Point<T, SIZE> {
         }
         sum.sqrt()
     }
+
+    ///
+    /// Returns the euclidean norm of the position vector.
+    ///
+    #[inline(always)]
+    pub fn norm(&self) -> T {
+        self.dot(self).sqrt()
+    }
 }
 
 #[inline(always)]
@@ -369,5 +414,8 @@ mod tests {
         assert!(approx_eq!(f64, RealPoint1d::point1d(5.).dist(&RealPoint1d::point1d(3.)), 2.));
         assert!(approx_eq!(f64, RealPoint1d::point1d(3.).dist(&RealPoint1d::point1d(5.)), 2.));
         assert!(approx_eq!(f64, RealPoint3d::point3d(7., 4., 3.).dist(&RealPoint3d::point3d(17., 6., 2.)), 105f64.sqrt()));
+        assert!(approx_eq!(f64, RealPoint3d::point3d(1., 2., 3.).dot(&RealPoint3d::point3d(4., -5., 6.)), 12.));
+        assert!(approx_eq!(f64, RealPoint2d::point2d(3., 4.).norm(), 5.));
+        assert_eq!(RealPoint3d::point3d(1., 0., 0.).cross(&RealPoint3d::point3d(0., 1., 0.)), RealPoint3d::point3d(0., 0., 1.));
     }
 }
@@ -0,0 +1,211 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::core::{RealPoint3d, RealRectMatrix};
+use crate::core::{ParametricSurface, gauss_legendre};
+
+///
+/// Integral properties of a model made of surface patches: total area, volume enclosed,
+/// centroid and inertia tensor of the enclosed solid with unit density. The tensor refers
+/// to the centroid.
+/// 
+/// Volume integrals are turned into surface integrals by the divergence theorem, so the
+/// patches must close the solid and their normals Xξ × Xη must be oriented consistently,
+/// either all outwards or all inwards; the orientation is detected from the sign of the
+/// volume.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use isogeometric_analysis::core::MassProperties;
+/// use isogeometric_analysis::bezier::BezierTeapot;
+/// let props = MassProperties::compute(&BezierTeapot::build_patches(), 6);
+/// println!("Area: {}, volume: {}", props.area, props.volume);
+/// ```
+/// 
+#[derive(Debug, Clone)]
+pub struct MassProperties {
+    pub area: f64,
+    pub volume: f64,
+    pub centroid: RealPoint3d,
+    pub inertia: RealRectMatrix<3, 3>
+}
+
+impl MassProperties {
+    ///
+    /// Integrates over the patches with a Gauss-Legendre rule of the given number of
+    /// points per direction on every smooth piece of each patch. Bicubic patches need 5
+    /// points for the volume to be exact.
+    /// 
+    pub fn compute<P: ParametricSurface>(patches: &[P], points: usize) -> MassProperties {
+        let (nodes, weights) = gauss_legendre(points);
+        let mut area = 0f64;
+        let mut volume = 0f64;
+        // First moments ∫x dV, second moments ∫x² dV and products ∫xy dV, ∫xz dV, ∫yz dV.
+        let mut first = [0f64; 3];
+        let mut second = [0f64; 3];
+        let mut products = [0f64; 3];
+        for patch in patches {
+            let (cuts_xi, cuts_eta) = patch.breakpoints();
+            for span_xi in cuts_xi.windows(2) {
+                for span_eta in cuts_eta.windows(2) {
+                    let (c_xi, h_xi) = (0.5*(span_xi[0] + span_xi[1]), 0.5*(span_xi[1] - span_xi[0]));
+                    let (c_eta, h_eta) = (0.5*(span_eta[0] + span_eta[1]), 0.5*(span_eta[1] - span_eta[0]));
+                    for (u, wu) in nodes.iter().zip(weights.iter()) {
+                        for (v, wv) in nodes.iter().zip(weights.iter()) {
+                            let d = patch.derivatives(c_xi + h_xi*u, c_eta + h_eta*v, 1);
+                            let w = wu*wv*h_xi*h_eta;
+                            let x = d[0][0];
+                            let n = d[1][0].cross(&d[0][1])*w;
+                            area += n.norm();
+                            volume += x.dot(&n)/3f64;
+                            for i in 0..3 {
+                                let xi = x.value(i);
+                                first[i] += xi*xi*n.value(i)/2f64;
+                                second[i] += xi*xi*xi*n.value(i)/3f64;
+                            }
+                            products[0] += x.x()*x.x()*x.y()*n.x()/2f64;
+                            products[1] += x.x()*x.x()*x.z()*n.x()/2f64;
+                            products[2] += x.y()*x.y()*x.z()*n.y()/2f64;
+                        }
+                    }
+                }
+            }
+        }
+
+        if volume < 0f64 {
+            volume = -volume;
+            for v in first.iter_mut().chain(second.iter_mut()).chain(products.iter_mut()) {
+                *v = -*v;
+            }
+        }
+
+        let c = [first[0]/volume, first[1]/volume, first[2]/volume];
+        // Central moments by the parallel axis theorem.
+        let m = |i: usize| second[i] - volume*c[i]*c[i];
+        let (pxy, pxz, pyz) = (products[0] - volume*c[0]*c[1], products[1] - volume*c[0]*c[2], products[2] - volume*c[1]*c[2]);
+        let inertia = RealRectMatrix::mat_from_vec([
+            [m(1) + m(2), -pxy, -pxz],
+            [-pxy, m(0) + m(2), -pyz],
+            [-pxz, -pyz, m(0) + m(1)]
+        ]);
+        MassProperties {
+            area,
+            volume,
+            centroid: RealPoint3d::point3d(c[0], c[1], c[2]),
+            inertia
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{MassProperties, RealPoint3d, Mapping, p2, p3};
+    use crate::bezier::{BezierSurf, BezierTeapot};
+    use array2d::Array2D;
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_cube() {
+        // Unit cube [0, 1]³ from six bilinear patches with outward normals.
+        let corners = |a: RealPoint3d, b: RealPoint3d, c: RealPoint3d, d: RealPoint3d|
+            BezierSurf { data: Array2D::from_rows(&[vec![a, b], vec![c, d]]) };
+        let cube = vec![
+            corners(p3(0., 0., 0.), p3(1., 0., 0.), p3(0., 1., 0.), p3(1., 1., 0.)),
+            corners(p3(0., 0., 1.), p3(0., 1., 1.), p3(1., 0., 1.), p3(1., 1., 1.)),
+            corners(p3(0., 0., 0.), p3(0., 0., 1.), p3(1., 0., 0.), p3(1., 0., 1.)),
+            corners(p3(0., 1., 0.), p3(1., 1., 0.), p3(0., 1., 1.), p3(1., 1., 1.)),
+            corners(p3(0., 0., 0.), p3(0., 1., 0.), p3(0., 0., 1.), p3(0., 1., 1.)),
+            corners(p3(1., 0., 0.), p3(1., 0., 1.), p3(1., 1., 0.), p3(1., 1., 1.))
+        ];
+        let props = MassProperties::compute(&cube, 2);
+        assert_approx_eq!(f64, props.area, 6., epsilon = 1E-12);
+        assert_approx_eq!(f64, props.volume, 1., epsilon = 1E-12);
+        for i in 0..3 {
+            assert_approx_eq!(f64, props.centroid.value(i), 0.5, epsilon = 1E-12);
+            for j in 0..3 {
+                assert_approx_eq!(f64, props.inertia.value(i, j), if i == j { 1./6. } else { 0. }, epsilon = 1E-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_translation() {
+        // Closed model: a cube whose six faces, as bicubic patches, bulge outwards.
+        let face = |a: RealPoint3d, b: RealPoint3d, c: RealPoint3d| {
+            let normal = (c - a).cross(&(b - a));
+            let rows = (0..4).map(|i| (0..4).map(|j| {
+                let bulge = if (1..3).contains(&i) && (1..3).contains(&j) { 0.2 } else { 0. };
+                a + (c - a)*(i as f64/3.) + (b - a)*(j as f64/3.) + normal*bulge
+            }).collect()).collect::<Vec<Vec<RealPoint3d>>>();
+            BezierSurf { data: Array2D::from_rows(&rows) }
+        };
+        let cube = vec![
+            face(p3(0., 0., 0.), p3(1., 0., 0.), p3(0., 1., 0.)),
+            face(p3(0., 0., 1.), p3(0., 1., 1.), p3(1., 0., 1.)),
+            face(p3(0., 0., 0.), p3(0., 0., 1.), p3(1., 0., 0.)),
+            face(p3(0., 1., 0.), p3(1., 1., 0.), p3(0., 1., 1.)),
+            face(p3(0., 0., 0.), p3(0., 1., 0.), p3(0., 0., 1.)),
+            face(p3(1., 0., 0.), p3(1., 0., 1.), p3(1., 1., 0.))
+        ];
+        let shift = p3(10., -20., 30.);
+        let moved = cube.iter()
+            .map(|f| BezierSurf { data: Array2D::from_row_major(&f.data.elements_row_major_iter().map(|p| *p + shift).collect::<Vec<RealPoint3d>>(), 4, 4) })
+            .collect::<Vec<BezierSurf<3>>>();
+        let (props, props_moved) = (MassProperties::compute(&cube, 5), MassProperties::compute(&moved, 5));
+        assert!(props.volume > 1.);
+        assert_approx_eq!(f64, props_moved.area, props.area, epsilon = 1E-9);
+        assert_approx_eq!(f64, props_moved.volume, props.volume, epsilon = 1E-9);
+        for i in 0..3 {
+            assert_approx_eq!(f64, props.centroid.value(i), 0.5, epsilon = 1E-9);
+            assert_approx_eq!(f64, props_moved.centroid.value(i) - shift.value(i), props.centroid.value(i), epsilon = 1E-9);
+            for j in 0..3 {
+                assert_approx_eq!(f64, props_moved.inertia.value(i, j), props.inertia.value(i, j), epsilon = 1E-8);
+            }
+        }
+    }
+
+    #[test]
+    fn test_teapot() {
+        // The teapot is open at the spout and the handle and spout cut into the body, so
+        // only the area is meaningful: the volume terms depend on the origin.
+        let patches = BezierTeapot::build_patches();
+        let props = MassProperties::compute(&patches, 16);
+
+        // Cross-check with the area of a fine triangulation.
+        let k = 64;
+        let mut area = 0f64;
+        for patch in &patches {
+            let grid = (0..=k).map(|i| (0..=k).map(|j| patch.evaluate(&p2(i as f64/k as f64, j as f64/k as f64))).collect())
+                .collect::<Vec<Vec<RealPoint3d>>>();
+            for i in 0..k {
+                for j in 0..k {
+                    for (a, b, c) in [(grid[i][j], grid[i + 1][j], grid[i + 1][j + 1]), (grid[i][j], grid[i + 1][j + 1], grid[i][j + 1])] {
+                        area += (b - a).cross(&(c - a)).norm()/2.;
+                    }
+                }
+            }
+        }
+        assert_approx_eq!(f64, props.area, area, epsilon = 1E-2);
+        assert_approx_eq!(f64, props.area, 52.8828, epsilon = 1E-3);
+    }
+}
@@ -0,0 +1,123 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use std::f64::consts::PI;
+
+///
+/// Maximum number of bisections in adaptive quadrature.
+///
+const MAX_DEPTH: u32 = 40;
+
+///
+/// Returns the nodes and the weights of the n-point Gauss-Legendre rule on [-1, 1]. The
+/// nodes are the roots of the Legendre polynomial of degree n, found by Newton iteration.
+/// 
+pub fn gauss_legendre(n: usize) -> (Vec<f64>, Vec<f64>) {
+    let mut nodes = vec![0f64; n];
+    let mut weights = vec![0f64; n];
+    for i in 0..n - n/2 {
+        let mut x = (PI*(i as f64 + 0.75)/(n as f64 + 0.5)).cos();
+        let mut dp = 0f64;
+        for _ in 0..100 {
+            // Recurrence for P_n and its derivative.
+            let (mut p0, mut p1) = (1f64, x);
+            for k in 2..=n {
+                let p2 = ((2*k - 1) as f64*x*p1 - (k - 1) as f64*p0)/(k as f64);
+                p0 = p1;
+                p1 = p2;
+            }
+            let pn = if n == 0 { 1. } else { p1 };
+            let pn1 = if n == 0 { 0. } else { p0 };
+            dp = (n as f64)*(x*pn - pn1)/(x*x - 1.);
+            let dx = pn/dp;
+            x -= dx;
+            if dx.abs() < 1E-16 {
+                break;
+            }
+        }
+        nodes[i] = -x;
+        nodes[n - 1 - i] = x;
+        weights[i] = 2./((1. - x*x)*dp*dp);
+        weights[n - 1 - i] = weights[i];
+    }
+    (nodes, weights)
+}
+
+///
+/// Integrates f on [a, b] with the n-point Gauss-Legendre rule.
+/// 
+pub fn quad_gauss(f: impl Fn(f64) -> f64, a: f64, b: f64, n: usize) -> f64 {
+    let (nodes, weights) = gauss_legendre(n);
+    quad_rule(&f, a, b, &nodes, &weights)
+}
+
+fn quad_rule(f: &impl Fn(f64) -> f64, a: f64, b: f64, nodes: &[f64], weights: &[f64]) -> f64 {
+    let (c, h) = (0.5*(a + b), 0.5*(b - a));
+    nodes.iter().zip(weights.iter()).map(|(x, w)| w*f(c + h*x)).sum::<f64>()*h
+}
+
+///
+/// Integrates f on [a, b] with adaptive 5-point Gauss-Legendre quadrature: an interval is
+/// bisected until the rule on the halves agrees with the rule on the whole within the
+/// tolerance, which is split between the halves.
+/// 
+pub fn quad_adaptive_gauss(f: impl Fn(f64) -> f64, a: f64, b: f64, tolerance: f64) -> f64 {
+    fn adapt(f: &impl Fn(f64) -> f64, a: f64, b: f64, whole: f64, tolerance: f64, depth: u32, rule: &(Vec<f64>, Vec<f64>)) -> f64 {
+        let m = 0.5*(a + b);
+        let left = quad_rule(f, a, m, &rule.0, &rule.1);
+        let right = quad_rule(f, m, b, &rule.0, &rule.1);
+        if depth >= MAX_DEPTH || (left + right - whole).abs() <= tolerance {
+            return left + right;
+        }
+        adapt(f, a, m, left, 0.5*tolerance, depth + 1, rule) + adapt(f, m, b, right, 0.5*tolerance, depth + 1, rule)
+    }
+
+    let rule = gauss_legendre(5);
+    let whole = quad_rule(&f, a, b, &rule.0, &rule.1);
+    adapt(&f, a, b, whole, tolerance, 0, &rule)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{gauss_legendre, quad_adaptive_gauss, quad_gauss};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_gauss() {
+        let (nodes, weights) = gauss_legendre(3);
+        assert_approx_eq!(f64, nodes[0], -0.6f64.sqrt(), epsilon = 1E-15);
+        assert_approx_eq!(f64, nodes[1], 0., epsilon = 1E-15);
+        assert_approx_eq!(f64, weights[0], 5./9., epsilon = 1E-15);
+        assert_approx_eq!(f64, weights[1], 8./9., epsilon = 1E-15);
+        for n in 1..12 {
+            assert_approx_eq!(f64, gauss_legendre(n).1.iter().sum::<f64>(), 2., epsilon = 1E-13);
+            // Exact for polynomials of degree 2n - 1.
+            assert_approx_eq!(f64, quad_gauss(|x| x.powi(2*n as i32 - 2), 0., 1., n), 1./((2*n - 1) as f64), epsilon = 1E-13);
+        }
+    }
+
+    #[test]
+    fn test_adaptive() {
+        assert_approx_eq!(f64, quad_adaptive_gauss(|x| x.sqrt(), 0., 1., 1E-12), 2./3., epsilon = 1E-10);
+        assert_approx_eq!(f64, quad_adaptive_gauss(|x| (100.*x).sin(), 0., std::f64::consts::PI, 1E-12), 0., epsilon = 1E-10);
+    }
+}
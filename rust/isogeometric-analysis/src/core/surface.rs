@@ -0,0 +1,336 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::core::{RealPoint, RealPoint3d, RealRange};
+use crate::core::fact;
+use array2d::Array2D;
+
+///
+/// Coefficients of the first fundamental form E, F, G and of the second fundamental form
+/// L, M, N in a point of a surface, together with the unit normal they refer to.
+/// 
+#[derive(Debug, Clone, Copy)]
+pub struct FundamentalForms {
+    pub e: f64,
+    pub f: f64,
+    pub g: f64,
+    pub l: f64,
+    pub m: f64,
+    pub n: f64,
+    pub normal: RealPoint3d
+}
+
+///
+/// Curvatures in a point of a surface. The principal curvatures satisfy k1 >= k2 and their
+/// directions are unit tangent vectors; signs refer to the normal Xξ × Xη.
+/// 
+#[derive(Debug, Clone, Copy)]
+pub struct Curvatures {
+    pub gaussian: f64,
+    pub mean: f64,
+    pub k1: f64,
+    pub k2: f64,
+    pub d1: RealPoint3d,
+    pub d2: RealPoint3d
+}
+
+///
+/// Selects a scalar out of the curvatures.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CurvatureKind {
+    Gaussian,
+    Mean,
+    Maximum,
+    Minimum,
+    Absolute
+}
+
+impl Curvatures {
+    ///
+    /// Returns the selected scalar. Absolute is |k1| + |k2|, which vanishes only on flat regions.
+    /// 
+    pub fn value(&self, kind: CurvatureKind) -> f64 {
+        match kind {
+            CurvatureKind::Gaussian => self.gaussian,
+            CurvatureKind::Mean => self.mean,
+            CurvatureKind::Maximum => self.k1,
+            CurvatureKind::Minimum => self.k2,
+            CurvatureKind::Absolute => self.k1.abs() + self.k2.abs()
+        }
+    }
+}
+
+///
+/// Curvatures sampled on a regular grid of the parametric domain, e.g. to inspect the
+/// quality of a surface. Rows run along Xi.
+/// 
+pub struct CurvatureMap {
+    pub xi: Vec<f64>,
+    pub eta: Vec<f64>,
+    pub points: Array2D<RealPoint3d>,
+    pub curvatures: Array2D<Curvatures>
+}
+
+impl CurvatureMap {
+    ///
+    /// Returns the grid of the selected scalar.
+    /// 
+    pub fn values(&self, kind: CurvatureKind) -> Array2D<f64> {
+        let rows = self.curvatures.as_rows().iter()
+            .map(|row| row.iter().map(|c| c.value(kind)).collect())
+            .collect::<Vec<Vec<f64>>>();
+        Array2D::from_rows(&rows)
+    }
+
+    ///
+    /// Returns the minimum and the maximum of the selected scalar, ignoring samples where
+    /// the curvature is not defined.
+    /// 
+    pub fn range(&self, kind: CurvatureKind) -> RealRange {
+        self.curvatures.elements_row_major_iter()
+            .map(|c| c.value(kind))
+            .filter(|v| v.is_finite())
+            .fold(RealRange { a: f64::INFINITY, b: f64::NEG_INFINITY }, |r, v| RealRange { a: r.a.min(v), b: r.b.max(v) })
+    }
+}
+
+///
+/// Differential geometry of parametric surfaces f:ℝ²→ℝ³. Implementors provide the domain
+/// and the partial derivatives; normals, fundamental forms and curvatures are derived
+/// from those.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use isogeometric_analysis::core::{CurvatureKind, ParametricSurface};
+/// use isogeometric_analysis::bezier::BezierTeapot;
+/// let patches = BezierTeapot::build_patches();
+/// let c = patches[0].curvatures(0.5, 0.5);
+/// let map = patches[0].curvature_map(10, 10);
+/// let range = map.range(CurvatureKind::Gaussian);
+/// ```
+/// 
+pub trait ParametricSurface {
+    ///
+    /// Returns the parametric domains along Xi and Eta.
+    /// 
+    fn domain(&self) -> (RealRange, RealRange);
+
+    ///
+    /// Returns the partial derivatives in (xi, eta): element [k][l] is differentiated k
+    /// times along Xi and l times along Eta, for k + l <= n. Other elements are zero.
+    /// 
+    fn derivatives(&self, xi: f64, eta: f64, n: usize) -> Vec<Vec<RealPoint3d>>;
+
+    ///
+    /// Returns the sorted parameters along Xi and Eta where the surface may lose smoothness,
+    /// including the ends of the domain. Integration is split at these parameters.
+    /// 
+    fn breakpoints(&self) -> (Vec<f64>, Vec<f64>) {
+        let (r_xi, r_eta) = self.domain();
+        (vec![r_xi.a, r_xi.b], vec![r_eta.a, r_eta.b])
+    }
+
+    ///
+    /// Returns the unit normal Xξ × Xη/|Xξ × Xη| in (xi, eta), which is not defined where
+    /// the surface degenerates.
+    /// 
+    fn normal(&self, xi: f64, eta: f64) -> RealPoint3d {
+        let d = self.derivatives(xi, eta, 1);
+        let n = d[1][0].cross(&d[0][1]);
+        n*(1f64/n.norm())
+    }
+
+    ///
+    /// Returns the fundamental forms in (xi, eta).
+    /// 
+    fn fundamental_forms(&self, xi: f64, eta: f64) -> FundamentalForms {
+        forms(&self.derivatives(xi, eta, 2))
+    }
+
+    ///
+    /// Returns Gaussian, mean and principal curvatures in (xi, eta) with the principal
+    /// directions, from the eigenvalues of the shape operator.
+    /// 
+    fn curvatures(&self, xi: f64, eta: f64) -> Curvatures {
+        let d = self.derivatives(xi, eta, 2);
+        let ff = forms(&d);
+        let det = ff.e*ff.g - ff.f*ff.f;
+        let gaussian = (ff.l*ff.n - ff.m*ff.m)/det;
+        let mean = (ff.e*ff.n - 2f64*ff.f*ff.m + ff.g*ff.l)/(2f64*det);
+        let root = (mean*mean - gaussian).max(0f64).sqrt();
+        let (k1, k2) = (mean + root, mean - root);
+
+        // Solve (II - k1 I)(dξ, dη) = 0 with the better conditioned row; at umbilics any
+        // direction is principal.
+        let rows = [(ff.l - k1*ff.e, ff.m - k1*ff.f), (ff.m - k1*ff.f, ff.n - k1*ff.g)];
+        let (a, b) = if rows[0].0.hypot(rows[0].1) >= rows[1].0.hypot(rows[1].1) { rows[0] } else { rows[1] };
+        let mut d1 = if a.hypot(b) <= 1E-12*(k1.abs() + 1f64)*(ff.e + ff.g) { d[1][0] } else { d[1][0]*(-b) + d[0][1]*a };
+        d1 *= 1f64/d1.norm();
+        let d2 = ff.normal.cross(&d1);
+        Curvatures { gaussian, mean, k1, k2, d1, d2 }
+    }
+
+    ///
+    /// Samples the curvatures on a grid of count_xi × count_eta parameters evenly spaced
+    /// over the domain.
+    /// 
+    fn curvature_map(&self, count_xi: usize, count_eta: usize) -> CurvatureMap {
+        let (r_xi, r_eta) = self.domain();
+        let xi = sample(&r_xi, count_xi);
+        let eta = sample(&r_eta, count_eta);
+        let mut points = Vec::with_capacity(xi.len());
+        let mut curvatures = Vec::with_capacity(xi.len());
+        for x in &xi {
+            points.push(eta.iter().map(|e| self.derivatives(*x, *e, 0)[0][0]).collect::<Vec<RealPoint3d>>());
+            curvatures.push(eta.iter().map(|e| self.curvatures(*x, *e)).collect::<Vec<Curvatures>>());
+        }
+        CurvatureMap {
+            xi,
+            eta,
+            points: Array2D::from_rows(&points),
+            curvatures: Array2D::from_rows(&curvatures)
+        }
+    }
+}
+
+fn sample(r: &RealRange, count: usize) -> Vec<f64> {
+    if count < 2 {
+        return vec![0.5*(r.a + r.b); count];
+    }
+    (0..count).map(|i| r.a + (r.b - r.a)*(i as f64)/((count - 1) as f64)).collect()
+}
+
+fn forms(d: &[Vec<RealPoint3d>]) -> FundamentalForms {
+    let (xu, xv) = (d[1][0], d[0][1]);
+    let n = xu.cross(&xv);
+    let normal = n*(1f64/n.norm());
+    FundamentalForms {
+        e: xu.dot(&xu),
+        f: xu.dot(&xv),
+        g: xv.dot(&xv),
+        l: d[2][0].dot(&normal),
+        m: d[1][1].dot(&normal),
+        n: d[0][2].dot(&normal),
+        normal
+    }
+}
+
+///
+/// Returns the partial derivatives of a rational surface S = A/w from those of the
+/// weighted numerator and of the weight, following Piegl and Tiller, algorithm A4.4.
+/// 
+pub(crate) fn rational_surface_derivatives<const S: usize>(a: &[Vec<RealPoint<S>>], w: &[Vec<f64>]) -> Vec<Vec<RealPoint<S>>> {
+    let binom = |n: usize, k: usize| (fact(n as u32)/(fact(k as u32)*fact((n - k) as u32))) as f64;
+    let n = a.len() - 1;
+    let mut skl = vec![vec![RealPoint::<S>::origin(); n + 1]; n + 1];
+    for k in 0..=n {
+        for l in 0..=n - k {
+            let mut v = a[k][l];
+            for j in 1..=l {
+                v -= skl[k][l - j]*(binom(l, j)*w[0][j]);
+            }
+            for i in 1..=k {
+                v -= skl[k - i][l]*(binom(k, i)*w[i][0]);
+                let mut v2 = RealPoint::<S>::origin();
+                for j in 1..=l {
+                    v2 += skl[k - i][l - j]*(binom(l, j)*w[i][j]);
+                }
+                v -= v2*binom(k, i);
+            }
+            skl[k][l] = v*(1f64/w[0][0]);
+        }
+    }
+    skl
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{CurvatureKind, ParametricSurface, RealPoint3d, p3};
+    use crate::bezier::BezierSurf;
+    use crate::bspline::BsplineSurf;
+    use crate::nurbs::NurbsSurf;
+    use array2d::Array2D;
+    use float_cmp::assert_approx_eq;
+
+    ///
+    /// Quarter of a cylinder of radius 2 along z, as a rational quadratic by linear patch.
+    /// 
+    fn cylinder() -> NurbsSurf<3> {
+        let h = 1f64/2f64.sqrt();
+        let data = Array2D::from_rows(&[
+            vec![p3(2., 0., 0.), p3(2., 0., 1.)],
+            vec![p3(2., 2., 0.), p3(2., 2., 1.)],
+            vec![p3(0., 2., 0.), p3(0., 2., 1.)]
+        ]);
+        let weights = Array2D::from_rows(&[vec![1., 1.], vec![h, h], vec![1., 1.]]);
+        NurbsSurf::create(data, weights, vec![0., 0., 0., 1., 1., 1.], vec![0., 0., 1., 1.], 2, 1).unwrap()
+    }
+
+    #[test]
+    fn test_cylinder() {
+        let cyl = cylinder();
+        for (xi, eta) in [(0.1, 0.2), (0.5, 0.5), (0.8, 0.9)] {
+            let p = cyl.derivatives(xi, eta, 0)[0][0];
+            let n = cyl.normal(xi, eta);
+            assert_approx_eq!(f64, n.x(), p.x()/2., epsilon = 1E-12);
+            assert_approx_eq!(f64, n.y(), p.y()/2., epsilon = 1E-12);
+            let c = cyl.curvatures(xi, eta);
+            // The outward normal sees the cylinder as concave.
+            assert_approx_eq!(f64, c.gaussian, 0., epsilon = 1E-12);
+            assert_approx_eq!(f64, c.mean, -0.25, epsilon = 1E-12);
+            assert_approx_eq!(f64, c.k1, 0., epsilon = 1E-12);
+            assert_approx_eq!(f64, c.k2, -0.5, epsilon = 1E-12);
+            assert_approx_eq!(f64, c.d1.z().abs(), 1., epsilon = 1E-12);
+            assert_approx_eq!(f64, c.d2.z(), 0., epsilon = 1E-12);
+        }
+        let map = cyl.curvature_map(5, 3);
+        assert_eq!(map.values(CurvatureKind::Minimum).num_rows(), 5);
+        assert_approx_eq!(f64, map.range(CurvatureKind::Absolute).a, 0.5, epsilon = 1E-12);
+        assert_approx_eq!(f64, map.range(CurvatureKind::Absolute).b, 0.5, epsilon = 1E-12);
+    }
+
+    #[test]
+    fn test_paraboloid() {
+        // z = x² + y² over [-1, 1]², exact as a biquadratic patch: x² has Bernstein
+        // coefficients 1, -1, 1.
+        let c = [1., -1., 1.];
+        let rows = (0..3).map(|i| (0..3).map(|j| p3(i as f64 - 1., j as f64 - 1., c[i] + c[j])).collect())
+            .collect::<Vec<Vec<RealPoint3d>>>();
+        let data = Array2D::from_rows(&rows);
+        let bez = BezierSurf { data: data.clone() };
+        let bsp = BsplineSurf::create(data, vec![0., 0., 0., 1., 1., 1.], vec![0., 0., 0., 1., 1., 1.], 2, 2).unwrap();
+        let nurbs = NurbsSurf::from_bspline(&bsp);
+        for (xi, eta) in [(0.5, 0.5), (0.25, 0.75), (0.9, 0.1)] {
+            let (x, y) = (2.*xi - 1., 2.*eta - 1.);
+            let w = 1. + 4.*x*x + 4.*y*y;
+            for c in [bez.curvatures(xi, eta), bsp.curvatures(xi, eta), nurbs.curvatures(xi, eta)] {
+                assert_approx_eq!(f64, c.gaussian, 4./(w*w), epsilon = 1E-10);
+                assert_approx_eq!(f64, c.mean, (2. + 4.*x*x + 4.*y*y)/w.powf(1.5), epsilon = 1E-10);
+            }
+            let ff = bez.fundamental_forms(xi, eta);
+            assert_approx_eq!(f64, ff.e, 4.*(1. + 4.*x*x), epsilon = 1E-10);
+            assert_approx_eq!(f64, ff.f, 16.*x*y, epsilon = 1E-10);
+        }
+    }
+}
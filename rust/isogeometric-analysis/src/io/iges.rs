@@ -0,0 +1,735 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::core::RealPoint3d;
+use crate::nurbs::{NurbsCurve, NurbsSurf};
+use array2d::Array2D;
+use std::fmt::{Display, Formatter};
+use std::io::{BufRead, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+///
+/// Error raised when reading IGES data.
+/// 
+#[derive(Debug)]
+pub enum IgesError {
+    /// The data could not be read.
+    Io(std::io::Error),
+    /// The data is not valid IGES. The 1-based number of the offending line is reported.
+    Syntax { line: usize, reason: String }
+}
+
+impl Display for IgesError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            IgesError::Io(error) => write!(f, "{}", error),
+            IgesError::Syntax { line, reason } => write!(f, "line {}: {}", line, reason)
+        }
+    }
+}
+
+impl std::error::Error for IgesError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IgesError::Io(error) => Some(error),
+            _ => None
+        }
+    }
+}
+
+///
+/// Geometry exchanged through IGES files: rational B-spline curves (entity 126) and
+/// rational B-spline surfaces (entity 128). Other entities are skipped when reading.
+/// Transformation matrices (entity 124) referenced by curves and surfaces are applied
+/// to the control points.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use isogeometric_analysis::bezier::BezierTeapot;
+/// use isogeometric_analysis::io::IgesModel;
+/// let mut model = IgesModel::new();
+/// for patch in BezierTeapot::build_patches() {
+///     model.add_bezier_surf(&patch);
+/// }
+/// let mut out = Vec::<u8>::new();
+/// model.write(&mut out).unwrap();
+/// let read = IgesModel::read(out.as_slice()).unwrap();
+/// assert_eq!(read.surfaces.len(), 32);
+/// ```
+/// 
+#[derive(Debug, Clone, Default)]
+pub struct IgesModel {
+    pub curves: Vec<NurbsCurve<3>>,
+    pub surfaces: Vec<NurbsSurf<3>>
+}
+
+///
+/// Entry of the directory section.
+/// 
+struct DirectoryEntry {
+    entity_type: i64,
+    param_pointer: usize,
+    transform: usize,
+    param_count: usize,
+    line: usize
+}
+
+impl IgesModel {
+    ///
+    /// Creates an empty model.
+    /// 
+    pub fn new() -> IgesModel {
+        IgesModel::default()
+    }
+
+    ///
+    /// Adds a Bezier surface, converted to a NURBS surface.
+    /// 
+    pub fn add_bezier_surf(&mut self, bez: &crate::bezier::BezierSurf<3>) -> &mut Self {
+        self.surfaces.push(NurbsSurf::from_bezier(bez));
+        self
+    }
+
+    ///
+    /// Adds a Bezier curve, converted to a NURBS curve.
+    /// 
+    pub fn add_bezier_curve(&mut self, bez: &crate::bezier::BezierCurve<3>) -> &mut Self {
+        self.curves.push(NurbsCurve::from_bezier(bez));
+        self
+    }
+
+    ///
+    /// Adds a rational Bezier curve, converted to a NURBS curve.
+    /// 
+    pub fn add_rat_bezier_curve(&mut self, bez: &crate::bezier::RatBezierCurve<3, 4>) -> &mut Self {
+        self.curves.push(NurbsCurve::from_rat_bezier(bez));
+        self
+    }
+
+    ///
+    /// Reads the curves and surfaces contained in IGES data.
+    /// 
+    pub fn read<R: BufRead>(reader: R) -> Result<IgesModel, IgesError> {
+        let mut global = String::new();
+        let mut directory = Vec::<(String, usize)>::new();
+        let mut params = Vec::<(String, usize)>::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line.map_err(IgesError::Io)?;
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() {
+                continue;
+            }
+            let chars = line.chars().collect::<Vec<char>>();
+            if chars.len() < 73 {
+                return Err(IgesError::Syntax { line: i + 1, reason: "line shorter than 73 columns".to_string() });
+            }
+            let data = chars[0..72].iter().collect::<String>();
+            match chars[72] {
+                'S' | 'T' => {},
+                'G' => global.push_str(&data),
+                'D' => directory.push((data, i + 1)),
+                'P' => params.push((chars[0..64].iter().collect::<String>(), i + 1)),
+                c => return Err(IgesError::Syntax { line: i + 1, reason: format!("unknown section '{}'", c) })
+            }
+        }
+
+        let (param_delim, record_delim) = parse_delimiters(&global);
+        let mut entries = Vec::<DirectoryEntry>::new();
+        for pair in directory.chunks(2) {
+            if pair.len() != 2 {
+                return Err(IgesError::Syntax { line: pair[0].1, reason: "incomplete directory entry".to_string() });
+            }
+            entries.push(DirectoryEntry {
+                entity_type: de_field(&pair[0], 0)?,
+                param_pointer: de_pointer(&pair[0], 1)?,
+                transform: de_pointer(&pair[0], 6)?,
+                param_count: de_pointer(&pair[1], 3)?,
+                line: pair[0].1
+            });
+        }
+
+        let values_of = |entry: &DirectoryEntry| -> Result<Vec<String>, IgesError> {
+            let from = entry.param_pointer;
+            let to = from.checked_sub(1).and_then(|start| start.checked_add(entry.param_count)).filter(|to| *to <= params.len());
+            let to = to.ok_or_else(|| IgesError::Syntax { line: entry.line, reason: "invalid parameter data pointer".to_string() })?;
+            let text = params[(from - 1)..to].iter()
+                .map(|(s, _)| s.as_str())
+                .collect::<String>();
+            Ok(tokenize(&text, param_delim, record_delim))
+        };
+
+        let mut model = IgesModel::new();
+        for entry in &entries {
+            match entry.entity_type {
+                126 | 128 => {},
+                124 => continue,
+                t => {
+                    log::warn!("Skipping unsupported IGES entity {}", t);
+                    continue;
+                }
+            }
+            let line = params.get(entry.param_pointer.max(1) - 1).map(|(_, l)| *l).unwrap_or(entry.line);
+            let mut values = ParamReader { tokens: values_of(entry)?, next: 1, line };
+            let transform = transform_for(entry, &entries, &values_of, 0)?;
+            if entry.entity_type == 126 {
+                let mut curve = values.curve()?;
+                curve.p.iter_mut().for_each(|p| *p = apply(&transform, p));
+                model.curves.push(curve);
+            }
+            else {
+                let mut surf = values.surface()?;
+                let points = surf.data.elements_row_major_iter().map(|p| apply(&transform, p)).collect::<Vec<RealPoint3d>>();
+                surf.data = Array2D::from_row_major(&points, surf.data.num_rows(), surf.data.num_columns());
+                model.surfaces.push(surf);
+            }
+        }
+        Ok(model)
+    }
+
+    ///
+    /// Writes curves as entities 126 and surfaces as entities 128, dated with the current
+    /// time.
+    /// 
+    pub fn write(&self, w: &mut impl Write) -> std::io::Result<()> {
+        self.write_at(w, SystemTime::now())
+    }
+
+    ///
+    /// Writes curves as entities 126 and surfaces as entities 128, dated with the given
+    /// time, so that the same model always gives the same file.
+    /// 
+    pub fn write_at(&self, w: &mut impl Write, time: SystemTime) -> std::io::Result<()> {
+        let mut entities = Vec::<(i64, Vec<String>)>::new();
+        for curve in &self.curves {
+            entities.push((126, curve_params(curve)));
+        }
+        for surf in &self.surfaces {
+            entities.push((128, surf_params(surf)));
+        }
+
+        let start = vec!["IGES data written by the isogeometric_analysis crate.".to_string()];
+        let global = global_params(time);
+        let mut directory = Vec::<String>::new();
+        let mut params = Vec::<String>::new();
+        for (k, (entity_type, values)) in entities.iter().enumerate() {
+            let de = 2*k + 1;
+            let lines = wrap(values, 64);
+            directory.push(format!("{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}",
+                entity_type, params.len() + 1, 0, 0, 0, 0, 0, 0, "00000000"));
+            directory.push(format!("{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}",
+                entity_type, 0, 0, lines.len(), 0, "", "", "", k + 1));
+            for line in lines {
+                params.push(format!("{:<64}{:>8}", line, de));
+            }
+        }
+
+        let global = wrap(&global, 72);
+        write_section(w, &start, 'S')?;
+        write_section(w, &global, 'G')?;
+        write_section(w, &directory, 'D')?;
+        write_section(w, &params, 'P')?;
+        let terminate = format!("S{:>7}G{:>7}D{:>7}P{:>7}", start.len(), global.len(), directory.len(), params.len());
+        write_section(w, &[terminate], 'T')
+    }
+}
+
+///
+/// Sequential reader of the parameters of an entity.
+/// 
+struct ParamReader {
+    tokens: Vec<String>,
+    next: usize,
+    line: usize
+}
+
+impl ParamReader {
+    fn error(&self, reason: &str) -> IgesError {
+        IgesError::Syntax { line: self.line, reason: reason.to_string() }
+    }
+
+    fn real(&mut self) -> Result<f64, IgesError> {
+        let token = self.tokens.get(self.next).ok_or_else(|| self.error("missing parameters"))?;
+        self.next += 1;
+        let token = token.trim().replace(['D', 'd'], "E");
+        if token.is_empty() {
+            return Ok(0f64);
+        }
+        token.parse().map_err(|_| self.error(&format!("invalid number \"{}\"", token)))
+    }
+
+    ///
+    /// Reads an upper index or a degree, which cannot exceed the number of parameters.
+    /// 
+    fn int(&mut self) -> Result<usize, IgesError> {
+        let v = self.real()?;
+        if v < 0f64 || v.fract() != 0f64 || v > self.tokens.len() as f64 {
+            return Err(self.error(&format!("invalid index {}", v)));
+        }
+        Ok(v as usize)
+    }
+
+    ///
+    /// Checks that count values, given as a product of factors, remain to be read.
+    /// 
+    fn count(&self, factors: &[usize]) -> Result<usize, IgesError> {
+        factors.iter().try_fold(1usize, |n, f| n.checked_mul(*f))
+            .filter(|n| *n <= self.tokens.len().saturating_sub(self.next))
+            .ok_or_else(|| self.error("missing parameters"))
+    }
+
+    fn reals(&mut self, count: usize) -> Result<Vec<f64>, IgesError> {
+        self.count(&[count])?;
+        (0..count).map(|_| self.real()).collect()
+    }
+
+    fn points(&mut self, count: usize) -> Result<Vec<RealPoint3d>, IgesError> {
+        self.count(&[count, 3])?;
+        (0..count).map(|_| Ok(RealPoint3d::point3d(self.real()?, self.real()?, self.real()?))).collect()
+    }
+
+    ///
+    /// Reads the parameter range that follows the control points, if present, and warns
+    /// if it differs from the knot range [a, b]: trimmed entities are read untrimmed.
+    /// 
+    fn check_range(&mut self, a: f64, b: f64) {
+        if let Ok(range) = self.reals(2) {
+            if range[0] != a || range[1] != b {
+                log::warn!("Line {}: ignoring the parameter range [{}, {}], which differs from the knot range [{}, {}]",
+                           self.line, range[0], range[1], a, b);
+            }
+        }
+    }
+
+    ///
+    /// Parses the parameters of entity 126.
+    /// 
+    fn curve(&mut self) -> Result<NurbsCurve<3>, IgesError> {
+        let k = self.int()?;
+        let m = self.int()?;
+        self.reals(4)?;
+        let knots = self.reals(k + m + 2)?;
+        let weights = self.reals(k + 1)?;
+        let points = self.points(k + 1)?;
+        self.check_range(knots[m], knots[k + 1]);
+        NurbsCurve::create(points, weights, knots, m).ok_or_else(|| self.error("invalid rational B-spline curve"))
+    }
+
+    ///
+    /// Parses the parameters of entity 128.
+    /// 
+    fn surface(&mut self) -> Result<NurbsSurf<3>, IgesError> {
+        let k1 = self.int()?;
+        let k2 = self.int()?;
+        let m1 = self.int()?;
+        let m2 = self.int()?;
+        self.reals(5)?;
+        let knots_xi = self.reals(k1 + m1 + 2)?;
+        let knots_eta = self.reals(k2 + m2 + 2)?;
+        let count = self.count(&[k1 + 1, k2 + 1])?;
+        let weights = self.reals(count)?;
+        let points = self.points(count)?;
+        self.check_range(knots_xi[m1], knots_xi[k1 + 1]);
+        self.check_range(knots_eta[m2], knots_eta[k2 + 1]);
+        NurbsSurf::create(
+            Array2D::from_column_major(&points, k1 + 1, k2 + 1),
+            Array2D::from_column_major(&weights, k1 + 1, k2 + 1),
+            knots_xi, knots_eta, m1, m2
+        ).ok_or_else(|| self.error("invalid rational B-spline surface"))
+    }
+}
+
+///
+/// Affine transformation [R | T] stored by rows.
+/// 
+type Transform = [[f64; 4]; 3];
+
+const IDENTITY: Transform = [[1., 0., 0., 0.], [0., 1., 0., 0.], [0., 0., 1., 0.]];
+
+///
+/// Returns the composition of the chain of transformation matrices of an entry.
+/// 
+fn transform_for(entry: &DirectoryEntry, entries: &[DirectoryEntry],
+                 values_of: &dyn Fn(&DirectoryEntry) -> Result<Vec<String>, IgesError>, depth: usize) -> Result<Transform, IgesError> {
+    if entry.transform == 0 {
+        return Ok(IDENTITY);
+    }
+    let index = (entry.transform - 1)/2;
+    if depth > entries.len() || 2*index + 1 != entry.transform || index >= entries.len() || entries[index].entity_type != 124 {
+        return Err(IgesError::Syntax { line: entry.line, reason: "invalid transformation matrix pointer".to_string() });
+    }
+    let mut reader = ParamReader { tokens: values_of(&entries[index])?, next: 1, line: entries[index].line };
+    let mut t = IDENTITY;
+    for row in t.iter_mut() {
+        for v in row.iter_mut() {
+            *v = reader.real()?;
+        }
+    }
+
+    // The matrix of an entity is applied first, then the matrix it refers to.
+    let parent = transform_for(&entries[index], entries, values_of, depth + 1)?;
+    let mut ret = IDENTITY;
+    for i in 0..3 {
+        for j in 0..4 {
+            ret[i][j] = (0..3).map(|k| parent[i][k]*t[k][j]).sum::<f64>() + if j == 3 { parent[i][3] } else { 0f64 };
+        }
+    }
+    Ok(ret)
+}
+
+fn apply(t: &Transform, p: &RealPoint3d) -> RealPoint3d {
+    let v = [p.x(), p.y(), p.z()];
+    let row = |i: usize| t[i][0]*v[0] + t[i][1]*v[1] + t[i][2]*v[2] + t[i][3];
+    RealPoint3d::point3d(row(0), row(1), row(2))
+}
+
+///
+/// Reads the i-th 8 columns field of a directory entry line.
+/// 
+fn de_field(line: &(String, usize), i: usize) -> Result<i64, IgesError> {
+    let field = line.0.chars().skip(8*i).take(8).collect::<String>();
+    let field = field.trim();
+    if field.is_empty() {
+        return Ok(0);
+    }
+    field.parse().map_err(|_| IgesError::Syntax { line: line.1, reason: format!("invalid directory field \"{}\"", field) })
+}
+
+///
+/// Returns a non-negative field of a directory entry line, used as a pointer or a count.
+/// 
+fn de_pointer(line: &(String, usize), i: usize) -> Result<usize, IgesError> {
+    let value = de_field(line, i)?;
+    if value < 0 {
+        return Err(IgesError::Syntax { line: line.1, reason: format!("negative directory field {}", value) });
+    }
+    Ok(value as usize)
+}
+
+///
+/// Returns the parameter and record delimiters declared in the global section.
+/// 
+fn parse_delimiters(global: &str) -> (char, char) {
+    let chars = global.chars().collect::<Vec<char>>();
+    let (param_delim, next) = if chars.len() > 2 && chars[0] == '1' && chars[1] == 'H' {
+        (chars[2], 4)
+    }
+    else {
+        (',', 1)
+    };
+    let record_delim = if chars.len() > next + 2 && chars[next] == '1' && chars[next + 1] == 'H' {
+        chars[next + 2]
+    }
+    else {
+        ';'
+    };
+    (param_delim, record_delim)
+}
+
+///
+/// Splits the parameter data of an entity into tokens, up to the record delimiter.
+/// Hollerith strings are kept as a single token.
+/// 
+fn tokenize(text: &str, param_delim: char, record_delim: char) -> Vec<String> {
+    let chars = text.chars().collect::<Vec<char>>();
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == 'H' && !current.trim().is_empty() && current.trim().chars().all(|d| d.is_ascii_digit()) {
+            let n: usize = current.trim().parse().unwrap_or(0);
+            current = chars.iter().skip(i + 1).take(n).collect();
+            i += n + 1;
+            continue;
+        }
+        if c == param_delim || c == record_delim {
+            tokens.push(current.trim().to_string());
+            current.clear();
+            if c == record_delim {
+                break;
+            }
+        }
+        else {
+            current.push(c);
+        }
+        i += 1;
+    }
+    tokens
+}
+
+///
+/// Formats a real so that it always contains a decimal point and an exponent.
+/// 
+fn real(v: f64) -> String {
+    let s = format!("{:E}", v);
+    if s.contains('.') { s } else { s.replace('E', ".0E") }
+}
+
+///
+/// Formats a string as a Hollerith constant.
+/// 
+fn hollerith(s: &str) -> String {
+    format!("{}H{}", s.chars().count(), s)
+}
+
+fn curve_params(curve: &NurbsCurve<3>) -> Vec<String> {
+    let k = curve.p.len() - 1;
+    let closed = curve.p[0] == curve.p[k];
+    let polynomial = curve.weights.iter().all(|w| *w == curve.weights[0]);
+    let planar = curve.p.iter().all(|p| p.z() == curve.p[0].z());
+    let mut v = vec!["126".to_string(), k.to_string(), curve.degree.to_string()];
+    v.extend([planar, closed, polynomial, false].iter().map(|b| (*b as u8).to_string()));
+    v.extend(curve.knots.iter().map(|x| real(*x)));
+    v.extend(curve.weights.iter().map(|x| real(*x)));
+    for p in &curve.p {
+        v.extend([p.x(), p.y(), p.z()].iter().map(|x| real(*x)));
+    }
+    let range = curve.knot_range();
+    v.push(real(range.a));
+    v.push(real(range.b));
+    v.extend([0f64, 0f64, if planar { 1f64 } else { 0f64 }].iter().map(|x| real(*x)));
+    v
+}
+
+fn surf_params(surf: &NurbsSurf<3>) -> Vec<String> {
+    let (k1, k2) = (surf.data.num_rows() - 1, surf.data.num_columns() - 1);
+    let polynomial = surf.weights.elements_row_major_iter().all(|w| *w == surf.weights[(0, 0)]);
+    let mut v = vec!["128".to_string(), k1.to_string(), k2.to_string(),
+        surf.degree_xi.to_string(), surf.degree_eta.to_string()];
+    v.extend(["0", "0", if polynomial { "1" } else { "0" }, "0", "0"].iter().map(|s| s.to_string()));
+    v.extend(surf.knots_xi.iter().map(|x| real(*x)));
+    v.extend(surf.knots_eta.iter().map(|x| real(*x)));
+    v.extend(surf.weights.elements_column_major_iter().map(|x| real(*x)));
+    for p in surf.data.elements_column_major_iter() {
+        v.extend([p.x(), p.y(), p.z()].iter().map(|x| real(*x)));
+    }
+    let (r_xi, r_eta) = (surf.knot_range_xi(), surf.knot_range_eta());
+    v.extend([r_xi.a, r_xi.b, r_eta.a, r_eta.b].iter().map(|x| real(*x)));
+    v
+}
+
+fn global_params(time: SystemTime) -> Vec<String> {
+    let date = hollerith(&timestamp(time));
+    vec![
+        "1H,".to_string(), "1H;".to_string(), hollerith("isogeometric_analysis"), hollerith(""),
+        hollerith("isogeometric_analysis"), hollerith(env!("CARGO_PKG_VERSION")),
+        "32".to_string(), "38".to_string(), "6".to_string(), "308".to_string(), "15".to_string(),
+        hollerith("isogeometric_analysis"), real(1f64), "2".to_string(), hollerith("MM"),
+        "1".to_string(), real(1f64), date.clone(), real(1E-8), real(0f64),
+        hollerith(""), hollerith(""), "11".to_string(), "0".to_string(), date
+    ]
+}
+
+///
+/// Returns the UTC time formatted as YYYYMMDD.HHNNSS.
+/// 
+fn timestamp(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0) as i64;
+    let (days, rem) = (secs.div_euclid(86400), secs.rem_euclid(86400));
+
+    // Conversion of days since epoch to a civil date.
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era*146097;
+    let yoe = (doe - doe/1460 + doe/36524 - doe/146096)/365;
+    let doy = doe - (365*yoe + yoe/4 - yoe/100);
+    let mp = (5*doy + 2)/153;
+    let d = doy - (153*mp + 2)/5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era*400 + if m <= 2 { 1 } else { 0 };
+    format!("{:04}{:02}{:02}.{:02}{:02}{:02}", y, m, d, rem/3600, (rem % 3600)/60, rem % 60)
+}
+
+///
+/// Joins values with the parameter delimiter, terminates them with the record
+/// delimiter and wraps them in lines not longer than width, without splitting values.
+/// 
+fn wrap(values: &[String], width: usize) -> Vec<String> {
+    let mut lines = Vec::<String>::new();
+    let mut current = String::new();
+    for (i, v) in values.iter().enumerate() {
+        let token = format!("{}{}", v, if i + 1 == values.len() { ';' } else { ',' });
+        if !current.is_empty() && current.len() + token.len() > width {
+            lines.push(current);
+            current = String::new();
+        }
+        current.push_str(&token);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn write_section(w: &mut impl Write, lines: &[String], section: char) -> std::io::Result<()> {
+    for (i, line) in lines.iter().enumerate() {
+        writeln!(w, "{:<72}{}{:>7}", line, section, i + 1)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{IgesModel, IgesError};
+    use crate::bezier::{BezierCircle, BezierTeapot, RatBezierCurve};
+    use crate::core::{Mapping, RealPoint3d, p1, p2, p3};
+    use float_cmp::assert_approx_eq;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_round_trip() {
+        let patches = BezierTeapot::build_patches();
+        let mut model = IgesModel::new();
+        for patch in &patches {
+            model.add_bezier_surf(patch);
+        }
+        let arcs = BezierCircle { radius: 3, segments: 4 }.compute().unwrap();
+        for arc in arcs {
+            let p = arc.p.iter().map(|p| p3(p.x(), p.y(), 1f64)).collect();
+            model.add_rat_bezier_curve(&RatBezierCurve::<3, 4>::create(p, arc.weights.clone()));
+        }
+
+        let mut out = Vec::<u8>::new();
+        model.write(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.lines().all(|l| l.chars().count() == 80));
+        assert!(text.lines().last().unwrap().starts_with("S      1G"));
+
+        let read = IgesModel::read(text.as_bytes()).unwrap();
+        assert_eq!(read.surfaces.len(), patches.len());
+        assert_eq!(read.curves.len(), 4);
+        for (surf, patch) in read.surfaces.iter().zip(patches.iter()) {
+            assert_eq!(surf.data, patch.data);
+            assert_eq!((surf.degree_xi, surf.degree_eta), (3, 3));
+            assert!(surf.weights.elements_row_major_iter().all(|w| *w == 1f64));
+            assert_approx_eq!(RealPoint3d, surf.evaluate(&p2(0.3, 0.6)), patch.evaluate(&p2(0.3, 0.6)), epsilon = 1E-12);
+        }
+        for (curve, orig) in read.curves.iter().zip(model.curves.iter()) {
+            assert_eq!(curve.p, orig.p);
+            assert_eq!(curve.weights, orig.weights);
+            assert_eq!(curve.knots, orig.knots);
+            let x = curve.evaluate(&p1(0.4));
+            assert_approx_eq!(f64, x.dist(&p3(0., 0., 1.)), 3f64, epsilon = 1E-12);
+        }
+    }
+
+    #[test]
+    fn test_timestamp() {
+        let mut model = IgesModel::new();
+        model.add_bezier_surf(&BezierTeapot::build_patches()[0]);
+        // 2000-02-29 12:34:56 UTC.
+        let time = UNIX_EPOCH + Duration::from_secs(951827696);
+        let write = || {
+            let mut out = Vec::<u8>::new();
+            model.write_at(&mut out, time).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let text = write();
+        assert_eq!(text, write());
+        assert_eq!(text.matches("15H20000229.123456").count(), 2);
+
+        // A trimmed parameter range is ignored.
+        let trimmed = text.replace("0.0E0,1.0E0,0.0E0,1.0E0; ", "0.0E0,5.0E-1,0.0E0,1.0E0;");
+        assert_ne!(trimmed, text);
+        assert_eq!(IgesModel::read(trimmed.as_bytes()).unwrap().surfaces[0].knots_xi, model.surfaces[0].knots_xi);
+    }
+
+    #[test]
+    fn test_transform() {
+        let mut model = IgesModel::new();
+        model.add_bezier_curve(&crate::bezier::BezierCurve::create(vec![p3(0., 0., 0.), p3(1., 0., 0.)]));
+        let mut out = Vec::<u8>::new();
+        model.write(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        // Rotate by 90° around z and translate, through an entity 124.
+        let lines = text.lines().collect::<Vec<&str>>();
+        let d = lines.iter().position(|l| l.chars().nth(72) == Some('D')).unwrap();
+        let p = lines.iter().position(|l| l.chars().nth(72) == Some('P')).unwrap();
+        let p_count = lines.iter().filter(|l| l.chars().nth(72) == Some('P')).count();
+        let entity = format!("{:<64}{:>8}P{:>7}", "124,0.,-1.,0.,5.,1.,0.,0.,0.,0.,0.,1.,2.;", 3, p_count + 1);
+        let mut edited = lines[..d].to_vec().iter().map(|l| l.to_string()).collect::<Vec<String>>();
+        edited.push(format!("{}{:>8}{}", &lines[d][..48], 3, &lines[d][56..]));
+        edited.push(lines[d + 1].to_string());
+        edited.push(format!("{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}D{:>7}", 124, p_count + 1, 0, 0, 0, 0, 0, 0, "00000000", 3));
+        edited.push(format!("{:>8}{:>8}{:>8}{:>8}{:>8}{:>24}{:>8}D{:>7}", 124, 0, 0, 1, 0, "", 2, 4));
+        edited.extend(lines[p..(lines.len() - 1)].iter().map(|l| l.to_string()));
+        edited.push(entity);
+        let read = IgesModel::read(edited.join("\n").as_bytes()).unwrap();
+        assert_eq!(read.curves.len(), 1);
+        assert_approx_eq!(RealPoint3d, read.curves[0].p[0], p3(5., 0., 2.));
+        assert_approx_eq!(RealPoint3d, read.curves[0].p[1], p3(5., 1., 2.));
+    }
+
+    #[test]
+    fn test_errors() {
+        match IgesModel::read("too short\n".as_bytes()) {
+            Err(IgesError::Syntax { line: 1, .. }) => {},
+            e => panic!("{:?}", e)
+        }
+        let mut model = IgesModel::new();
+        model.add_bezier_curve(&crate::bezier::BezierCurve::create(vec![p3(0., 0., 0.), p3(1., 0., 0.)]));
+        let mut out = Vec::<u8>::new();
+        model.write(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap().replace("126,1,1,", "126,1,X,");
+        match IgesModel::read(text.as_bytes()) {
+            Err(IgesError::Syntax { reason, .. }) => assert_eq!(reason, "invalid number \"X\""),
+            e => panic!("{:?}", e)
+        }
+    }
+
+    #[test]
+    fn test_corrupted() {
+        let mut model = IgesModel::new();
+        model.add_bezier_surf(&BezierTeapot::build_patches()[0]);
+        let mut out = Vec::<u8>::new();
+        model.write(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines = text.lines().collect::<Vec<&str>>();
+        let d = lines.iter().position(|l| l.chars().nth(72) == Some('D')).unwrap();
+        let read_with = |d_field: &str, params: &str| {
+            let mut edited = lines.iter().map(|l| l.to_string()).collect::<Vec<String>>();
+            edited[d] = format!("{}{:>8}{}", &lines[d][..8], d_field, &lines[d][16..]);
+            if !params.is_empty() {
+                let p = d + 2;
+                edited[p] = format!("{:<64}{}", params, &lines[p][64..]);
+            }
+            IgesModel::read(edited.join("\n").as_bytes())
+        };
+
+        // Negative and overflowing parameter data pointers.
+        for (pointer, expected) in [("-1", "negative directory field -1"), ("99999999", "invalid parameter data pointer")] {
+            match read_with(pointer, "") {
+                Err(IgesError::Syntax { line, reason }) => assert_eq!((line, reason.as_str()), (d + 1, expected)),
+                e => panic!("{:?}", e)
+            }
+        }
+        // Counts of knots and control points beyond the parameters.
+        for (params, expected) in [("128,1E9,3,3,3,0,0,1,0,0,", "invalid index 1000000000"), ("128,40,40,3,3,0,0,1,0,0,", "missing parameters")] {
+            match read_with("1", params) {
+                Err(IgesError::Syntax { reason, .. }) => assert_eq!(reason, expected),
+                e => panic!("{:?}", e)
+            }
+        }
+    }
+}
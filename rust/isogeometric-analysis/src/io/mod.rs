@@ -0,0 +1,43 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+pub use self::vtk::VtkGrid;
+pub use self::vtk::VtkCellType;
+pub use self::vtk::VtkDataArray;
+pub use self::vtk::VtkSurfaceCells;
+pub use self::iges::IgesModel;
+pub use self::iges::IgesError;
+pub use self::step::StepModel;
+pub use self::step::StepError;
+pub use self::svg::SvgOptions;
+pub use self::svg::SvgWriter;
+pub use self::raster::Camera;
+pub use self::raster::RgbImage;
+pub use self::raster::RasterOptions;
+pub use self::raster::Rasterizer;
+mod vtk;
+mod iges;
+mod step;
+mod svg;
+mod raster;
+#[cfg(feature = "serde")]
+mod serialization;
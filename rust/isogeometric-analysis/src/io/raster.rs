@@ -0,0 +1,400 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::bezier::BezierSurf;
+use crate::core::{BoundingBox, HslProvider, Mapping, RealPoint3d, RealRange, p2, p3};
+use colorsys::Rgb;
+use std::io::Write;
+
+///
+/// Distance from the eye below which geometry is clipped.
+///
+const NEAR: f64 = 1E-3;
+
+///
+/// Pinhole camera with a vertical field of view in degrees.
+/// 
+#[derive(Debug, Clone, Copy)]
+pub struct Camera {
+    pub eye: RealPoint3d,
+    pub target: RealPoint3d,
+    pub up: RealPoint3d,
+    pub fov: f64
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Camera::orbit(&RealPoint3d::origin(), 10., -60., 25., 30.)
+    }
+}
+
+impl Camera {
+    ///
+    /// Creates a camera looking at target from the given distance. Azimuth is measured
+    /// around the z axis from the x axis, elevation from the xy plane, both in degrees.
+    /// 
+    pub fn orbit(target: &RealPoint3d, distance: f64, azimuth: f64, elevation: f64, fov: f64) -> Camera {
+        let (a, e) = (azimuth.to_radians(), elevation.to_radians());
+        Camera {
+            eye: *target + p3(e.cos()*a.cos(), e.cos()*a.sin(), e.sin())*distance,
+            target: *target,
+            up: p3(0., 0., 1.),
+            fov
+        }
+    }
+
+    ///
+    /// Creates an orbiting camera whose view contains all the patches. The bounding sphere
+    /// of the control points is used, as each patch lies in their convex hull.
+    /// 
+    pub fn framing(patches: &[BezierSurf<3>], azimuth: f64, elevation: f64, fov: f64) -> Camera {
+        let points = patches.iter().flat_map(|p| p.data.elements_row_major_iter()).collect::<Vec<&RealPoint3d>>();
+        if points.is_empty() {
+            return Camera::orbit(&RealPoint3d::origin(), 1., azimuth, elevation, fov);
+        }
+        let center = BoundingBox::from_points(points.iter().copied()).center();
+        let radius = points.iter().map(|p| p.dist(&center)).fold(0f64, f64::max).max(1E-9);
+        Camera::orbit(&center, radius/(0.5*fov.to_radians()).sin(), azimuth, elevation, fov)
+    }
+
+    ///
+    /// Returns the unit right, up and forward vectors of the camera.
+    /// 
+    fn basis(&self) -> (RealPoint3d, RealPoint3d, RealPoint3d) {
+        let f = self.target - self.eye;
+        let f = f*(1./f.norm());
+        let mut r = f.cross(&self.up);
+        if r.norm() < 1E-9 {
+            // Looking along the up vector: any horizontal direction will do.
+            r = f.cross(&p3(0., 1., 0.));
+        }
+        let r = r*(1./r.norm());
+        (r, r.cross(&f), f)
+    }
+}
+
+///
+/// Image with 8 bits RGB pixels stored row by row from the top.
+/// 
+#[derive(Debug, Clone)]
+pub struct RgbImage {
+    pub width: usize,
+    pub height: usize,
+    pub data: Vec<u8>
+}
+
+impl RgbImage {
+    pub fn new(width: usize, height: usize, color: [u8; 3]) -> RgbImage {
+        RgbImage {
+            width,
+            height,
+            data: color.iter().cloned().cycle().take(3*width*height).collect()
+        }
+    }
+
+    ///
+    /// Returns the color of the pixel in column x and row y.
+    /// 
+    pub fn pixel(&self, x: usize, y: usize) -> [u8; 3] {
+        let i = 3*(y*self.width + x);
+        [self.data[i], self.data[i + 1], self.data[i + 2]]
+    }
+
+    ///
+    /// Writes the image as binary PPM.
+    /// 
+    pub fn write_ppm(&self, w: &mut impl Write) -> std::io::Result<()> {
+        write!(w, "P6\n{} {}\n255\n", self.width, self.height)?;
+        w.write_all(&self.data)
+    }
+
+    ///
+    /// Writes the image as PNG.
+    /// 
+    pub fn write_png(&self, w: &mut impl Write) -> std::io::Result<()> {
+        let mut encoder = png::Encoder::new(w, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.data)?;
+        Ok(())
+    }
+}
+
+///
+/// Options of the rasterizer.
+/// 
+#[derive(Debug, Clone)]
+pub struct RasterOptions {
+    pub width: usize,
+    pub height: usize,
+    pub camera: Camera,
+    /// Direction towards a directional light. When None, the light comes from the eye.
+    pub light: Option<RealPoint3d>,
+    /// Fraction of the color that is not affected by the light.
+    pub ambient: f64,
+    /// Number of intervals each surface is split into along each parametric direction.
+    pub tessellation: usize,
+    pub background: [u8; 3]
+}
+
+impl Default for RasterOptions {
+    fn default() -> Self {
+        RasterOptions {
+            width: 800,
+            height: 600,
+            camera: Camera::default(),
+            light: None,
+            ambient: 0.15,
+            tessellation: 16,
+            background: [255, 255, 255]
+        }
+    }
+}
+
+///
+/// Vertex projected to the screen: position in pixels, inverse depth and intensity.
+/// 
+#[derive(Clone, Copy)]
+struct ScreenVertex {
+    x: f64,
+    y: f64,
+    inv_z: f64,
+    light: f64
+}
+
+///
+/// Z-buffer rasterizer of tessellated surfaces with Lambert shading. Both sides of the
+/// surfaces are lit, so the orientation of patches does not matter.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use isogeometric_analysis::bezier::BezierTeapot;
+/// use isogeometric_analysis::io::{Camera, Rasterizer, RasterOptions};
+/// let patches = BezierTeapot::build_patches();
+/// let mut raster = Rasterizer::new(RasterOptions {
+///     width: 160,
+///     height: 120,
+///     camera: Camera::framing(&patches, -60., 25., 30.),
+///     tessellation: 4,
+///     ..RasterOptions::default()
+/// });
+/// raster.draw_patches(&patches, None);
+/// let mut png = Vec::new();
+/// raster.image().write_png(&mut png).unwrap();
+/// ```
+/// 
+pub struct Rasterizer {
+    options: RasterOptions,
+    image: RgbImage,
+    depth: Vec<f64>
+}
+
+impl Rasterizer {
+    pub fn new(options: RasterOptions) -> Rasterizer {
+        Rasterizer {
+            image: RgbImage::new(options.width, options.height, options.background),
+            depth: vec![0f64; options.width*options.height],
+            options
+        }
+    }
+
+    ///
+    /// Returns the rendered image.
+    /// 
+    pub fn image(&self) -> &RgbImage {
+        &self.image
+    }
+
+    ///
+    /// Draws a set of patches, all with the same color or with a different hue each from
+    /// HslProvider when no color is provided.
+    /// 
+    pub fn draw_patches(&mut self, patches: &[BezierSurf<3>], color: Option<[u8; 3]>) {
+        let colors = HslProvider { count: patches.len() as u32 };
+        let unit = RealRange { a: 0., b: 1. };
+        for (i, patch) in patches.iter().enumerate() {
+            let c = color.unwrap_or_else(|| {
+                let rgb = Rgb::from_hex_str(&colors.hex_color_for_index(i as u32)).unwrap();
+                [rgb.red() as u8, rgb.green() as u8, rgb.blue() as u8]
+            });
+            self.draw_surface(patch, &unit, &unit, c);
+        }
+    }
+
+    ///
+    /// Tessellates and draws any surface over the given parametric ranges.
+    /// 
+    pub fn draw_surface<M: Mapping<f64, f64, 2, 3>>(&mut self, m: &M, r_xi: &RealRange, r_eta: &RealRange, color: [u8; 3]) {
+        let n = self.options.tessellation.max(1);
+        let grid = (0..=n).map(|i| (0..=n).map(|j| m.evaluate(&p2(
+            r_xi.a + (r_xi.b - r_xi.a)*(i as f64)/(n as f64),
+            r_eta.a + (r_eta.b - r_eta.a)*(j as f64)/(n as f64)
+        ))).collect::<Vec<RealPoint3d>>()).collect::<Vec<Vec<RealPoint3d>>>();
+
+        // Vertex normals average the normals of the adjacent quads, computed from their
+        // diagonals so that collapsed edges do not matter.
+        let mut normals = vec![vec![RealPoint3d::origin(); n + 1]; n + 1];
+        for i in 0..n {
+            for j in 0..n {
+                let d1 = grid[i + 1][j + 1] - grid[i][j];
+                let d2 = grid[i][j + 1] - grid[i + 1][j];
+                let normal = d1.cross(&d2);
+                for (k, l) in [(i, j), (i + 1, j), (i, j + 1), (i + 1, j + 1)] {
+                    normals[k][l] += normal;
+                }
+            }
+        }
+
+        let (right, up, forward) = self.options.camera.basis();
+        let light = self.options.light.unwrap_or(forward*-1.);
+        let light = light*(1./light.norm());
+        let focal = 0.5*(self.options.height as f64)/(0.5*self.options.camera.fov.to_radians()).tan();
+        let eye = self.options.camera.eye;
+        let (w, h) = (self.options.width as f64, self.options.height as f64);
+        let ambient = self.options.ambient;
+        let project = |p: &RealPoint3d, normal: &RealPoint3d| -> Option<ScreenVertex> {
+            let v = *p - eye;
+            let z = v.dot(&forward);
+            if z < NEAR {
+                return None;
+            }
+            let norm = normal.norm();
+            let lambert = if norm > 0. { (normal.dot(&light)/norm).abs() } else { 1. };
+            Some(ScreenVertex {
+                x: 0.5*w + focal*v.dot(&right)/z,
+                y: 0.5*h - focal*v.dot(&up)/z,
+                inv_z: 1./z,
+                light: ambient + (1. - ambient)*lambert
+            })
+        };
+        let screen = (0..=n).map(|i| (0..=n).map(|j| project(&grid[i][j], &normals[i][j])).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        for i in 0..n {
+            for j in 0..n {
+                if let (Some(a), Some(b), Some(c), Some(d)) = (screen[i][j], screen[i + 1][j], screen[i + 1][j + 1], screen[i][j + 1]) {
+                    self.fill_triangle(&a, &b, &c, color);
+                    self.fill_triangle(&a, &c, &d, color);
+                }
+            }
+        }
+    }
+
+    ///
+    /// Fills a triangle testing the pixel centers against its edges.
+    /// 
+    fn fill_triangle(&mut self, a: &ScreenVertex, b: &ScreenVertex, c: &ScreenVertex, color: [u8; 3]) {
+        let edge = |p: &ScreenVertex, q: &ScreenVertex, x: f64, y: f64| (q.x - p.x)*(y - p.y) - (q.y - p.y)*(x - p.x);
+        let area = edge(a, b, c.x, c.y);
+        if area.abs() < 1E-12 {
+            return;
+        }
+        let x0 = a.x.min(b.x).min(c.x).floor().max(0.) as usize;
+        let y0 = a.y.min(b.y).min(c.y).floor().max(0.) as usize;
+        let x1 = (a.x.max(b.x).max(c.x).ceil().max(0.) as usize).min(self.image.width);
+        let y1 = (a.y.max(b.y).max(c.y).ceil().max(0.) as usize).min(self.image.height);
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let (px, py) = (x as f64 + 0.5, y as f64 + 0.5);
+                let wa = edge(b, c, px, py)/area;
+                let wb = edge(c, a, px, py)/area;
+                let wc = edge(a, b, px, py)/area;
+                if wa < 0. || wb < 0. || wc < 0. {
+                    continue;
+                }
+                let inv_z = wa*a.inv_z + wb*b.inv_z + wc*c.inv_z;
+                let index = y*self.image.width + x;
+                if inv_z <= self.depth[index] {
+                    continue;
+                }
+                self.depth[index] = inv_z;
+                let light = (wa*a.light + wb*b.light + wc*c.light).min(1.);
+                for (k, c) in color.iter().enumerate() {
+                    self.image.data[3*index + k] = ((*c as f64)*light).round() as u8;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bezier::{BezierSurf, BezierTeapot};
+    use crate::core::{RealPoint3d, p3};
+    use crate::io::{Camera, Rasterizer, RasterOptions};
+    use array2d::Array2D;
+
+    fn square(z: f64, s: f64) -> BezierSurf<3> {
+        BezierSurf { data: Array2D::from_rows(&[
+            vec![p3(-s, -s, z), p3(-s, s, z)],
+            vec![p3(s, -s, z), p3(s, s, z)]
+        ]) }
+    }
+
+    #[test]
+    fn test_depth() {
+        let mut raster = Rasterizer::new(RasterOptions {
+            width: 40,
+            height: 30,
+            camera: Camera::orbit(&RealPoint3d::origin(), 10., 0., 90., 30.),
+            tessellation: 2,
+            ..RasterOptions::default()
+        });
+        // The camera looks down the z axis: the upper square hides the center of the
+        // lower one, which is larger, whatever the drawing order.
+        raster.draw_patches(&[square(1., 1.)], Some([0, 0, 255]));
+        raster.draw_patches(&[square(-1., 2.)], Some([255, 0, 0]));
+        let image = raster.image();
+        assert_eq!(image.pixel(20, 15), [0, 0, 255]);
+        assert_eq!(image.pixel(0, 0), [255, 255, 255]);
+        let row = (0..40).map(|x| image.pixel(x, 15)).collect::<Vec<[u8; 3]>>();
+        let blue = row.iter().filter(|c| **c == [0, 0, 255]).count();
+        let red = row.iter().filter(|c| **c == [255, 0, 0]).count();
+        assert!(blue > 0 && red > 0 && blue + red < 40);
+    }
+
+    #[test]
+    fn test_write() {
+        let patches = BezierTeapot::build_patches();
+        let mut raster = Rasterizer::new(RasterOptions {
+            width: 64,
+            height: 48,
+            camera: Camera::framing(&patches, -60., 25., 30.),
+            tessellation: 4,
+            ..RasterOptions::default()
+        });
+        raster.draw_patches(&patches, None);
+        let image = raster.image();
+        assert!(image.data.chunks(3).any(|c| c != [255, 255, 255]));
+        assert_eq!(image.pixel(0, 0), [255, 255, 255]);
+
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n64 48\n255\n"));
+        assert_eq!(ppm.len(), 13 + 64*48*3);
+        let mut png = Vec::new();
+        image.write_png(&mut png).unwrap();
+        assert!(png.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]));
+    }
+}
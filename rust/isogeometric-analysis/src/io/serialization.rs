@@ -0,0 +1,365 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+// Serde support for the geometric types, enabled by the "serde" feature. The JSON schema
+// is documented in README.md: points are arrays of coordinates, matrices arrays of rows,
+// and curves and surfaces objects holding degrees, control points and, where relevant,
+// weights and knots. Deserialization validates the data and fails for inconsistent input.
+
+use crate::bezier::{BezierCurve, BezierSurf, RatBezierCurve};
+use crate::bspline::{BsplineCurve, BsplineSurf};
+use crate::core::{MatElement, Point, RealPoint, RectMatrix};
+use crate::nurbs::{NurbsCurve, NurbsSurf};
+use array2d::Array2D;
+use serde::de::Error;
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+impl<T: MatElement + Serialize, const SIZE: usize> Serialize for Point<T, SIZE> {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        let mut seq = serializer.serialize_seq(Some(SIZE))?;
+        for i in 0..SIZE {
+            seq.serialize_element(&self.value(i))?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de, T: MatElement + Deserialize<'de>, const SIZE: usize> Deserialize<'de> for Point<T, SIZE> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<T>::deserialize(deserializer)?;
+        if values.len() != SIZE {
+            return Err(D::Error::invalid_length(values.len(), &format!("{} coordinates", SIZE).as_str()));
+        }
+        let mut p = Point::<T, SIZE>::origin();
+        for (i, v) in values.into_iter().enumerate() {
+            p.set_value(i, v);
+        }
+        Ok(p)
+    }
+}
+
+impl<T: MatElement + Serialize, const R: usize, const C: usize> Serialize for RectMatrix<T, R, C> {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        (0..R).map(|i| self.row_to_vec(i).to_vec()).collect::<Vec<Vec<T>>>().serialize(serializer)
+    }
+}
+
+impl<'de, T: MatElement + Deserialize<'de>, const R: usize, const C: usize> Deserialize<'de> for RectMatrix<T, R, C> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let rows = Vec::<Vec<T>>::deserialize(deserializer)?;
+        if rows.len() != R || rows.iter().any(|r| r.len() != C) {
+            return Err(D::Error::custom(format!("expected a {}x{} matrix", R, C)));
+        }
+        let mut m = RectMatrix::<T, R, C>::zeros();
+        for (i, row) in rows.into_iter().enumerate() {
+            for (j, v) in row.into_iter().enumerate() {
+                m.set_value(i, j, v);
+            }
+        }
+        Ok(m)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CurveData<const S: usize> {
+    degree: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    knots: Option<Vec<f64>>,
+    control_points: Vec<RealPoint<S>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weights: Option<Vec<f64>>
+}
+
+#[derive(Serialize, Deserialize)]
+struct SurfData<const S: usize> {
+    degree_xi: usize,
+    degree_eta: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    knots_xi: Option<Vec<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    knots_eta: Option<Vec<f64>>,
+    control_points: Vec<Vec<RealPoint<S>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weights: Option<Vec<Vec<f64>>>
+}
+
+impl<const S: usize> CurveData<S> {
+    ///
+    /// Checks the number of control points against the degree of a Bezier curve, the
+    /// presence of the fields and the sign of the weights.
+    /// 
+    fn check<E: Error>(&self, knots: bool, weights: bool) -> Result<(), E> {
+        if self.control_points.is_empty() {
+            return Err(E::custom("no control points"));
+        }
+        if !knots && self.degree + 1 != self.control_points.len() {
+            return Err(E::custom(format!("degree {} needs {} control points", self.degree, self.degree + 1)));
+        }
+        if knots != self.knots.is_some() {
+            return Err(E::custom(if knots { "missing field `knots`" } else { "unexpected field `knots`" }));
+        }
+        match &self.weights {
+            Some(w) if weights && w.len() != self.control_points.len() =>
+                Err(E::custom("weights do not match control points")),
+            Some(w) if w.iter().any(|w| *w <= 0f64) => Err(E::custom("weights must be positive")),
+            Some(_) if !weights => Err(E::custom("unexpected field `weights`")),
+            None if weights => Err(E::custom("missing field `weights`")),
+            _ => Ok(())
+        }
+    }
+}
+
+impl<const S: usize> SurfData<S> {
+    ///
+    /// Checks the grid of control points against the degrees of a Bezier surface, the
+    /// presence of the fields and the sign of the weights.
+    /// 
+    fn check<E: Error>(&self, knots: bool, weights: bool) -> Result<(), E> {
+        let rows = &self.control_points;
+        if rows.is_empty() || rows[0].is_empty() || rows.iter().any(|r| r.len() != rows[0].len()) {
+            return Err(E::custom("control points do not form a grid"));
+        }
+        if !knots && (self.degree_xi + 1 != rows.len() || self.degree_eta + 1 != rows[0].len()) {
+            return Err(E::custom(format!("degrees ({}, {}) need a {}x{} grid of control points",
+                self.degree_xi, self.degree_eta, self.degree_xi + 1, self.degree_eta + 1)));
+        }
+        if knots != (self.knots_xi.is_some() && self.knots_eta.is_some()) || (!knots && (self.knots_xi.is_some() || self.knots_eta.is_some())) {
+            return Err(E::custom(if knots { "missing field `knots_xi` or `knots_eta`" } else { "unexpected knots" }));
+        }
+        match &self.weights {
+            Some(w) if weights && (w.len() != rows.len() || w.iter().any(|r| r.len() != rows[0].len())) =>
+                Err(E::custom("weights do not match control points")),
+            Some(w) if w.iter().flatten().any(|w| *w <= 0f64) => Err(E::custom("weights must be positive")),
+            Some(_) if !weights => Err(E::custom("unexpected field `weights`")),
+            None if weights => Err(E::custom("missing field `weights`")),
+            _ => Ok(())
+        }
+    }
+}
+
+impl<const S: usize> Serialize for BezierCurve<S> {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        CurveData { degree: self.p.len() - 1, knots: None, control_points: self.p.clone(), weights: None }.serialize(serializer)
+    }
+}
+
+impl<'de, const S: usize> Deserialize<'de> for BezierCurve<S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = CurveData::<S>::deserialize(deserializer)?;
+        data.check::<D::Error>(false, false)?;
+        Ok(BezierCurve::create(data.control_points))
+    }
+}
+
+impl<const S: usize, const H: usize> Serialize for RatBezierCurve<S, H> {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        CurveData {
+            degree: self.p.len() - 1,
+            knots: None,
+            control_points: self.p.clone(),
+            weights: Some(self.weights.clone())
+        }.serialize(serializer)
+    }
+}
+
+impl<'de, const S: usize, const H: usize> Deserialize<'de> for RatBezierCurve<S, H> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if H != S + 1 {
+            return Err(D::Error::custom(format!("homogeneous dimension {} must be {}", H, S + 1)));
+        }
+        let data = CurveData::<S>::deserialize(deserializer)?;
+        data.check::<D::Error>(false, true)?;
+        Ok(RatBezierCurve::create(data.control_points, data.weights.unwrap()))
+    }
+}
+
+impl<const S: usize> Serialize for BezierSurf<S> {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        SurfData {
+            degree_xi: self.degree_xi() as usize,
+            degree_eta: self.degree_eta() as usize,
+            knots_xi: None,
+            knots_eta: None,
+            control_points: self.data.as_rows(),
+            weights: None
+        }.serialize(serializer)
+    }
+}
+
+impl<'de, const S: usize> Deserialize<'de> for BezierSurf<S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = SurfData::<S>::deserialize(deserializer)?;
+        data.check::<D::Error>(false, false)?;
+        Ok(BezierSurf { data: Array2D::from_rows(&data.control_points) })
+    }
+}
+
+impl<const S: usize> Serialize for BsplineCurve<S> {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        CurveData {
+            degree: self.degree,
+            knots: Some(self.knots.clone()),
+            control_points: self.p.clone(),
+            weights: None
+        }.serialize(serializer)
+    }
+}
+
+impl<'de, const S: usize> Deserialize<'de> for BsplineCurve<S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = CurveData::<S>::deserialize(deserializer)?;
+        data.check::<D::Error>(true, false)?;
+        BsplineCurve::create(data.control_points, data.knots.unwrap(), data.degree)
+            .ok_or_else(|| D::Error::custom("invalid B-spline curve"))
+    }
+}
+
+impl<const S: usize> Serialize for BsplineSurf<S> {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        SurfData {
+            degree_xi: self.degree_xi,
+            degree_eta: self.degree_eta,
+            knots_xi: Some(self.knots_xi.clone()),
+            knots_eta: Some(self.knots_eta.clone()),
+            control_points: self.data.as_rows(),
+            weights: None
+        }.serialize(serializer)
+    }
+}
+
+impl<'de, const S: usize> Deserialize<'de> for BsplineSurf<S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = SurfData::<S>::deserialize(deserializer)?;
+        data.check::<D::Error>(true, false)?;
+        BsplineSurf::create(Array2D::from_rows(&data.control_points), data.knots_xi.unwrap(), data.knots_eta.unwrap(),
+            data.degree_xi, data.degree_eta)
+            .ok_or_else(|| D::Error::custom("invalid B-spline surface"))
+    }
+}
+
+impl<const S: usize> Serialize for NurbsCurve<S> {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        CurveData {
+            degree: self.degree,
+            knots: Some(self.knots.clone()),
+            control_points: self.p.clone(),
+            weights: Some(self.weights.clone())
+        }.serialize(serializer)
+    }
+}
+
+impl<'de, const S: usize> Deserialize<'de> for NurbsCurve<S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = CurveData::<S>::deserialize(deserializer)?;
+        data.check::<D::Error>(true, true)?;
+        NurbsCurve::create(data.control_points, data.weights.unwrap(), data.knots.unwrap(), data.degree)
+            .ok_or_else(|| D::Error::custom("invalid NURBS curve"))
+    }
+}
+
+impl<const S: usize> Serialize for NurbsSurf<S> {
+    fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+        SurfData {
+            degree_xi: self.degree_xi,
+            degree_eta: self.degree_eta,
+            knots_xi: Some(self.knots_xi.clone()),
+            knots_eta: Some(self.knots_eta.clone()),
+            control_points: self.data.as_rows(),
+            weights: Some(self.weights.as_rows())
+        }.serialize(serializer)
+    }
+}
+
+impl<'de, const S: usize> Deserialize<'de> for NurbsSurf<S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = SurfData::<S>::deserialize(deserializer)?;
+        data.check::<D::Error>(true, true)?;
+        NurbsSurf::create(Array2D::from_rows(&data.control_points), Array2D::from_rows(&data.weights.unwrap()),
+            data.knots_xi.unwrap(), data.knots_eta.unwrap(), data.degree_xi, data.degree_eta)
+            .ok_or_else(|| D::Error::custom("invalid NURBS surface"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bezier::{BezierCircle, BezierCurve, BezierSurf, BezierTeapot, RatBezierCurve};
+    use crate::core::{Mapping, RealPoint2d, RealPoint3d, RealRectMatrix, p1, p2};
+    use crate::nurbs::{NurbsCurve, NurbsSurf};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_schema() {
+        let p = RealPoint3d::point3d(1., -2.5, 0.);
+        assert_eq!(serde_json::to_string(&p).unwrap(), "[1.0,-2.5,0.0]");
+        assert_eq!(serde_json::from_str::<RealPoint3d>("[1,-2.5,0]").unwrap(), p);
+        assert!(serde_json::from_str::<RealPoint3d>("[1,2]").is_err());
+
+        let m = RealRectMatrix::<2, 3>::mat_from_vec([[1., 2., 3.], [4., 5., 6.]]);
+        assert_eq!(serde_json::to_string(&m).unwrap(), "[[1.0,2.0,3.0],[4.0,5.0,6.0]]");
+        assert_eq!(serde_json::from_str::<RealRectMatrix<2, 3>>("[[1,2,3],[4,5,6]]").unwrap(), m);
+        assert!(serde_json::from_str::<RealRectMatrix<3, 2>>("[[1,2,3],[4,5,6]]").is_err());
+
+        let bez = BezierCurve::create(vec![RealPoint2d::point2d(0., 0.), RealPoint2d::point2d(1., 1.)]);
+        assert_eq!(serde_json::to_string(&bez).unwrap(), r#"{"degree":1,"control_points":[[0.0,0.0],[1.0,1.0]]}"#);
+        assert!(serde_json::from_str::<BezierCurve<2>>(r#"{"degree":2,"control_points":[[0,0],[1,1]]}"#).is_err());
+        assert!(serde_json::from_str::<BezierCurve<2>>(r#"{"degree":1,"control_points":[[0,0],[1,1]],"weights":[1,1]}"#).is_err());
+
+        let nurbs = serde_json::from_str::<NurbsCurve<2>>(
+            r#"{"degree":1,"knots":[0,0,1,1],"control_points":[[0,0],[1,1]],"weights":[1,2]}"#).unwrap();
+        assert_eq!(nurbs.weights, vec![1., 2.]);
+        assert!(serde_json::from_str::<NurbsCurve<2>>(
+            r#"{"degree":1,"knots":[0,1],"control_points":[[0,0],[1,1]],"weights":[1,2]}"#).is_err());
+
+        // Weights must be positive and rational curves need one more homogeneous coordinate.
+        let error = |e: serde_json::Error| e.to_string();
+        assert!(error(serde_json::from_str::<NurbsCurve<2>>(
+            r#"{"degree":1,"knots":[0,0,1,1],"control_points":[[0,0],[1,1]],"weights":[1,0]}"#).err().unwrap()).starts_with("weights must be positive"));
+        assert!(error(serde_json::from_str::<NurbsSurf<2>>(
+            r#"{"degree_xi":0,"degree_eta":0,"knots_xi":[0,1],"knots_eta":[0,1],"control_points":[[[0,0]]],"weights":[[-1]]}"#).err().unwrap())
+            .starts_with("weights must be positive"));
+        assert!(serde_json::from_str::<RatBezierCurve<2, 3>>(r#"{"degree":1,"control_points":[[0,0],[1,1]],"weights":[1,-2]}"#).is_err());
+        assert!(error(serde_json::from_str::<RatBezierCurve<2, 4>>(r#"{"degree":1,"control_points":[[0,0],[1,1]],"weights":[1,2]}"#).err().unwrap())
+            .starts_with("homogeneous dimension 4 must be 3"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let arcs = BezierCircle { radius: 2, segments: 4 }.compute().unwrap();
+        let arc = &arcs[1];
+        let json = serde_json::to_string(arc).unwrap();
+        let read = serde_json::from_str::<RatBezierCurve<2, 3>>(&json).unwrap();
+        assert_eq!(read.weights, arc.weights);
+        for patch in BezierTeapot::build_patches() {
+            let json = serde_json::to_string(&patch).unwrap();
+            let read = serde_json::from_str::<BezierSurf<3>>(&json).unwrap();
+            assert_eq!(read.data, patch.data);
+            let nurbs = NurbsSurf::from_bezier(&patch);
+            let read = serde_json::from_str::<NurbsSurf<3>>(&serde_json::to_string(&nurbs).unwrap()).unwrap();
+            let xi = p2(0.3, 0.8);
+            assert_approx_eq!(RealPoint3d, read.evaluate(&xi), patch.evaluate(&xi), epsilon = 1E-12);
+        }
+        let nurbs = NurbsCurve::from_rat_bezier(arc);
+        let read = serde_json::from_str::<NurbsCurve<2>>(&serde_json::to_string(&nurbs).unwrap()).unwrap();
+        assert_approx_eq!(RealPoint2d, read.evaluate(&p1(0.4)), arc.evaluate(&p1(0.4)), epsilon = 1E-12);
+    }
+}
@@ -0,0 +1,633 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::core::RealPoint3d;
+use crate::nurbs::{NurbsCurve, NurbsSurf};
+use array2d::Array2D;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+
+///
+/// Error raised when reading STEP data.
+/// 
+#[derive(Debug)]
+pub enum StepError {
+    /// The data could not be read.
+    Io(std::io::Error),
+    /// The data does not follow the syntax of ISO 10303-21. The 1-based number of the
+    /// offending line is reported.
+    Syntax { line: usize, reason: String }
+}
+
+impl Display for StepError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            StepError::Io(error) => write!(f, "{}", error),
+            StepError::Syntax { line, reason } => write!(f, "line {}: {}", line, reason)
+        }
+    }
+}
+
+impl std::error::Error for StepError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StepError::Io(error) => Some(error),
+            _ => None
+        }
+    }
+}
+
+///
+/// Token of the exchange structure.
+/// 
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ref(usize),
+    Keyword(String),
+    Str(String),
+    Enum(String),
+    Int(i64),
+    Real(f64),
+    LParen,
+    RParen,
+    Comma,
+    Semicolon,
+    Equals,
+    Unset
+}
+
+///
+/// Splits ISO 10303-21 text into tokens, each with its line number. Comments are dropped.
+/// 
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>, StepError> {
+    let chars = text.chars().collect::<Vec<char>>();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+    let error = |line: usize, reason: String| StepError::Syntax { line, reason };
+    while i < chars.len() {
+        let c = chars[i];
+        let start = line;
+        match c {
+            '\n' => { line += 1; i += 1; },
+            c if c.is_whitespace() => i += 1,
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    if chars[i] == '\n' { line += 1; }
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err(error(start, "unterminated comment".to_string()));
+                }
+                i += 2;
+            },
+            '(' => { tokens.push((Token::LParen, line)); i += 1; },
+            ')' => { tokens.push((Token::RParen, line)); i += 1; },
+            ',' => { tokens.push((Token::Comma, line)); i += 1; },
+            ';' => { tokens.push((Token::Semicolon, line)); i += 1; },
+            '=' => { tokens.push((Token::Equals, line)); i += 1; },
+            '$' | '*' => { tokens.push((Token::Unset, line)); i += 1; },
+            '\'' => {
+                let mut s = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(error(start, "unterminated string".to_string())),
+                        Some('\'') if chars.get(i + 1) == Some(&'\'') => { s.push('\''); i += 2; },
+                        Some('\'') => { i += 1; break; },
+                        Some(c) => {
+                            if *c == '\n' { line += 1; }
+                            s.push(*c);
+                            i += 1;
+                        }
+                    }
+                }
+                tokens.push((Token::Str(s), start));
+            },
+            '.' if chars.get(i + 1).map(|c| c.is_ascii_alphabetic() || *c == '_').unwrap_or(false) => {
+                let end = (i + 1..chars.len()).find(|k| chars[*k] == '.')
+                    .ok_or_else(|| error(line, "unterminated enumeration".to_string()))?;
+                tokens.push((Token::Enum(chars[(i + 1)..end].iter().collect()), line));
+                i = end + 1;
+            },
+            '#' => {
+                let end = (i + 1..chars.len()).find(|k| !chars[*k].is_ascii_digit()).unwrap_or(chars.len());
+                let id = chars[(i + 1)..end].iter().collect::<String>().parse()
+                    .map_err(|_| error(line, "invalid instance name".to_string()))?;
+                tokens.push((Token::Ref(id), line));
+                i = end;
+            },
+            c if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' => {
+                let end = (i + 1..chars.len())
+                    .find(|k| !(chars[*k].is_ascii_digit() || ".eE+-".contains(chars[*k])))
+                    .unwrap_or(chars.len());
+                let s = chars[i..end].iter().collect::<String>();
+                let token = if s.contains(['.', 'e', 'E']) {
+                    Token::Real(s.parse().map_err(|_| error(line, format!("invalid real \"{}\"", s)))?)
+                }
+                else {
+                    Token::Int(s.parse().map_err(|_| error(line, format!("invalid integer \"{}\"", s)))?)
+                };
+                tokens.push((token, line));
+                i = end;
+            },
+            c if c.is_ascii_alphabetic() || c == '_' || c == '!' => {
+                let end = (i + 1..chars.len())
+                    .find(|k| !(chars[*k].is_ascii_alphanumeric() || chars[*k] == '_' || chars[*k] == '-'))
+                    .unwrap_or(chars.len());
+                tokens.push((Token::Keyword(chars[i..end].iter().collect::<String>().to_uppercase()), line));
+                i = end;
+            },
+            c => return Err(error(line, format!("unexpected character '{}'", c)))
+        }
+    }
+    Ok(tokens)
+}
+
+///
+/// Parameter of an entity instance.
+/// 
+#[derive(Debug, Clone, PartialEq)]
+enum Param {
+    Ref(usize),
+    Int(i64),
+    Real(f64),
+    Str(String),
+    Enum(String),
+    List(Vec<Param>),
+    Typed(String, Vec<Param>),
+    Unset
+}
+
+impl Param {
+    fn real(&self) -> Option<f64> {
+        match self {
+            Param::Real(v) => Some(*v),
+            Param::Int(v) => Some(*v as f64),
+            _ => None
+        }
+    }
+
+    fn int(&self) -> Option<usize> {
+        match self {
+            Param::Int(v) if *v >= 0 => Some(*v as usize),
+            _ => None
+        }
+    }
+
+    fn list(&self) -> Option<&Vec<Param>> {
+        match self {
+            Param::List(v) => Some(v),
+            _ => None
+        }
+    }
+
+    ///
+    /// Collects the instance names referenced by this parameter.
+    /// 
+    fn refs(&self, out: &mut Vec<usize>) {
+        match self {
+            Param::Ref(id) => out.push(*id),
+            Param::List(v) | Param::Typed(_, v) => v.iter().for_each(|p| p.refs(out)),
+            _ => {}
+        }
+    }
+}
+
+///
+/// Entity instance: a simple record or a complex one made of several partial records.
+/// 
+type Record = (String, Vec<Param>);
+
+#[derive(Debug, Clone)]
+struct Instance {
+    records: Vec<Record>,
+    line: usize
+}
+
+impl Instance {
+    fn record(&self, name: &str) -> Option<&Vec<Param>> {
+        self.records.iter().find(|(n, _)| n == name).map(|(_, p)| p)
+    }
+
+    fn is_complex(&self) -> bool {
+        self.records.len() > 1
+    }
+}
+
+///
+/// Recursive descent parser over the tokens of the data section.
+/// 
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    next: usize
+}
+
+impl Parser {
+    fn line(&self) -> usize {
+        self.tokens.get(self.next).or_else(|| self.tokens.last()).map(|(_, l)| *l).unwrap_or(0)
+    }
+
+    fn error(&self, reason: &str) -> StepError {
+        StepError::Syntax { line: self.line(), reason: reason.to_string() }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(t, _)| t)
+    }
+
+    fn take(&mut self) -> Result<Token, StepError> {
+        let t = self.tokens.get(self.next).map(|(t, _)| t.clone()).ok_or_else(|| self.error("unexpected end of data"))?;
+        self.next += 1;
+        Ok(t)
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), StepError> {
+        if self.take()? != token {
+            self.next -= 1;
+            return Err(self.error(&format!("expected {:?}", token)));
+        }
+        Ok(())
+    }
+
+    ///
+    /// Parses a parenthesized, comma separated list of parameters.
+    /// 
+    fn params(&mut self) -> Result<Vec<Param>, StepError> {
+        self.expect(Token::LParen)?;
+        let mut params = Vec::new();
+        if self.peek() == Some(&Token::RParen) {
+            self.next += 1;
+            return Ok(params);
+        }
+        loop {
+            params.push(self.param()?);
+            match self.take()? {
+                Token::Comma => continue,
+                Token::RParen => return Ok(params),
+                _ => {
+                    self.next -= 1;
+                    return Err(self.error("expected ',' or ')'"));
+                }
+            }
+        }
+    }
+
+    fn param(&mut self) -> Result<Param, StepError> {
+        Ok(match self.take()? {
+            Token::Ref(id) => Param::Ref(id),
+            Token::Int(v) => Param::Int(v),
+            Token::Real(v) => Param::Real(v),
+            Token::Str(s) => Param::Str(s),
+            Token::Enum(s) => Param::Enum(s),
+            Token::Unset => Param::Unset,
+            Token::LParen => {
+                self.next -= 1;
+                Param::List(self.params()?)
+            },
+            Token::Keyword(name) => Param::Typed(name, self.params()?),
+            _ => {
+                self.next -= 1;
+                return Err(self.error("unexpected token"));
+            }
+        })
+    }
+
+    ///
+    /// Parses the instances of all the data sections.
+    /// 
+    fn instances(&mut self) -> Result<BTreeMap<usize, Instance>, StepError> {
+        let mut instances = BTreeMap::new();
+        let mut in_data = false;
+        while let Some(token) = self.peek().cloned() {
+            match token {
+                Token::Keyword(ref k) if k == "DATA" && !in_data => {
+                    self.next += 1;
+                    if self.peek() == Some(&Token::LParen) {
+                        self.params()?;
+                    }
+                    self.expect(Token::Semicolon)?;
+                    in_data = true;
+                },
+                Token::Keyword(ref k) if k == "ENDSEC" => {
+                    self.next += 1;
+                    self.expect(Token::Semicolon)?;
+                    in_data = false;
+                },
+                Token::Ref(id) if in_data => {
+                    let line = self.line();
+                    self.next += 1;
+                    self.expect(Token::Equals)?;
+                    let records = match self.take()? {
+                        Token::Keyword(name) => vec![(name, self.params()?)],
+                        Token::LParen => {
+                            let mut records = Vec::new();
+                            while let Some(Token::Keyword(name)) = self.peek().cloned() {
+                                self.next += 1;
+                                records.push((name, self.params()?));
+                            }
+                            self.expect(Token::RParen)?;
+                            records
+                        },
+                        _ => {
+                            self.next -= 1;
+                            return Err(self.error("expected an entity"));
+                        }
+                    };
+                    self.expect(Token::Semicolon)?;
+                    if instances.insert(id, Instance { records, line }).is_some() {
+                        return Err(StepError::Syntax { line, reason: format!("duplicate instance #{}", id) });
+                    }
+                },
+                _ if in_data => return Err(self.error("expected an instance")),
+                _ => self.next += 1
+            }
+        }
+        Ok(instances)
+    }
+}
+
+///
+/// B-spline geometry read from STEP (ISO 10303-21) data. Instances of
+/// B_SPLINE_CURVE_WITH_KNOTS and B_SPLINE_SURFACE_WITH_KNOTS, either simple or in complex
+/// instances together with RATIONAL_B_SPLINE_CURVE and RATIONAL_B_SPLINE_SURFACE, are
+/// mapped to NURBS with unit weights when not rational. Other entities are skipped.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use isogeometric_analysis::io::StepModel;
+/// let data = "ISO-10303-21;\nHEADER;\nENDSEC;\nDATA;\n\
+///     #1=CARTESIAN_POINT('',(0.,0.,0.));\n\
+///     #2=CARTESIAN_POINT('',(1.,1.,0.));\n\
+///     #3=B_SPLINE_CURVE_WITH_KNOTS('',1,(#1,#2),.POLYLINE_FORM.,.F.,.F.,(2,2),(0.,1.),.UNSPECIFIED.);\n\
+///     ENDSEC;\nEND-ISO-10303-21;\n";
+/// let model = StepModel::read(data.as_bytes()).unwrap();
+/// assert_eq!(model.curves.len(), 1);
+/// ```
+/// 
+#[derive(Debug, Clone, Default)]
+pub struct StepModel {
+    pub curves: Vec<NurbsCurve<3>>,
+    pub surfaces: Vec<NurbsSurf<3>>
+}
+
+impl StepModel {
+    ///
+    /// Reads the B-spline curves and surfaces contained in STEP data, in the order of
+    /// their instance names.
+    /// 
+    pub fn read<R: BufRead>(mut reader: R) -> Result<StepModel, StepError> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text).map_err(StepError::Io)?;
+        let mut parser = Parser { tokens: tokenize(&text)?, next: 0 };
+        let instances = parser.instances()?;
+
+        let mut model = StepModel::default();
+        let mut used = HashSet::<usize>::new();
+        let mut skipped = BTreeMap::<String, usize>::new();
+        for (id, instance) in &instances {
+            let curve = instance.record("B_SPLINE_CURVE_WITH_KNOTS").is_some();
+            let surface = instance.record("B_SPLINE_SURFACE_WITH_KNOTS").is_some();
+            let result = if curve {
+                map_curve(instance, &instances).map(|c| model.curves.push(c))
+            }
+            else if surface {
+                map_surface(instance, &instances).map(|s| model.surfaces.push(s))
+            }
+            else {
+                continue;
+            };
+            match result {
+                Ok(()) => {
+                    let mut refs = Vec::new();
+                    instance.records.iter().for_each(|(_, params)| params.iter().for_each(|p| p.refs(&mut refs)));
+                    used.extend(refs);
+                    used.insert(*id);
+                },
+                Err(reason) => log::warn!("Skipping STEP instance #{} at line {}: {}", id, instance.line, reason)
+            }
+        }
+
+        for (id, instance) in &instances {
+            if !used.contains(id) {
+                let name = instance.records.iter().map(|(n, _)| n.as_str()).collect::<Vec<&str>>().join(" ");
+                *skipped.entry(name).or_insert(0) += 1;
+            }
+        }
+        for (name, count) in &skipped {
+            log::warn!("Skipped {} unsupported STEP instances of {}", count, name);
+        }
+        Ok(model)
+    }
+}
+
+///
+/// Returns the record holding the B-spline attributes of an instance: the simple
+/// instance itself or the named partial record of a complex one, whose parameters do
+/// not include the name of the entity.
+/// 
+fn spline_record<'a>(instance: &'a Instance, simple: &str, partial: &str) -> Result<&'a [Param], String> {
+    if instance.is_complex() {
+        instance.record(partial).map(|p| p.as_slice()).ok_or_else(|| format!("missing {}", partial))
+    }
+    else {
+        instance.record(simple).map(|p| if p.is_empty() { p.as_slice() } else { &p[1..] }).ok_or_else(|| format!("missing {}", simple))
+    }
+}
+
+///
+/// Resolves a reference to a cartesian point.
+/// 
+fn point(param: &Param, instances: &BTreeMap<usize, Instance>) -> Result<RealPoint3d, String> {
+    let id = match param {
+        Param::Ref(id) => *id,
+        _ => return Err("control points must be references".to_string())
+    };
+    let coords = instances.get(&id)
+        .and_then(|i| i.record("CARTESIAN_POINT"))
+        .and_then(|p| p.get(1))
+        .and_then(|p| p.list())
+        .ok_or_else(|| format!("#{} is not a cartesian point", id))?;
+    let c = coords.iter().map(|c| c.real()).collect::<Option<Vec<f64>>>()
+        .ok_or_else(|| format!("invalid coordinates in #{}", id))?;
+    if c.is_empty() || c.len() > 3 {
+        return Err(format!("invalid coordinates in #{}", id));
+    }
+    Ok(RealPoint3d::point3d(c[0], *c.get(1).unwrap_or(&0f64), *c.get(2).unwrap_or(&0f64)))
+}
+
+fn reals(param: Option<&Param>, what: &str) -> Result<Vec<f64>, String> {
+    param.and_then(|p| p.list())
+        .and_then(|l| l.iter().map(|v| v.real()).collect::<Option<Vec<f64>>>())
+        .ok_or_else(|| format!("invalid {}", what))
+}
+
+///
+/// Expands distinct knots and their multiplicities into a knot vector.
+/// 
+fn knot_vector(mults: Option<&Param>, knots: Option<&Param>) -> Result<Vec<f64>, String> {
+    let mults = mults.and_then(|p| p.list())
+        .and_then(|l| l.iter().map(|v| v.int()).collect::<Option<Vec<usize>>>())
+        .ok_or_else(|| "invalid knot multiplicities".to_string())?;
+    let knots = reals(knots, "knots")?;
+    if mults.len() != knots.len() {
+        return Err("knots and multiplicities differ in length".to_string());
+    }
+    Ok(knots.iter().zip(mults.iter()).flat_map(|(k, m)| vec![*k; *m]).collect())
+}
+
+fn map_curve(instance: &Instance, instances: &BTreeMap<usize, Instance>) -> Result<NurbsCurve<3>, String> {
+    let (bspline, knots) = if instance.is_complex() {
+        (spline_record(instance, "", "B_SPLINE_CURVE")?, spline_record(instance, "", "B_SPLINE_CURVE_WITH_KNOTS")?)
+    }
+    else {
+        // Simple instances list the B_SPLINE_CURVE attributes first.
+        let all = spline_record(instance, "B_SPLINE_CURVE_WITH_KNOTS", "")?;
+        if all.len() < 5 {
+            return Err("too few parameters".to_string());
+        }
+        all.split_at(5)
+    };
+    let degree = bspline.first().and_then(|p| p.int()).ok_or_else(|| "invalid degree".to_string())?;
+    let points = bspline.get(1).and_then(|p| p.list()).ok_or_else(|| "invalid control points".to_string())?
+        .iter().map(|p| point(p, instances)).collect::<Result<Vec<RealPoint3d>, String>>()?;
+    let knots = knot_vector(knots.first(), knots.get(1))?;
+    let weights = match instance.record("RATIONAL_B_SPLINE_CURVE") {
+        Some(r) => reals(r.first(), "weights")?,
+        None => vec![1f64; points.len()]
+    };
+    NurbsCurve::create(points, weights, knots, degree).ok_or_else(|| "invalid B-spline curve".to_string())
+}
+
+fn map_surface(instance: &Instance, instances: &BTreeMap<usize, Instance>) -> Result<NurbsSurf<3>, String> {
+    let (bspline, knots) = if instance.is_complex() {
+        (spline_record(instance, "", "B_SPLINE_SURFACE")?, spline_record(instance, "", "B_SPLINE_SURFACE_WITH_KNOTS")?)
+    }
+    else {
+        let all = spline_record(instance, "B_SPLINE_SURFACE_WITH_KNOTS", "")?;
+        if all.len() < 7 {
+            return Err("too few parameters".to_string());
+        }
+        all.split_at(7)
+    };
+    let p = bspline.first().and_then(|p| p.int()).ok_or_else(|| "invalid degree".to_string())?;
+    let q = bspline.get(1).and_then(|p| p.int()).ok_or_else(|| "invalid degree".to_string())?;
+    let rows = bspline.get(2).and_then(|p| p.list()).ok_or_else(|| "invalid control points".to_string())?
+        .iter()
+        .map(|row| row.list().ok_or_else(|| "invalid control points".to_string())?
+            .iter().map(|p| point(p, instances)).collect::<Result<Vec<RealPoint3d>, String>>())
+        .collect::<Result<Vec<Vec<RealPoint3d>>, String>>()?;
+    if rows.is_empty() || rows.iter().any(|r| r.len() != rows[0].len()) {
+        return Err("control points do not form a grid".to_string());
+    }
+    let knots_xi = knot_vector(knots.first(), knots.get(2))?;
+    let knots_eta = knot_vector(knots.get(1), knots.get(3))?;
+    let weights = match instance.record("RATIONAL_B_SPLINE_SURFACE") {
+        Some(r) => r.first().and_then(|p| p.list()).ok_or_else(|| "invalid weights".to_string())?
+            .iter().map(|row| reals(Some(row), "weights")).collect::<Result<Vec<Vec<f64>>, String>>()?,
+        None => vec![vec![1f64; rows[0].len()]; rows.len()]
+    };
+    if weights.len() != rows.len() || weights.iter().any(|r| r.len() != rows[0].len()) {
+        return Err("weights do not match control points".to_string());
+    }
+    NurbsSurf::create(Array2D::from_rows(&rows), Array2D::from_rows(&weights), knots_xi, knots_eta, p, q)
+        .ok_or_else(|| "invalid B-spline surface".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{StepModel, StepError};
+    use crate::io::step::{tokenize, Token};
+    use crate::bezier::BezierTeapot;
+    use crate::core::{Mapping, RealPoint3d, p1, p2};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize("#12 = FOO('it''s (a) /*', .T., -1.5E-3, 42, $, *); /* note\n */ BAR()")
+            .unwrap().into_iter().map(|(t, _)| t).collect::<Vec<Token>>();
+        assert_eq!(tokens, vec![
+            Token::Ref(12), Token::Equals, Token::Keyword("FOO".to_string()), Token::LParen,
+            Token::Str("it's (a) /*".to_string()), Token::Comma, Token::Enum("T".to_string()), Token::Comma,
+            Token::Real(-1.5E-3), Token::Comma, Token::Int(42), Token::Comma, Token::Unset, Token::Comma,
+            Token::Unset, Token::RParen, Token::Semicolon, Token::Keyword("BAR".to_string()), Token::LParen,
+            Token::RParen
+        ]);
+        assert_eq!(tokenize("\n\n#1='abc").unwrap_err().to_string(), "line 3: unterminated string");
+    }
+
+    #[test]
+    fn test_read() {
+        let patch = &BezierTeapot::build_patches()[0];
+        let mut data = String::from("ISO-10303-21;\nHEADER;\nFILE_NAME('teapot.stp','2026-10-18',(''),(''),'','','');\nENDSEC;\nDATA;\n");
+        for (k, p) in patch.data.elements_row_major_iter().enumerate() {
+            data += &format!("#{}=CARTESIAN_POINT('',({:?},{:?},{:?}));\n", k + 1, p.x(), p.y(), p.z());
+        }
+        let rows = (0..4).map(|i| format!("({})", (0..4).map(|j| format!("#{}", 4*i + j + 1)).collect::<Vec<_>>().join(",")))
+            .collect::<Vec<_>>().join(",");
+        data += &format!("#20=B_SPLINE_SURFACE_WITH_KNOTS('patch',3,3,({}),.UNSPECIFIED.,.F.,.F.,.F.,(4,4),(4,4),(0.,1.),(0.,1.),.UNSPECIFIED.);\n", rows);
+        data += "#21=ADVANCED_FACE('',(#30),#20,.T.);\n";
+
+        // Quarter of a unit circle in the plane z = 1.
+        data += "#31=CARTESIAN_POINT('',(1.,0.,1.));\n#32=CARTESIAN_POINT('',(1.,1.,1.));\n#33=CARTESIAN_POINT('',(0.,1.,1.));\n";
+        data += "#34=( BOUNDED_CURVE() B_SPLINE_CURVE(2,(#31,#32,#33),.CIRCULAR_ARC.,.F.,.F.) \
+            B_SPLINE_CURVE_WITH_KNOTS((3,3),(0.,1.),.PIECEWISE_BEZIER_KNOTS.) CURVE() GEOMETRIC_REPRESENTATION_ITEM() \
+            RATIONAL_B_SPLINE_CURVE((1.,0.70710678118654752,1.)) REPRESENTATION_ITEM('') );\n";
+
+        // Rational bilinear surface, with weights that do not change the geometry.
+        data += "#41=CARTESIAN_POINT('',(0.,0.));\n#42=CARTESIAN_POINT('',(0.,1.));\n#43=CARTESIAN_POINT('',(1.,0.));\n#44=CARTESIAN_POINT('',(1.,1.));\n";
+        data += "#45=( BOUNDED_SURFACE() B_SPLINE_SURFACE(1,1,((#41,#42),(#43,#44)),.PLANE_SURF.,.F.,.F.,.F.) \
+            B_SPLINE_SURFACE_WITH_KNOTS((2,2),(2,2),(0.,1.),(0.,1.),.UNSPECIFIED.) GEOMETRIC_REPRESENTATION_ITEM() \
+            RATIONAL_B_SPLINE_SURFACE(((2.,2.),(2.,2.))) REPRESENTATION_ITEM('') SURFACE() );\n";
+        data += "ENDSEC;\nEND-ISO-10303-21;\n";
+
+        let model = StepModel::read(data.as_bytes()).unwrap();
+        assert_eq!(model.surfaces.len(), 2);
+        assert_eq!(model.curves.len(), 1);
+        assert_eq!(model.surfaces[0].data, patch.data);
+        for i in 0..=10 {
+            for j in 0..=10 {
+                let xi = p2((i as f64)/10., (j as f64)/10.);
+                assert_approx_eq!(RealPoint3d, model.surfaces[0].evaluate(&xi), patch.evaluate(&xi), epsilon = 1E-12);
+                assert_approx_eq!(RealPoint3d, model.surfaces[1].evaluate(&xi), RealPoint3d::point3d(xi.x(), xi.y(), 0.), epsilon = 1E-12);
+            }
+            let x = model.curves[0].evaluate(&p1((i as f64)/10.));
+            assert_approx_eq!(f64, x.dist(&RealPoint3d::point3d(0., 0., 1.)), 1., epsilon = 1E-12);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        match StepModel::read("DATA;\n#1=CARTESIAN_POINT('',(0.,0.,0.))\n#2=CARTESIAN_POINT('',(0.,0.,0.));\nENDSEC;\n".as_bytes()) {
+            Err(StepError::Syntax { line: 3, reason }) => assert_eq!(reason, "expected Semicolon"),
+            e => panic!("{:?}", e)
+        }
+
+        // Invalid splines are skipped.
+        let model = StepModel::read("DATA;\n#1=CARTESIAN_POINT('',(0.,0.,0.));\n\
+            #2=B_SPLINE_CURVE_WITH_KNOTS('',1,(#1,#3),.UNSPECIFIED.,.F.,.F.,(2,2),(0.,1.),.UNSPECIFIED.);\nENDSEC;\n".as_bytes()).unwrap();
+        assert!(model.curves.is_empty());
+    }
+}
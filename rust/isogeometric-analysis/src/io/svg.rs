@@ -0,0 +1,342 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::bezier::{BezierCircle, BezierCurve, RatBezierCurve};
+use crate::core::{BoundingBox, HslProvider, Mapping, RealPoint2d, RealRange, p1};
+use std::io::Write;
+
+///
+/// Maximum number of bisections of a parametric interval while flattening.
+///
+const MAX_DEPTH: u32 = 12;
+
+///
+/// Number of intervals a curve is split into before flattening adaptively.
+///
+const INITIAL_INTERVALS: usize = 8;
+
+///
+/// Options of the SVG output.
+/// 
+#[derive(Debug, Clone)]
+pub struct SvgOptions {
+    /// Size of the picture in pixels.
+    pub width: u32,
+    pub height: u32,
+    /// Empty border around the drawing, in pixels.
+    pub margin: f64,
+    /// Width of the curves, in pixels.
+    pub stroke_width: f64,
+    /// Draws the control polygon of each curve with a dashed line.
+    pub control_polygon: bool,
+    /// Draws the control points of each curve.
+    pub control_points: bool,
+    /// Colours each curve with a hue from HslProvider, otherwise curves are black.
+    pub colors: bool,
+    /// Maximum distance between a flattened curve and its polyline, in model units.
+    pub tolerance: f64
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            width: 800,
+            height: 600,
+            margin: 20.,
+            stroke_width: 2.,
+            control_polygon: false,
+            control_points: false,
+            colors: true,
+            tolerance: 1E-3
+        }
+    }
+}
+
+///
+/// Piece of a path: a polyline or a Bezier curve of degree at most 3, both by points.
+/// 
+enum PathSegment {
+    Polyline(Vec<RealPoint2d>),
+    Bezier(Vec<RealPoint2d>)
+}
+
+impl PathSegment {
+    fn points(&self) -> &Vec<RealPoint2d> {
+        match self {
+            PathSegment::Polyline(p) | PathSegment::Bezier(p) => p
+        }
+    }
+}
+
+struct SvgCurve {
+    path: Vec<PathSegment>,
+    control: Vec<RealPoint2d>
+}
+
+///
+/// Writes planar curves to SVG. Bezier curves up to degree 3 are written with native path
+/// commands, any other curve is flattened adaptively. The drawing is fitted into the
+/// picture, with the y axis pointing up.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use isogeometric_analysis::bezier::{BezierCircle, BezierCurveDemo1};
+/// use isogeometric_analysis::io::{SvgOptions, SvgWriter};
+/// let mut svg = SvgWriter::new(SvgOptions { control_polygon: true, ..SvgOptions::default() });
+/// svg.add_bezier(&BezierCurveDemo1::create());
+/// svg.add_circle(&BezierCircle { radius: 1, segments: 4 });
+/// let mut out = Vec::new();
+/// svg.write(&mut out).unwrap();
+/// ```
+/// 
+pub struct SvgWriter {
+    pub options: SvgOptions,
+    curves: Vec<SvgCurve>
+}
+
+impl SvgWriter {
+    pub fn new(options: SvgOptions) -> SvgWriter {
+        SvgWriter {
+            options,
+            curves: Vec::new()
+        }
+    }
+
+    ///
+    /// Adds any curve in the plane, flattened over the range. The control points, if any,
+    /// are drawn according to the options.
+    /// 
+    pub fn add_mapping<M: Mapping<f64, f64, 1, 2>>(&mut self, m: &M, range: &RealRange, control: &[RealPoint2d]) {
+        self.curves.push(SvgCurve {
+            path: vec![PathSegment::Polyline(flatten(m, range, self.options.tolerance))],
+            control: control.to_vec()
+        });
+    }
+
+    ///
+    /// Adds a Bezier curve. Degrees up to 3 map to native path commands.
+    /// 
+    pub fn add_bezier(&mut self, bez: &BezierCurve<2>) {
+        if bez.degree() > 3 {
+            self.add_mapping(bez, &RealRange { a: 0., b: 1. }, &bez.p);
+            return;
+        }
+        self.curves.push(SvgCurve {
+            path: vec![PathSegment::Bezier(bez.p.clone())],
+            control: bez.p.clone()
+        });
+    }
+
+    ///
+    /// Adds a rational Bezier curve, flattened.
+    /// 
+    pub fn add_rat_bezier(&mut self, bez: &RatBezierCurve<2, 3>) {
+        self.add_mapping(bez, &RealRange { a: 0., b: 1. }, &bez.p);
+    }
+
+    ///
+    /// Adds the segments of a circle as a single curve. Returns false when the circle
+    /// cannot be computed.
+    /// 
+    pub fn add_circle(&mut self, circle: &BezierCircle) -> bool {
+        let segments = match circle.compute() {
+            Some(s) => s,
+            None => return false
+        };
+        let range = RealRange { a: 0., b: 1. };
+        let mut curve = SvgCurve { path: Vec::new(), control: Vec::new() };
+        for seg in &segments {
+            curve.path.push(PathSegment::Polyline(flatten(seg, &range, self.options.tolerance)));
+            let skip = if curve.control.is_empty() { 0 } else { 1 };
+            curve.control.extend(seg.p.iter().skip(skip));
+        }
+        self.curves.push(curve);
+        true
+    }
+
+    ///
+    /// Writes the SVG document.
+    /// 
+    pub fn write(&self, w: &mut impl Write) -> std::io::Result<()> {
+        let o = &self.options;
+        let all = self.curves.iter()
+            .flat_map(|c| c.path.iter().flat_map(|s| s.points().iter()).chain(c.control.iter()));
+        let mut bbox = BoundingBox::from_points(all);
+        if bbox.is_empty() {
+            bbox = BoundingBox::from_points(&[RealPoint2d::origin()]);
+        }
+        let (min, max) = (bbox.min, bbox.max);
+        let extent = max - min;
+        let sx = (o.width as f64 - 2.*o.margin)/extent.x();
+        let sy = (o.height as f64 - 2.*o.margin)/extent.y();
+        let s = match (sx.is_finite(), sy.is_finite()) {
+            (true, true) => sx.min(sy),
+            (true, false) => sx,
+            (false, true) => sy,
+            _ => 1.
+        };
+        let map = |p: &RealPoint2d| format!("{} {}", num(o.margin + (p.x() - min.x())*s), num(o.margin + (max.y() - p.y())*s));
+
+        writeln!(w, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(w, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">", o.width, o.height)?;
+        writeln!(w, "<g fill=\"none\" stroke-linejoin=\"round\" stroke-linecap=\"round\">")?;
+        let colors = HslProvider { count: self.curves.len() as u32 };
+        for (i, curve) in self.curves.iter().enumerate() {
+            let color = if o.colors { colors.hex_color_for_index(i as u32) } else { "#000000".to_string() };
+            let mut d = Vec::<String>::new();
+            let mut pen: Option<RealPoint2d> = None;
+            for segment in &curve.path {
+                let p = segment.points();
+                if pen.map(|q| q != p[0]).unwrap_or(true) {
+                    d.push(format!("M {}", map(&p[0])));
+                }
+                let rest = p[1..].iter().map(&map).collect::<Vec<String>>().join(" ");
+                match segment {
+                    PathSegment::Bezier(_) if p.len() == 3 => d.push(format!("Q {}", rest)),
+                    PathSegment::Bezier(_) if p.len() == 4 => d.push(format!("C {}", rest)),
+                    _ if p.len() > 1 => d.push(format!("L {}", rest)),
+                    _ => {}
+                }
+                pen = p.last().cloned();
+            }
+            writeln!(w, "<path d=\"{}\" stroke=\"{}\" stroke-width=\"{}\"/>", d.join(" "), color, num(o.stroke_width))?;
+            if o.control_polygon && curve.control.len() > 1 {
+                let points = curve.control.iter().map(|p| map(p).replace(' ', ",")).collect::<Vec<String>>().join(" ");
+                writeln!(w, "<polyline points=\"{}\" stroke=\"{}\" stroke-opacity=\"0.5\" stroke-dasharray=\"6 4\" stroke-width=\"{}\"/>",
+                    points, color, num(o.stroke_width/2.))?;
+            }
+            if o.control_points {
+                for p in &curve.control {
+                    let c = map(p);
+                    let mut xy = c.split(' ');
+                    writeln!(w, "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>",
+                        xy.next().unwrap(), xy.next().unwrap(), num(1.5*o.stroke_width), color)?;
+                }
+            }
+        }
+        writeln!(w, "</g>")?;
+        writeln!(w, "</svg>")
+    }
+}
+
+///
+/// Formats a coordinate with three decimals at most.
+/// 
+fn num(v: f64) -> String {
+    let r = (v*1000.).round()/1000.;
+    format!("{}", if r == 0. { 0. } else { r })
+}
+
+///
+/// Distance of p from the segment ab.
+/// 
+fn dist_from_segment(p: &RealPoint2d, a: &RealPoint2d, b: &RealPoint2d) -> f64 {
+    let ab = *b - *a;
+    let len2 = ab.x()*ab.x() + ab.y()*ab.y();
+    if len2 == 0. {
+        return p.dist(a);
+    }
+    let ap = *p - *a;
+    let t = ((ap.x()*ab.x() + ap.y()*ab.y())/len2).clamp(0., 1.);
+    p.dist(&(*a + ab*t))
+}
+
+///
+/// Approximates a curve with a polyline by bisecting the parametric range until the
+/// midpoint of each interval is within the tolerance from the chord.
+/// 
+pub(crate) fn flatten<M: Mapping<f64, f64, 1, 2>>(m: &M, range: &RealRange, tolerance: f64) -> Vec<RealPoint2d> {
+    fn bisect<M: Mapping<f64, f64, 1, 2>>(m: &M, a: (f64, RealPoint2d), b: (f64, RealPoint2d), tolerance: f64,
+                                          depth: u32, out: &mut Vec<RealPoint2d>) {
+        let t = 0.5*(a.0 + b.0);
+        let mid = (t, m.evaluate(&p1(t)));
+        if depth < MAX_DEPTH && dist_from_segment(&mid.1, &a.1, &b.1) > tolerance {
+            bisect(m, a, mid, tolerance, depth + 1, out);
+            bisect(m, mid, b, tolerance, depth + 1, out);
+        }
+        else {
+            out.push(b.1);
+        }
+    }
+
+    let mut out = vec![m.evaluate(&p1(range.a))];
+    let step = (range.b - range.a)/(INITIAL_INTERVALS as f64);
+    let mut a = (range.a, out[0]);
+    for k in 1..=INITIAL_INTERVALS {
+        let t = if k == INITIAL_INTERVALS { range.b } else { range.a + step*(k as f64) };
+        let b = (t, m.evaluate(&p1(t)));
+        bisect(m, a, b, tolerance, 0, &mut out);
+        a = b;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bezier::{BezierCircle, BezierCurve, BezierCurveDemo1};
+    use crate::core::{RealPoint2d, RealRange, p2};
+    use crate::io::{SvgOptions, SvgWriter};
+    use crate::io::svg::{flatten, dist_from_segment};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_flatten() {
+        let circle = BezierCircle { radius: 3, segments: 3 }.compute().unwrap();
+        let points = flatten(&circle[0], &RealRange { a: 0., b: 1. }, 1E-4);
+        assert_approx_eq!(RealPoint2d, points[0], circle[0].p[0]);
+        assert_approx_eq!(RealPoint2d, *points.last().unwrap(), circle[0].p[2]);
+        for w in points.windows(2) {
+            // The sagitta of a chord of the circle.
+            let m = (w[0] + w[1])*0.5;
+            assert!(3. - m.dist(&RealPoint2d::origin()) < 1E-4);
+        }
+        let line = BezierCurve::create(vec![p2(0., 0.), p2(1., 1.), p2(2., 2.)]);
+        assert_eq!(flatten(&line, &RealRange { a: 0., b: 1. }, 1E-6).len(), 9);
+        assert_approx_eq!(f64, dist_from_segment(&p2(2., 1.), &p2(0., 0.), &p2(1., 0.)), 2f64.sqrt());
+    }
+
+    #[test]
+    fn test_write() {
+        let mut svg = SvgWriter::new(SvgOptions {
+            width: 120,
+            height: 120,
+            margin: 10.,
+            control_points: true,
+            control_polygon: true,
+            ..SvgOptions::default()
+        });
+        svg.add_bezier(&BezierCurve::create(vec![p2(0., 0.), p2(0., 1.), p2(1., 1.), p2(1., 0.)]));
+        svg.add_bezier(&BezierCurveDemo1::create());
+        assert!(svg.add_circle(&BezierCircle { radius: 1, segments: 4 }));
+        assert!(!svg.add_circle(&BezierCircle { radius: 1, segments: 1 }));
+        let mut out = Vec::new();
+        svg.write(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("<path d=\"M 35 47.5 C 35 22.5 60 22.5 60 47.5\" stroke=\"#ff0000\" stroke-width=\"2\"/>"));
+        assert_eq!(text.matches("<path").count(), 3);
+        assert_eq!(text.matches("<polyline").count(), 3);
+        assert_eq!(text.matches("<circle").count(), 4 + 6 + 9);
+        assert!(text.contains("stroke=\"#00ff00\""));
+        assert!(text.ends_with("</g>\n</svg>\n"));
+    }
+}
@@ -0,0 +1,463 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::core::{RealPoint, RealPoint3d};
+use crate::core::RealRange;
+use crate::core::Mapping;
+use std::io::{Result, Write};
+
+///
+/// VTK cell types used to represent sampled geometries.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VtkCellType {
+    PolyLine = 4,
+    Triangle = 5,
+    Quad = 9,
+    Hexahedron = 12
+}
+
+///
+/// Cells used to tessellate a sampled surface.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VtkSurfaceCells {
+    Quads,
+    Triangles
+}
+
+///
+/// Named array of values attached to the points of a grid. Values are stored
+/// point by point, each point having `components` values.
+/// 
+#[derive(Debug, Clone)]
+pub struct VtkDataArray {
+    pub name: String,
+    pub components: usize,
+    pub values: Vec<f64>
+}
+
+///
+/// Unstructured grid obtained by sampling a mapping from ℝ^DIM to ℝ^S, with S <= 3.
+/// The grid keeps the parametric coordinates of each sample, so that fields
+/// can be computed on them after sampling. The grid can be written in the legacy
+/// VTK format or in the XML VTU format, both readable by ParaView.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use isogeometric_analysis::bezier::BezierTeapot;
+/// use isogeometric_analysis::core::RealRange;
+/// use isogeometric_analysis::io::{VtkGrid, VtkSurfaceCells};
+/// let r = RealRange { a: 0f64, b: 1f64 };
+/// let mut grid = VtkGrid::<2>::new();
+/// for patch in BezierTeapot::build_patches() {
+///     grid.append(VtkGrid::<2>::from_surface(&patch, &r, &r, 10, 10, VtkSurfaceCells::Quads));
+/// }
+/// grid.add_scalar_field("height", |_xi, x| x.z());
+/// let mut out = Vec::<u8>::new();
+/// grid.write_vtu(&mut out).unwrap();
+/// ```
+/// 
+#[derive(Debug, Clone)]
+pub struct VtkGrid<const DIM: usize> {
+    pub params: Vec<RealPoint<DIM>>,
+    pub points: Vec<RealPoint3d>,
+    pub cells: Vec<(VtkCellType, Vec<usize>)>,
+    pub point_data: Vec<VtkDataArray>
+}
+
+impl<const DIM: usize> Default for VtkGrid<DIM> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const DIM: usize> VtkGrid<DIM> {
+    ///
+    /// Creates an empty grid.
+    /// 
+    pub fn new() -> VtkGrid<DIM> {
+        VtkGrid {
+            params: Vec::new(),
+            points: Vec::new(),
+            cells: Vec::new(),
+            point_data: Vec::new()
+        }
+    }
+
+    ///
+    /// Adds a scalar field computed by f from the parametric and the physical
+    /// coordinates of each point.
+    /// 
+    pub fn add_scalar_field(&mut self, name: &str, f: impl Fn(&RealPoint<DIM>, &RealPoint3d) -> f64) -> &mut Self {
+        let values = self.params.iter()
+            .zip(self.points.iter())
+            .map(|(xi, x)| f(xi, x))
+            .collect();
+        self.point_data.push(VtkDataArray {
+            name: name.to_string(),
+            components: 1,
+            values
+        });
+        self
+    }
+
+    ///
+    /// Adds a vector field computed by f from the parametric and the physical
+    /// coordinates of each point. Vectors with less than 3 components are padded
+    /// with zeros, as ParaView expects 3D vectors.
+    /// 
+    pub fn add_vector_field<const C: usize>(&mut self, name: &str, f: impl Fn(&RealPoint<DIM>, &RealPoint3d) -> RealPoint<C>) -> &mut Self {
+        if C > 3 {
+            panic!("Vector fields can have at most 3 components");
+        }
+        let mut values = Vec::with_capacity(3*self.points.len());
+        for (xi, x) in self.params.iter().zip(self.points.iter()) {
+            let v = f(xi, x);
+            for i in 0..3 {
+                values.push(v.value(i));
+            }
+        }
+        self.point_data.push(VtkDataArray {
+            name: name.to_string(),
+            components: 3,
+            values
+        });
+        self
+    }
+
+    ///
+    /// Merges another grid into this one. Both grids must carry the same fields
+    /// in the same order. An empty grid accepts any other grid.
+    /// 
+    pub fn append(&mut self, other: VtkGrid<DIM>) -> &mut Self {
+        if self.points.is_empty() && self.point_data.is_empty() {
+            *self = other;
+            return self;
+        }
+        let compatible = self.point_data.len() == other.point_data.len() &&
+            self.point_data.iter().zip(other.point_data.iter())
+                .all(|(a, b)| a.name == b.name && a.components == b.components);
+        if !compatible {
+            panic!("Cannot merge grids with different point data");
+        }
+
+        let offset = self.points.len();
+        self.params.extend(other.params);
+        self.points.extend(other.points);
+        for (t, conn) in other.cells {
+            self.cells.push((t, conn.iter().map(|i| i + offset).collect()));
+        }
+        for (a, b) in self.point_data.iter_mut().zip(other.point_data) {
+            a.values.extend(b.values);
+        }
+        self
+    }
+
+    ///
+    /// Writes the grid in the legacy ASCII VTK format.
+    /// 
+    pub fn write_legacy(&self, w: &mut impl Write, title: &str) -> Result<()> {
+        writeln!(w, "# vtk DataFile Version 3.0")?;
+        writeln!(w, "{}", title.lines().next().unwrap_or(""))?;
+        writeln!(w, "ASCII")?;
+        writeln!(w, "DATASET UNSTRUCTURED_GRID")?;
+        writeln!(w, "POINTS {} double", self.points.len())?;
+        for p in &self.points {
+            writeln!(w, "{} {} {}", p.x(), p.y(), p.z())?;
+        }
+
+        let size: usize = self.cells.iter().map(|(_, conn)| conn.len() + 1).sum();
+        writeln!(w, "CELLS {} {}", self.cells.len(), size)?;
+        for (_, conn) in &self.cells {
+            write!(w, "{}", conn.len())?;
+            for i in conn {
+                write!(w, " {}", i)?;
+            }
+            writeln!(w)?;
+        }
+        writeln!(w, "CELL_TYPES {}", self.cells.len())?;
+        for (t, _) in &self.cells {
+            writeln!(w, "{}", *t as u8)?;
+        }
+
+        if self.point_data.is_empty() {
+            return Ok(());
+        }
+        writeln!(w, "POINT_DATA {}", self.points.len())?;
+        for a in &self.point_data {
+            let name = a.name.replace(' ', "_");
+            if a.components == 1 {
+                writeln!(w, "SCALARS {} double 1", name)?;
+                writeln!(w, "LOOKUP_TABLE default")?;
+            }
+            else {
+                writeln!(w, "VECTORS {} double", name)?;
+            }
+            for v in a.values.chunks(a.components) {
+                writeln!(w, "{}", join(v))?;
+            }
+        }
+        Ok(())
+    }
+
+    ///
+    /// Writes the grid in the XML VTU format with ASCII data arrays.
+    /// 
+    pub fn write_vtu(&self, w: &mut impl Write) -> Result<()> {
+        writeln!(w, "<?xml version=\"1.0\"?>")?;
+        writeln!(w, "<VTKFile type=\"UnstructuredGrid\" version=\"0.1\" byte_order=\"LittleEndian\">")?;
+        writeln!(w, "  <UnstructuredGrid>")?;
+        writeln!(w, "    <Piece NumberOfPoints=\"{}\" NumberOfCells=\"{}\">", self.points.len(), self.cells.len())?;
+
+        let scalars = self.point_data.iter().find(|a| a.components == 1);
+        let vectors = self.point_data.iter().find(|a| a.components != 1);
+        write!(w, "      <PointData")?;
+        if let Some(a) = scalars {
+            write!(w, " Scalars=\"{}\"", escape(&a.name))?;
+        }
+        if let Some(a) = vectors {
+            write!(w, " Vectors=\"{}\"", escape(&a.name))?;
+        }
+        writeln!(w, ">")?;
+        for a in &self.point_data {
+            writeln!(w, "        <DataArray type=\"Float64\" Name=\"{}\" NumberOfComponents=\"{}\" format=\"ascii\">",
+                escape(&a.name), a.components)?;
+            writeln!(w, "          {}", join(&a.values))?;
+            writeln!(w, "        </DataArray>")?;
+        }
+        writeln!(w, "      </PointData>")?;
+
+        writeln!(w, "      <Points>")?;
+        writeln!(w, "        <DataArray type=\"Float64\" NumberOfComponents=\"3\" format=\"ascii\">")?;
+        for p in &self.points {
+            writeln!(w, "          {} {} {}", p.x(), p.y(), p.z())?;
+        }
+        writeln!(w, "        </DataArray>")?;
+        writeln!(w, "      </Points>")?;
+
+        let mut offset = 0usize;
+        let mut connectivity = Vec::<String>::new();
+        let mut offsets = Vec::<String>::new();
+        let mut types = Vec::<String>::new();
+        for (t, conn) in &self.cells {
+            offset += conn.len();
+            connectivity.extend(conn.iter().map(|i| i.to_string()));
+            offsets.push(offset.to_string());
+            types.push((*t as u8).to_string());
+        }
+        writeln!(w, "      <Cells>")?;
+        writeln!(w, "        <DataArray type=\"Int64\" Name=\"connectivity\" format=\"ascii\">")?;
+        writeln!(w, "          {}", connectivity.join(" "))?;
+        writeln!(w, "        </DataArray>")?;
+        writeln!(w, "        <DataArray type=\"Int64\" Name=\"offsets\" format=\"ascii\">")?;
+        writeln!(w, "          {}", offsets.join(" "))?;
+        writeln!(w, "        </DataArray>")?;
+        writeln!(w, "        <DataArray type=\"UInt8\" Name=\"types\" format=\"ascii\">")?;
+        writeln!(w, "          {}", types.join(" "))?;
+        writeln!(w, "        </DataArray>")?;
+        writeln!(w, "      </Cells>")?;
+
+        writeln!(w, "    </Piece>")?;
+        writeln!(w, "  </UnstructuredGrid>")?;
+        writeln!(w, "</VTKFile>")?;
+        Ok(())
+    }
+
+    ///
+    /// Samples a mapping on a structured set of parametric points and stores
+    /// the physical points, padded to 3D.
+    /// 
+    fn sample<const S: usize>(mapping: &impl Mapping<f64, f64, DIM, S>, ranges: &[&RealRange; DIM], counts: &[usize; DIM]) -> VtkGrid<DIM> {
+        if S > 3 {
+            panic!("Only geometries in ℝ, ℝ^2 and ℝ^3 can be written");
+        }
+        let total: usize = counts.iter().product();
+        let mut grid = VtkGrid::<DIM>::new();
+        let mut output = RealPoint::<S>::origin();
+        for k in 0..total {
+            // The first parametric direction is the fastest, as VTK expects.
+            let mut xi = RealPoint::<DIM>::origin();
+            let mut rem = k;
+            for d in 0..DIM {
+                let idx = rem % counts[d];
+                rem /= counts[d];
+                let t = if counts[d] > 1 { (idx as f64)/((counts[d] - 1) as f64) } else { 0f64 };
+                xi.set_value(d, ranges[d].a + t*(ranges[d].b - ranges[d].a));
+            }
+            mapping.evaluate_fill(&xi, &mut output);
+            grid.params.push(xi);
+            grid.points.push(RealPoint3d::point3d(output.value(0), output.value(1), output.value(2)));
+        }
+        grid
+    }
+}
+
+impl VtkGrid<1> {
+    ///
+    /// Samples a curve in `count` points of the range r and builds a polyline.
+    /// 
+    pub fn from_curve<const S: usize>(mapping: &impl Mapping<f64, f64, 1, S>, r: &RealRange, count: usize) -> VtkGrid<1> {
+        let count = count.max(2);
+        let mut grid = Self::sample(mapping, &[r], &[count]);
+        grid.cells.push((VtkCellType::PolyLine, (0..count).collect()));
+        grid
+    }
+}
+
+impl VtkGrid<2> {
+    ///
+    /// Samples a surface on a grid of `count_xi`x`count_eta` points and tessellates
+    /// it with quads or triangles.
+    /// 
+    pub fn from_surface<const S: usize>(mapping: &impl Mapping<f64, f64, 2, S>, r_xi: &RealRange, r_eta: &RealRange,
+                                       count_xi: usize, count_eta: usize, cells: VtkSurfaceCells) -> VtkGrid<2> {
+        let (n, m) = (count_xi.max(2), count_eta.max(2));
+        let mut grid = Self::sample(mapping, &[r_xi, r_eta], &[n, m]);
+        let idx = |i: usize, j: usize| i + j*n;
+        for j in 0..(m - 1) {
+            for i in 0..(n - 1) {
+                let quad = [idx(i, j), idx(i + 1, j), idx(i + 1, j + 1), idx(i, j + 1)];
+                match cells {
+                    VtkSurfaceCells::Quads => grid.cells.push((VtkCellType::Quad, quad.to_vec())),
+                    VtkSurfaceCells::Triangles => {
+                        grid.cells.push((VtkCellType::Triangle, vec![quad[0], quad[1], quad[2]]));
+                        grid.cells.push((VtkCellType::Triangle, vec![quad[0], quad[2], quad[3]]));
+                    }
+                }
+            }
+        }
+        grid
+    }
+}
+
+impl VtkGrid<3> {
+    ///
+    /// Samples a volume on a grid of `counts[0]`x`counts[1]`x`counts[2]` points and
+    /// builds hexahedral cells.
+    /// 
+    pub fn from_volume<const S: usize>(mapping: &impl Mapping<f64, f64, 3, S>, r_xi: &RealRange, r_eta: &RealRange,
+                                      r_zeta: &RealRange, counts: [usize; 3]) -> VtkGrid<3> {
+        let (n, m, l) = (counts[0].max(2), counts[1].max(2), counts[2].max(2));
+        let mut grid = Self::sample(mapping, &[r_xi, r_eta, r_zeta], &[n, m, l]);
+        let idx = |i: usize, j: usize, k: usize| i + j*n + k*n*m;
+        for k in 0..(l - 1) {
+            for j in 0..(m - 1) {
+                for i in 0..(n - 1) {
+                    grid.cells.push((VtkCellType::Hexahedron, vec![
+                        idx(i, j, k), idx(i + 1, j, k), idx(i + 1, j + 1, k), idx(i, j + 1, k),
+                        idx(i, j, k + 1), idx(i + 1, j, k + 1), idx(i + 1, j + 1, k + 1), idx(i, j + 1, k + 1)
+                    ]));
+                }
+            }
+        }
+        grid
+    }
+}
+
+///
+/// Joins values with spaces.
+/// 
+fn join(values: &[f64]) -> String {
+    values.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(" ")
+}
+
+///
+/// Escapes a string to be used as an XML attribute.
+/// 
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('"', "&quot;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{VtkGrid, VtkCellType, VtkSurfaceCells};
+    use crate::bezier::{BezierCurveDemo1, BezierTeapot};
+    use crate::core::{Mapping, RealPoint3d, RealRange, p2};
+
+    struct Cube {}
+
+    impl Mapping<f64, f64, 3, 3> for Cube {
+        fn evaluate_fill<'a>(&self, i: &RealPoint3d, o: &'a mut RealPoint3d) -> &'a mut RealPoint3d {
+            *o = *i*2f64;
+            o
+        }
+    }
+
+    #[test]
+    fn test_curve() {
+        let r = RealRange { a: 0f64, b: 1f64 };
+        let mut grid = VtkGrid::<1>::from_curve(&BezierCurveDemo1::create(), &r, 11);
+        grid.add_scalar_field("xi", |xi, _x| xi.x());
+        grid.add_vector_field("v", |_xi, x| p2(x.y(), -x.x()));
+        assert_eq!(grid.points.len(), 11);
+        assert_eq!(grid.cells.len(), 1);
+        assert_eq!(grid.cells[0].0, VtkCellType::PolyLine);
+        assert_eq!(grid.point_data[1].values.len(), 33);
+
+        let mut out = Vec::<u8>::new();
+        grid.write_legacy(&mut out, "demo").unwrap();
+        let s = String::from_utf8(out).unwrap();
+        assert!(s.starts_with("# vtk DataFile Version 3.0\ndemo\nASCII\nDATASET UNSTRUCTURED_GRID\nPOINTS 11 double\n0 0 0\n"));
+        assert!(s.contains("CELLS 1 12\n11 0 1 2 3 4 5 6 7 8 9 10\n"));
+        assert!(s.contains("CELL_TYPES 1\n4\n"));
+        assert!(s.contains("POINT_DATA 11\nSCALARS xi double 1\nLOOKUP_TABLE default\n0\n0.1\n"));
+        assert!(s.contains("VECTORS v double\n0 -0 0\n"));
+    }
+
+    #[test]
+    fn test_surface() {
+        let r = RealRange { a: 0f64, b: 1f64 };
+        let patches = BezierTeapot::build_patches();
+        let mut grid = VtkGrid::<2>::new();
+        for patch in &patches {
+            grid.append(VtkGrid::<2>::from_surface(patch, &r, &r, 5, 4, VtkSurfaceCells::Triangles));
+        }
+        assert_eq!(grid.points.len(), 32*20);
+        assert_eq!(grid.cells.len(), 32*4*3*2);
+        assert_eq!(*grid.cells.last().unwrap(), (VtkCellType::Triangle, vec![31*20 + 13, 31*20 + 19, 31*20 + 18]));
+
+        let mut quads = VtkGrid::<2>::from_surface(&patches[0], &r, &r, 3, 3, VtkSurfaceCells::Quads);
+        quads.add_scalar_field("jacobian", |_xi, _x| 1f64);
+        let mut out = Vec::<u8>::new();
+        quads.write_vtu(&mut out).unwrap();
+        let s = String::from_utf8(out).unwrap();
+        assert!(s.contains("<Piece NumberOfPoints=\"9\" NumberOfCells=\"4\">"));
+        assert!(s.contains("<PointData Scalars=\"jacobian\">"));
+        assert!(s.contains("<DataArray type=\"Int64\" Name=\"connectivity\" format=\"ascii\">\n          0 1 4 3 1 2 5 4 3 4 7 6 4 5 8 7\n"));
+        assert!(s.contains("<DataArray type=\"Int64\" Name=\"offsets\" format=\"ascii\">\n          4 8 12 16\n"));
+        assert!(s.contains("<DataArray type=\"UInt8\" Name=\"types\" format=\"ascii\">\n          9 9 9 9\n"));
+    }
+
+    #[test]
+    fn test_volume() {
+        let r = RealRange { a: 0f64, b: 1f64 };
+        let grid = VtkGrid::<3>::from_volume(&Cube {}, &r, &r, &r, [2, 3, 2]);
+        assert_eq!(grid.points.len(), 12);
+        assert_eq!(grid.points[11], RealPoint3d::point3d(2f64, 2f64, 2f64));
+        assert_eq!(grid.cells, vec![
+            (VtkCellType::Hexahedron, vec![0, 1, 3, 2, 6, 7, 9, 8]),
+            (VtkCellType::Hexahedron, vec![2, 3, 5, 4, 8, 9, 11, 10])
+        ]);
+    }
+}
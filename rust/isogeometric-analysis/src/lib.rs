@@ -22,3 +22,6 @@
 
 pub mod core;
 pub mod bezier;
+pub mod bspline;
+pub mod nurbs;
+pub mod io;
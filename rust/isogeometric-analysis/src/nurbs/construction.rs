@@ -0,0 +1,389 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::core::{RealPoint3d, LupDecomposition, ParametricCurve};
+use crate::bspline::{BsplineCurve, BsplineSurf, Parametrization, averaged_knots, basis_row};
+use crate::nurbs::{NurbsCurve, NurbsSurf};
+use crate::nurbs::primitives::unit_arc;
+use array2d::Array2D;
+
+impl NurbsSurf<3> {
+    ///
+    /// Revolves the profile by angle radians around the axis through origin with the given
+    /// direction. The profile runs along Xi and the circular arcs, made of up to four
+    /// rational quadratic segments, along Eta (Piegl and Tiller, A8.1).
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use std::f64::consts::PI;
+    /// use isogeometric_analysis::core::{Mapping, p2, p3};
+    /// use isogeometric_analysis::nurbs::{NurbsCurve, NurbsSurf};
+    /// let line = NurbsCurve::create(vec![p3(2., 0., 0.), p3(2., 0., 3.)], vec![1., 1.], vec![0., 0., 1., 1.], 1).unwrap();
+    /// let cylinder = NurbsSurf::revolve(&line, p3(0., 0., 0.), p3(0., 0., 1.), 2.*PI).unwrap();
+    /// let x = cylinder.evaluate(&p2(0.5, 0.3));
+    /// assert!((x.value(0).hypot(x.value(1)) - 2.).abs() < 1E-12);
+    /// ```
+    /// 
+    pub fn revolve(profile: &NurbsCurve<3>, origin: RealPoint3d, axis: RealPoint3d, angle: f64) -> Option<NurbsSurf<3>> {
+        let len = axis.norm();
+        if len == 0f64 || angle <= 0f64 {
+            log::warn!("The axis must not vanish and the angle must be positive");
+            return None;
+        }
+        let axis = axis*(1f64/len);
+        let (arc, arc_weights, knots_eta) = unit_arc(0f64, angle)?;
+        let (rows, cols) = (profile.p.len(), arc.len());
+        let mut data = Array2D::filled_with(RealPoint3d::origin(), rows, cols);
+        let mut weights = Array2D::filled_with(0f64, rows, cols);
+        for (j, (p, w)) in profile.p.iter().zip(profile.weights.iter()).enumerate() {
+            let o = origin + axis*(*p - origin).dot(&axis);
+            let x = *p - o;
+            let r = x.norm();
+            let (ex, ey) = if r > 0f64 { (x*(1f64/r), axis.cross(&(x*(1f64/r)))) } else { (x, x) };
+            for (i, (a, wa)) in arc.iter().zip(arc_weights.iter()).enumerate() {
+                data[(j, i)] = o + ex*(r*a.value(0)) + ey*(r*a.value(1));
+                weights[(j, i)] = w*wa;
+            }
+        }
+        NurbsSurf::create(data, weights, profile.knots.clone(), knots_eta, profile.degree, 2)
+    }
+
+    ///
+    /// Extrudes the curve along the vector direction. The curve runs along Xi and the
+    /// linear extrusion along Eta.
+    /// 
+    pub fn extrude(curve: &NurbsCurve<3>, direction: RealPoint3d) -> NurbsSurf<3> {
+        let columns = vec![curve.p.clone(), curve.p.iter().map(|p| *p + direction).collect()];
+        NurbsSurf {
+            data: Array2D::from_columns(&columns),
+            weights: Array2D::from_columns(&[curve.weights.clone(), curve.weights.clone()]),
+            knots_xi: curve.knots.clone(),
+            knots_eta: vec![0f64, 0f64, 1f64, 1f64],
+            degree_xi: curve.degree,
+            degree_eta: 1
+        }
+    }
+
+    ///
+    /// Returns the ruled surface that joins the two curves linearly along Eta. The curves
+    /// are first made compatible, see NurbsSurf::loft.
+    /// 
+    pub fn ruled(c1: &NurbsCurve<3>, c2: &NurbsCurve<3>) -> Option<NurbsSurf<3>> {
+        let curves = compatible_curves::<3, 4>(&[c1.clone(), c2.clone()])?;
+        let columns = curves.iter().map(|c| c.p.clone()).collect::<Vec<_>>();
+        Some(NurbsSurf::from_homogeneous(&BsplineSurf {
+            data: Array2D::from_columns(&columns),
+            knots_xi: curves[0].knots.clone(),
+            knots_eta: vec![0f64, 0f64, 1f64, 1f64],
+            degree_xi: curves[0].degree,
+            degree_eta: 1
+        }))
+    }
+
+    ///
+    /// Skins the section curves with a surface of degree at most degree along Eta that
+    /// passes through all of them (Piegl and Tiller, 10.3). The sections are reparametrised
+    /// to [0, 1], raised to a common degree and refined to common knots; their parameters
+    /// along Eta average the chosen parametrisation of every column of control points.
+    /// 
+    pub fn loft(sections: &[NurbsCurve<3>], degree: usize, method: Parametrization) -> Option<NurbsSurf<3>> {
+        if sections.len() < 2 {
+            log::warn!("At least two sections are needed");
+            return None;
+        }
+        let curves = compatible_curves::<3, 4>(sections)?;
+        let mut params = vec![0f64; curves.len()];
+        let mut count = 0;
+        for i in 0..curves[0].p.len() {
+            let column = curves.iter().map(|c| c.p[i].to_cartesian::<3>()).collect::<Vec<RealPoint3d>>();
+            if let Some(v) = method.params(&column) {
+                params.iter_mut().zip(v.iter()).for_each(|(a, b)| *a += b);
+                count += 1;
+            }
+        }
+        if count == 0 {
+            params = Parametrization::Uniform.params(&sections.iter().map(|s| s.p[0]).collect::<Vec<_>>())?;
+        }
+        else {
+            params.iter_mut().for_each(|u| *u /= count as f64);
+        }
+        skin(&curves, degree, &params)
+    }
+
+    ///
+    /// Sweeps the profile along the rail. The profile is given in a local frame whose x,
+    /// y and z axes are the normal, the binormal and the tangent of a rotation minimizing
+    /// frame of the rail; it is placed at count points evenly spaced in the parameter of the
+    /// rail and the copies are skinned with a cubic surface. The result approximates the
+    /// exact sweep better as count grows.
+    /// 
+    pub fn sweep(profile: &NurbsCurve<3>, rail: &NurbsCurve<3>, count: usize) -> Option<NurbsSurf<3>> {
+        if count < 2 {
+            log::warn!("At least two sections are needed");
+            return None;
+        }
+        let range = rail.domain();
+        let params = (0..count).map(|k| (k as f64)/((count - 1) as f64)).collect::<Vec<f64>>();
+        let frames = rotation_minimizing_frames(rail, &params.iter().map(|u| range.a + (range.b - range.a)*u).collect::<Vec<f64>>())?;
+        let sections = frames.iter().map(|(o, r, s, t)| NurbsCurve {
+            p: profile.p.iter().map(|p| *o + *r*p.value(0) + *s*p.value(1) + *t*p.value(2)).collect(),
+            ..profile.clone()
+        }).collect::<Vec<NurbsCurve<3>>>();
+        skin(&compatible_curves::<3, 4>(&sections)?, 3, &params)
+    }
+}
+
+///
+/// Interpolates the compatible homogeneous curves at params with a surface of degree at
+/// most degree along Eta.
+/// 
+fn skin(curves: &[BsplineCurve<4>], degree: usize, params: &[f64]) -> Option<NurbsSurf<3>> {
+    skin_homogeneous(curves, degree, params).map(|pw| NurbsSurf::from_homogeneous(&pw))
+}
+
+///
+/// Interpolates the compatible curves at params with a B-spline surface of degree at most
+/// degree along Eta, whose knots are averaged from the params.
+/// 
+pub(crate) fn skin_homogeneous<const H: usize>(curves: &[BsplineCurve<H>], degree: usize, params: &[f64]) -> Option<BsplineSurf<H>> {
+    let n = curves.len();
+    let q = degree.min(n - 1).max(1);
+    let knots_eta = averaged_knots(params, q, n, 1);
+    let rows = params.iter().map(|u| basis_row(&knots_eta, q, n, *u)).collect::<Vec<Vec<f64>>>();
+    let lu = LupDecomposition::new(&Array2D::from_rows(&rows))?;
+    let rows = (0..curves[0].p.len())
+        .map(|i| lu.solve_points(&curves.iter().map(|c| c.p[i]).collect::<Vec<_>>()))
+        .collect::<Vec<_>>();
+    Some(BsplineSurf {
+        data: Array2D::from_rows(&rows),
+        knots_xi: curves[0].knots.clone(),
+        knots_eta,
+        degree_xi: curves[0].degree,
+        degree_eta: q
+    })
+}
+
+///
+/// Returns the homogeneous forms of the curves over [0, 1] with a common degree and common
+/// knots.
+/// 
+pub(crate) fn compatible_curves<const S: usize, const H: usize>(curves: &[NurbsCurve<S>]) -> Option<Vec<BsplineCurve<H>>> {
+    let p = curves.iter().map(|c| c.degree).max()?;
+    let normalized = curves.iter().map(|c| {
+        let mut h = c.to_homogeneous::<H>();
+        let (a, b) = (h.knots[0], h.knots[h.knots.len() - 1]);
+        h.knots.iter_mut().for_each(|k| *k = (*k - a)/(b - a));
+        h.elevate_degree(p - c.degree)
+    }).collect::<Vec<BsplineCurve<H>>>();
+    let inserts = merge_knots(&normalized.iter().map(|c| (&c.knots[..], c.p.len())).collect::<Vec<_>>(), p);
+    let mut result = Vec::new();
+    for (c, xs) in normalized.into_iter().zip(inserts.iter()) {
+        result.push(c.insert_knots(xs)?);
+    }
+    let knots = result[0].knots.clone();
+    result.iter_mut().for_each(|c| c.knots = knots.clone());
+    Some(result)
+}
+
+///
+/// Returns the homogeneous surfaces over [0, 1]² with common degrees and common knots.
+/// 
+pub(crate) fn compatible_surfaces<const H: usize>(surfaces: &[BsplineSurf<H>]) -> Option<Vec<BsplineSurf<H>>> {
+    let p = surfaces.iter().map(|s| s.degree_xi).max()?;
+    let q = surfaces.iter().map(|s| s.degree_eta).max()?;
+    let normalized = surfaces.iter().map(|s| {
+        let mut s = s.clone();
+        for knots in [&mut s.knots_xi, &mut s.knots_eta] {
+            let (a, b) = (knots[0], knots[knots.len() - 1]);
+            knots.iter_mut().for_each(|k| *k = (*k - a)/(b - a));
+        }
+        s.elevate_degree(p - s.degree_xi, q - s.degree_eta)
+    }).collect::<Vec<BsplineSurf<H>>>();
+    let xs = merge_knots(&normalized.iter().map(|s| (&s.knots_xi[..], s.data.num_rows())).collect::<Vec<_>>(), p);
+    let es = merge_knots(&normalized.iter().map(|s| (&s.knots_eta[..], s.data.num_columns())).collect::<Vec<_>>(), q);
+    let mut result = Vec::new();
+    for ((s, x), e) in normalized.into_iter().zip(xs.iter()).zip(es.iter()) {
+        result.push(s.insert_knots(x, e)?);
+    }
+    let (knots_xi, knots_eta) = (result[0].knots_xi.clone(), result[0].knots_eta.clone());
+    result.iter_mut().for_each(|s| {
+        s.knots_xi = knots_xi.clone();
+        s.knots_eta = knots_eta.clone();
+    });
+    Some(result)
+}
+
+///
+/// Returns, for each knot vector of degree p with the given number of control points, the
+/// knots to insert so that all of them get the interior knots of the union, each with its
+/// largest multiplicity. Knots closer than a small tolerance are considered equal.
+/// 
+fn merge_knots(vectors: &[(&[f64], usize)], p: usize) -> Vec<Vec<f64>> {
+    const TOL: f64 = 1E-10;
+    let multiplicity = |knots: &[f64], u: f64| knots.iter().filter(|k| (**k - u).abs() < TOL).count();
+    let mut union = Vec::<(f64, usize)>::new();
+    for (knots, count) in vectors {
+        for u in &knots[p + 1..*count] {
+            let m = multiplicity(knots, *u);
+            match union.iter_mut().find(|(v, _)| (v - u).abs() < TOL) {
+                Some(entry) => entry.1 = entry.1.max(m),
+                None => union.push((*u, m))
+            }
+        }
+    }
+    union.sort_by(|a, b| a.0.total_cmp(&b.0));
+    vectors.iter().map(|(knots, _)| {
+        let mut xs = Vec::new();
+        for (u, m) in &union {
+            let own = knots.iter().find(|k| (**k - u).abs() < TOL).cloned().unwrap_or(*u);
+            xs.extend(vec![own; m - multiplicity(knots, *u)]);
+        }
+        xs
+    }).collect()
+}
+
+///
+/// Returns the origin, normal, binormal and tangent of a rotation minimizing frame of the
+/// rail at the params, computed by double reflection (Wang et al., 2008).
+/// 
+fn rotation_minimizing_frames(rail: &NurbsCurve<3>, params: &[f64]) -> Option<Vec<(RealPoint3d, RealPoint3d, RealPoint3d, RealPoint3d)>> {
+    let unit = |v: RealPoint3d| {
+        let n = v.norm();
+        if n > 0f64 { Some(v*(1f64/n)) } else { None }
+    };
+    let mut frames = Vec::<(RealPoint3d, RealPoint3d, RealPoint3d, RealPoint3d)>::new();
+    for u in params {
+        let d = rail.derivatives(*u, 1);
+        let (x, t) = (d[0], unit(d[1])?);
+        let (r, t) = match frames.last() {
+            None => {
+                // Any normal to the tangent, taken from the axis least aligned with it.
+                let axes = [RealPoint3d::point3d(1., 0., 0.), RealPoint3d::point3d(0., 1., 0.), RealPoint3d::point3d(0., 0., 1.)];
+                let axis = axes.iter().min_by(|a, b| a.dot(&t).abs().total_cmp(&b.dot(&t).abs()))?;
+                (unit(*axis - t*axis.dot(&t))?, t)
+            }
+            Some((x0, r0, _, t0)) => {
+                let reflect = |v: RealPoint3d, w: RealPoint3d| {
+                    let c = w.dot(&w);
+                    if c > 0f64 { v - w*(2f64*w.dot(&v)/c) } else { v }
+                };
+                let v1 = x - *x0;
+                let (rl, tl) = (reflect(*r0, v1), reflect(*t0, v1));
+                (unit(reflect(rl, t - tl))?, t)
+            }
+        };
+        frames.push((x, r, t.cross(&r), t));
+    }
+    Some(frames)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+    use crate::nurbs::{NurbsCurve, NurbsSurf};
+    use crate::bspline::Parametrization;
+    use crate::core::{Mapping, RealPoint3d, p1, p2, p3};
+    use float_cmp::assert_approx_eq;
+
+    fn half_circle() -> NurbsCurve<3> {
+        NurbsCurve::create(
+            vec![p3(0., 0., -1.), p3(1., 0., -1.), p3(1., 0., 0.), p3(1., 0., 1.), p3(0., 0., 1.)],
+            vec![1., 0.5f64.sqrt(), 1., 0.5f64.sqrt(), 1.],
+            vec![0., 0., 0., 0.5, 0.5, 1., 1., 1.], 2
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_revolve() {
+        let origin = p3(1., 2., 3.);
+        let mut profile = half_circle();
+        profile.p.iter_mut().for_each(|p| *p += origin);
+        for (angle, columns) in [(PI/3., 3), (PI, 5), (1.5*PI, 7), (2.*PI, 9)].iter() {
+            let sphere = NurbsSurf::revolve(&profile, origin, p3(0., 0., 2.), *angle).unwrap();
+            assert_eq!(sphere.data.num_columns(), *columns);
+            for i in 0..=10 {
+                for j in 0..=10 {
+                    let x = sphere.evaluate(&p2((i as f64)/10., (j as f64)/10.));
+                    assert_approx_eq!(f64, x.dist(&origin), 1., epsilon = 1E-12);
+                }
+            }
+            let end = sphere.evaluate(&p2(0.5, 1.)) - origin;
+            assert_approx_eq!(RealPoint3d, end, p3(angle.cos(), angle.sin(), 0.), epsilon = 1E-12);
+        }
+    }
+
+    #[test]
+    fn test_ruled_extrude() {
+        let arc = half_circle();
+        let line = NurbsCurve::create(vec![p3(3., 0., -1.), p3(3., 0., 0.), p3(3., 0., 1.)], vec![1., 1., 1.],
+                                      vec![0., 0., 0.5, 1., 1.], 1).unwrap();
+        let ruled = NurbsSurf::ruled(&arc, &line).unwrap();
+        let extruded = NurbsSurf::extrude(&arc, p3(0., 2., 0.));
+        for i in 0..=10 {
+            let u = (i as f64)/10.;
+            assert_approx_eq!(RealPoint3d, ruled.evaluate(&p2(u, 0.)), arc.evaluate(&p1(u)), epsilon = 1E-12);
+            assert_approx_eq!(RealPoint3d, ruled.evaluate(&p2(u, 1.)), line.evaluate(&p1(u)), epsilon = 1E-12);
+            let x = extruded.evaluate(&p2(u, 0.25));
+            assert_approx_eq!(f64, x.value(1), 0.5, epsilon = 1E-12);
+            assert_approx_eq!(f64, x.value(0).hypot(x.value(2)), 1., epsilon = 1E-12);
+        }
+    }
+
+    #[test]
+    fn test_loft_sweep() {
+        // Sections of a cone, one of them with a different parametrisation.
+        let sections = (0..4).map(|k| {
+            let r = 1. + (k as f64);
+            let mut c = half_circle();
+            c.p.iter_mut().for_each(|p| *p = p3(p.value(0)*r, p.value(2)*r, 2.*(k as f64)));
+            if k == 1 {
+                c = c.insert_knots::<4>(&[0.2]).unwrap().elevate_degree::<4>(1);
+            }
+            c
+        }).collect::<Vec<NurbsCurve<3>>>();
+        let loft = NurbsSurf::loft(&sections, 3, Parametrization::ChordLength).unwrap();
+        for i in 0..=10 {
+            for j in 0..=10 {
+                let x = loft.evaluate(&p2((i as f64)/10., (j as f64)/10.));
+                assert_approx_eq!(f64, x.value(0).hypot(x.value(1)), 1. + x.value(2)/2., epsilon = 1E-12);
+            }
+        }
+
+        // A circle swept along a quarter circle gives a quarter of a torus.
+        let circle = NurbsCurve::create(
+            vec![p3(0.5, 0., 0.), p3(0.5, 0.5, 0.), p3(-0.5, 0.5, 0.), p3(-0.5, 0., 0.), p3(-0.5, -0.5, 0.), p3(0.5, -0.5, 0.), p3(0.5, 0., 0.)],
+            vec![1., 0.5, 0.5, 1., 0.5, 0.5, 1.],
+            vec![0., 0., 0., 0.25, 0.5, 0.5, 0.75, 1., 1., 1.], 2
+        ).unwrap();
+        let rail = NurbsCurve::create(vec![p3(3., 0., 0.), p3(3., 3., 0.), p3(0., 3., 0.)], vec![1., 0.5f64.sqrt(), 1.],
+                                      vec![0., 0., 0., 1., 1., 1.], 2).unwrap();
+        let swept = NurbsSurf::sweep(&circle, &rail, 9).unwrap();
+        for i in 0..=10 {
+            for j in 0..=10 {
+                let x = swept.evaluate(&p2((i as f64)/10., (j as f64)/10.));
+                let centre = p3(x.value(0), x.value(1), 0.)*(3./x.value(0).hypot(x.value(1)));
+                assert_approx_eq!(f64, x.dist(&centre), 0.5, epsilon = 1E-3);
+            }
+        }
+    }
+}
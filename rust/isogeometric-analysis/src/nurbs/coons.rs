@@ -0,0 +1,233 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::core::{LupDecomposition, Mapping, RealPoint, p1};
+use crate::bspline::{BsplineSurf, averaged_knots, basis_row};
+use crate::nurbs::{NurbsCurve, NurbsSurf};
+use crate::nurbs::construction::{compatible_curves, compatible_surfaces, skin_homogeneous};
+use array2d::Array2D;
+
+impl<const S: usize> NurbsSurf<S> {
+    ///
+    /// Returns the bilinearly blended Coons patch bounded by the curves xi0 and xi1, running
+    /// along Xi at eta = 0 and eta = 1, and eta0 and eta1, running along Eta at xi = 0 and
+    /// xi = 1. The curves are reparametrised to [0, 1] and made compatible by degree
+    /// elevation and knot merging. The blending works on homogeneous control points, so the
+    /// curves must meet at the corners with the same weights, as they do when the end
+    /// weights are one. Returns None if the corners do not match.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use isogeometric_analysis::core::{Mapping, p2};
+    /// use isogeometric_analysis::nurbs::{NurbsCurve, NurbsSurf};
+    /// let line = |a, b| NurbsCurve::create(vec![a, b], vec![1., 1.], vec![0., 0., 1., 1.], 1).unwrap();
+    /// let coons = NurbsSurf::coons::<3>(&line(p2(0., 0.), p2(2., 0.)), &line(p2(0., 1.), p2(2., 1.)),
+    ///                                   &line(p2(0., 0.), p2(0., 1.)), &line(p2(2., 0.), p2(2., 1.))).unwrap();
+    /// assert!(coons.evaluate(&p2(0.5, 0.5)).dist(&p2(1., 0.5)) < 1E-12);
+    /// ```
+    /// 
+    pub fn coons<const H: usize>(xi0: &NurbsCurve<S>, xi1: &NurbsCurve<S>, eta0: &NurbsCurve<S>, eta1: &NurbsCurve<S>) -> Option<NurbsSurf<S>> {
+        let along_xi = compatible_curves::<S, H>(&[xi0.clone(), xi1.clone()])?;
+        let along_eta = compatible_curves::<S, H>(&[eta0.clone(), eta1.clone()])?;
+        let corners = [
+            (along_xi[0].p[0], along_eta[0].p[0]),
+            (along_xi[0].p[along_xi[0].p.len() - 1], along_eta[1].p[0]),
+            (along_xi[1].p[0], along_eta[0].p[along_eta[0].p.len() - 1]),
+            (along_xi[1].p[along_xi[1].p.len() - 1], along_eta[1].p[along_eta[1].p.len() - 1])
+        ];
+        if corners.iter().any(|(a, b)| !same_point(a, b)) {
+            log::warn!("The boundary curves must meet at the corners with the same weights");
+            return None;
+        }
+        let linear = vec![0f64, 0f64, 1f64, 1f64];
+        let ruled_xi = BsplineSurf {
+            data: Array2D::from_columns(&[along_xi[0].p.clone(), along_xi[1].p.clone()]),
+            knots_xi: along_xi[0].knots.clone(),
+            knots_eta: linear.clone(),
+            degree_xi: along_xi[0].degree,
+            degree_eta: 1
+        };
+        let ruled_eta = BsplineSurf {
+            data: Array2D::from_rows(&[along_eta[0].p.clone(), along_eta[1].p.clone()]),
+            knots_xi: linear.clone(),
+            knots_eta: along_eta[0].knots.clone(),
+            degree_xi: 1,
+            degree_eta: along_eta[0].degree
+        };
+        let bilinear = BsplineSurf {
+            data: Array2D::from_rows(&[vec![corners[0].0, corners[2].0], vec![corners[1].0, corners[3].0]]),
+            knots_xi: linear.clone(),
+            knots_eta: linear,
+            degree_xi: 1,
+            degree_eta: 1
+        };
+        let parts = compatible_surfaces(&[ruled_xi, ruled_eta, bilinear])?;
+        Some(NurbsSurf::from_homogeneous(&combine(&parts)))
+    }
+
+    ///
+    /// Returns the Gordon surface interpolating a network of curves: curves_xi run along Xi
+    /// at the values params_eta of Eta, and curves_eta run along Eta at the values
+    /// params_xi of Xi, all of them reparametrised to [0, 1]. The curves of each family are
+    /// skinned with degree at most degree, and the tensor product interpolant of their
+    /// intersections is subtracted. Returns None if the curves do not intersect at the
+    /// given parameters.
+    /// 
+    pub fn gordon<const H: usize>(curves_xi: &[NurbsCurve<S>], params_eta: &[f64], curves_eta: &[NurbsCurve<S>],
+                                  params_xi: &[f64], degree: usize) -> Option<NurbsSurf<S>> {
+        if curves_xi.len() < 2 || curves_eta.len() < 2 || curves_xi.len() != params_eta.len() || curves_eta.len() != params_xi.len() {
+            log::warn!("Each family needs at least two curves, each with its parameter");
+            return None;
+        }
+        let along_xi = compatible_curves::<S, H>(curves_xi)?;
+        let along_eta = compatible_curves::<S, H>(curves_eta)?;
+
+        // Intersections of the network, indexed by the curve along Eta and then along Xi.
+        let mut grid = Array2D::filled_with(RealPoint::<H>::origin(), along_eta.len(), along_xi.len());
+        for (j, (d, u)) in along_eta.iter().zip(params_xi.iter()).enumerate() {
+            for (i, (c, v)) in along_xi.iter().zip(params_eta.iter()).enumerate() {
+                let (a, b) = (c.evaluate(&p1(*u)), d.evaluate(&p1(*v)));
+                if !same_point(&a, &b) {
+                    log::warn!("The curves of the network do not intersect at the given parameters");
+                    return None;
+                }
+                grid[(j, i)] = a;
+            }
+        }
+
+        let skin_xi = skin_homogeneous(&along_xi, degree, params_eta)?;
+        let skin_eta = transpose(&skin_homogeneous(&along_eta, degree, params_xi)?);
+        let (p, q) = (skin_eta.degree_xi, skin_xi.degree_eta);
+        let solve = |params: &[f64], knots: &[f64], p: usize| {
+            let n = params.len();
+            let rows = params.iter().map(|u| basis_row(knots, p, n, *u)).collect::<Vec<Vec<f64>>>();
+            LupDecomposition::new(&Array2D::from_rows(&rows))
+        };
+        let (knots_xi, knots_eta) = (averaged_knots(params_xi, p, params_xi.len(), 1), averaged_knots(params_eta, q, params_eta.len(), 1));
+        let (lu_xi, lu_eta) = (solve(params_xi, &knots_xi, p)?, solve(params_eta, &knots_eta, q)?);
+        let columns = grid.as_columns().iter().map(|c| lu_xi.solve_points(c)).collect::<Vec<_>>();
+        let rows = Array2D::from_columns(&columns).as_rows().iter().map(|r| lu_eta.solve_points(r)).collect::<Vec<_>>();
+        let tensor = BsplineSurf { data: Array2D::from_rows(&rows), knots_xi, knots_eta, degree_xi: p, degree_eta: q };
+
+        let parts = compatible_surfaces(&[skin_xi, skin_eta, tensor])?;
+        Some(NurbsSurf::from_homogeneous(&combine(&parts)))
+    }
+}
+
+///
+/// Returns the Boolean sum a + b - c of compatible surfaces.
+/// 
+fn combine<const H: usize>(parts: &[BsplineSurf<H>]) -> BsplineSurf<H> {
+    let mut sum = parts[0].clone();
+    for r in 0..sum.data.num_rows() {
+        for c in 0..sum.data.num_columns() {
+            sum.data[(r, c)] += parts[1].data[(r, c)] - parts[2].data[(r, c)];
+        }
+    }
+    sum
+}
+
+///
+/// Swaps the directions of a surface.
+/// 
+fn transpose<const H: usize>(s: &BsplineSurf<H>) -> BsplineSurf<H> {
+    BsplineSurf {
+        data: Array2D::from_rows(&s.data.as_columns()),
+        knots_xi: s.knots_eta.clone(),
+        knots_eta: s.knots_xi.clone(),
+        degree_xi: s.degree_eta,
+        degree_eta: s.degree_xi
+    }
+}
+
+///
+/// Returns true if the points coincide up to a tolerance relative to their size.
+/// 
+fn same_point<const H: usize>(a: &RealPoint<H>, b: &RealPoint<H>) -> bool {
+    a.dist(b) <= 1E-9*(1f64 + a.norm())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nurbs::{NurbsCurve, NurbsSurf};
+    use crate::bezier::{BezierCurve, BezierTeapot};
+    use crate::core::{Mapping, RealPoint2d, RealPoint3d, p1, p2};
+    use float_cmp::assert_approx_eq;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_coons() {
+        // A quarter annulus is reproduced exactly.
+        let inner = NurbsCurve::arc(p2(0., 0.), 1., 0., FRAC_PI_2).unwrap();
+        let outer = NurbsCurve::arc(p2(0., 0.), 2., 0., FRAC_PI_2).unwrap();
+        let line = |a, b| NurbsCurve::create(vec![a, b], vec![1., 1.], vec![0., 0., 1., 1.], 1).unwrap();
+        let (right, top) = (line(p2(1., 0.), p2(2., 0.)), line(p2(0., 1.), p2(0., 2.)));
+        let annulus = NurbsSurf::coons::<3>(&inner, &outer, &right, &top).unwrap();
+        for i in 0..=10 {
+            for j in 0..=10 {
+                let (u, v) = ((i as f64)/10., (j as f64)/10.);
+                let x = annulus.evaluate(&p2(u, v));
+                assert_approx_eq!(f64, x.norm(), 1. + v, epsilon = 1E-12);
+            }
+        }
+
+        // Boundaries of different degrees and knots are interpolated.
+        let wavy = NurbsCurve::create(vec![p2(1., 0.), p2(1.2, 0.3), p2(0.8, 0.6), p2(1.1, 1.)], vec![1., 1., 1., 1.],
+                                      vec![0., 0., 0., 0.5, 2., 2., 2.], 2).unwrap();
+        let bottom = line(p2(0., 0.), p2(1., 0.));
+        let top = NurbsCurve::create(vec![p2(0., 1.), p2(0.5, 1.4), p2(1.1, 1.)], vec![1., 1., 1.], vec![0., 0., 0., 1., 1., 1.], 2).unwrap();
+        let left = line(p2(0., 0.), p2(0., 1.));
+        let coons = NurbsSurf::coons::<3>(&bottom, &top, &left, &wavy).unwrap();
+        for i in 0..=10 {
+            let t = (i as f64)/10.;
+            assert_approx_eq!(RealPoint2d, coons.evaluate(&p2(t, 1.)), top.evaluate(&p1(t)), epsilon = 1E-12);
+            assert_approx_eq!(RealPoint2d, coons.evaluate(&p2(1., t)), wavy.evaluate(&p1(2.*t)), epsilon = 1E-12);
+        }
+        assert!(NurbsSurf::coons::<3>(&bottom, &top, &left, &line(p2(1., 0.), p2(1., 2.))).is_none());
+    }
+
+    #[test]
+    fn test_gordon() {
+        // The iso-curves of a bicubic patch give back the patch.
+        let patch = &BezierTeapot::build_patches()[3];
+        let params = [0., 0.3, 0.7, 1.];
+        let rows = patch.data.as_rows();
+        let columns = patch.data.as_columns();
+        let curves_xi = params.iter().map(|v| {
+            let points = rows.iter().map(|r| BezierCurve::create(r.clone()).evaluate(&p1(*v))).collect::<Vec<_>>();
+            NurbsCurve::from_bezier(&BezierCurve::create(points))
+        }).collect::<Vec<NurbsCurve<3>>>();
+        let curves_eta = params.iter().map(|u| {
+            let points = columns.iter().map(|c| BezierCurve::create(c.clone()).evaluate(&p1(*u))).collect::<Vec<_>>();
+            NurbsCurve::from_bezier(&BezierCurve::create(points))
+        }).collect::<Vec<NurbsCurve<3>>>();
+        let gordon = NurbsSurf::gordon::<4>(&curves_xi, &params, &curves_eta, &params, 3).unwrap();
+        for i in 0..=10 {
+            for j in 0..=10 {
+                let xi = p2((i as f64)/10., (j as f64)/10.);
+                assert_approx_eq!(RealPoint3d, gordon.evaluate(&xi), patch.evaluate(&xi), epsilon = 1E-10);
+            }
+        }
+        assert!(NurbsSurf::gordon::<4>(&curves_xi, &[0., 0.4, 0.7, 1.], &curves_eta, &params, 3).is_none());
+    }
+}
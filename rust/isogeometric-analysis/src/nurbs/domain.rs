@@ -0,0 +1,425 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::core::{LupDecomposition, RealPoint2d, gauss_legendre, p2};
+use crate::bspline::{BsplineBasis, distinct_knots, gram};
+use crate::nurbs::{NurbsCurve, NurbsSurf};
+use array2d::Array2D;
+
+///
+/// How the interior control points of a planar domain are placed.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DomainSmoothing {
+    /// Bilinearly blended Coons patch of the boundaries.
+    Coons,
+    /// Control points minimising the Dirichlet energy ∫|∇x|² over the parametric domain,
+    /// i.e. a discrete harmonic map, with the boundary control points fixed. The energy
+    /// uses the B-spline basis, so it is exact for non-rational boundaries. The result may
+    /// fold on non-convex domains.
+    Harmonic,
+    /// Control points minimising the Winslow functional ∫(|x_ξ|² + |x_η|²)/det J by
+    /// gradient descent, at most iterations steps. It starts from the Coons patch, or from
+    /// the harmonic one if the former folds, and the functional grows without bound where
+    /// det J vanishes, so the result never folds. If both starting points fold, the Coons
+    /// patch is returned.
+    Winslow { iterations: usize }
+}
+
+///
+/// Extreme values of the Jacobian determinant of a planar parametrisation at the Gauss
+/// points of all its elements.
+/// 
+#[derive(Debug, Clone)]
+pub struct JacobianReport {
+    pub min: f64,
+    pub max: f64,
+    /// Parameters where the minimum is attained.
+    pub argmin: RealPoint2d,
+    /// Sign of the determinant at most points: 1 if Xi turns counterclockwise from Eta,
+    /// -1 otherwise.
+    pub orientation: f64,
+    /// Number of points where the determinant vanishes or has the opposite sign to the
+    /// orientation, i.e. where the map folds.
+    pub folded: usize,
+    pub samples: usize
+}
+
+impl JacobianReport {
+    ///
+    /// Returns true if the determinant keeps the same strict sign, so that the
+    /// parametrisation does not fold, at least at the sampled points.
+    /// 
+    pub fn is_regular(&self) -> bool {
+        self.min > 0f64 || self.max < 0f64
+    }
+}
+
+impl NurbsSurf<2> {
+    ///
+    /// Parametrises the planar domain bounded by the curves xi0 and xi1, running along Xi
+    /// at eta = 0 and eta = 1, and eta0 and eta1, running along Eta at xi = 0 and xi = 1.
+    /// The boundaries are made compatible, the interior is initialised with the Coons
+    /// patch and then smoothed as requested. Check the result with jacobian_report
+    /// before using it for analysis.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use isogeometric_analysis::core::p2;
+    /// use isogeometric_analysis::nurbs::{DomainSmoothing, NurbsCurve, NurbsSurf};
+    /// let line = |a, b| NurbsCurve::create(vec![a, b], vec![1., 1.], vec![0., 0., 1., 1.], 1).unwrap();
+    /// let top = NurbsCurve::create(vec![p2(0., 1.), p2(1., 2.), p2(2., 1.)], vec![1., 1., 1.], vec![0., 0., 0., 1., 1., 1.], 2).unwrap();
+    /// let domain = NurbsSurf::from_boundaries(&line(p2(0., 0.), p2(2., 0.)), &top, &line(p2(0., 0.), p2(0., 1.)),
+    ///                                         &line(p2(2., 0.), p2(2., 1.)), DomainSmoothing::Harmonic).unwrap();
+    /// assert!(domain.jacobian_report().is_regular());
+    /// ```
+    /// 
+    pub fn from_boundaries(xi0: &NurbsCurve<2>, xi1: &NurbsCurve<2>, eta0: &NurbsCurve<2>, eta1: &NurbsCurve<2>,
+                           smoothing: DomainSmoothing) -> Option<NurbsSurf<2>> {
+        let mut surf = NurbsSurf::coons::<3>(xi0, xi1, eta0, eta1)?;
+        match smoothing {
+            DomainSmoothing::Coons => {}
+            DomainSmoothing::Harmonic => surf.data = harmonic_points(&surf)?,
+            DomainSmoothing::Winslow { iterations } => {
+                if !surf.jacobian_report().is_regular() {
+                    let harmonic = NurbsSurf { data: harmonic_points(&surf)?, ..surf.clone() };
+                    if !harmonic.jacobian_report().is_regular() {
+                        log::warn!("No unfolded starting point for the Winslow functional");
+                        return Some(surf);
+                    }
+                    surf = harmonic;
+                }
+                surf.data = winslow_points(&surf, iterations);
+            }
+        }
+        Some(surf)
+    }
+
+    ///
+    /// Returns the determinant of the Jacobian matrix of the map at (xi, eta).
+    /// 
+    pub fn jacobian(&self, xi: f64, eta: f64) -> f64 {
+        determinant(&self.data, &rational_ders(self, xi, eta))
+    }
+
+    ///
+    /// Evaluates the Jacobian determinant at the (p + 1)(q + 1) Gauss points of every
+    /// element, the points used by the assembly of an IGA system.
+    /// 
+    pub fn jacobian_report(&self) -> JacobianReport {
+        let dets = gauss_samples(self).iter()
+            .map(|sample| (sample.param, determinant(&self.data, &sample.ders)))
+            .collect::<Vec<(RealPoint2d, f64)>>();
+        let mut report = JacobianReport { min: f64::INFINITY, max: f64::NEG_INFINITY, argmin: p2(0., 0.), orientation: 1f64,
+                                          folded: 0, samples: dets.len() };
+        for (param, det) in &dets {
+            if *det < report.min {
+                report.min = *det;
+                report.argmin = *param;
+            }
+            report.max = report.max.max(*det);
+        }
+        let negative = dets.iter().filter(|(_, det)| *det < 0f64).count();
+        if 2*negative > dets.len() {
+            report.orientation = -1f64;
+        }
+        report.folded = dets.iter().filter(|(_, det)| report.orientation*det <= 0f64).count();
+        report
+    }
+}
+
+///
+/// Returns the control points of the surface whose interior ones minimise the Dirichlet
+/// energy, keeping the boundary ones.
+/// 
+fn harmonic_points(surf: &NurbsSurf<2>) -> Option<Array2D<RealPoint2d>> {
+    let (n, m) = (surf.data.num_rows(), surf.data.num_columns());
+    if n < 3 || m < 3 {
+        return Some(surf.data.clone());
+    }
+    let (p, q) = (surf.degree_xi, surf.degree_eta);
+    let (m0x, m1x) = (gram(&surf.knots_xi, p, n, 0), gram(&surf.knots_xi, p, n, 1));
+    let (m0y, m1y) = (gram(&surf.knots_eta, q, m, 0), gram(&surf.knots_eta, q, m, 1));
+    let stiffness = |i: usize, j: usize, k: usize, l: usize| m1x[i][k]*m0y[j][l] + m0x[i][k]*m1y[j][l];
+
+    // Unknowns are the interior control points, in row-major order.
+    let interior = (1..n - 1).flat_map(|i| (1..m - 1).map(move |j| (i, j))).collect::<Vec<_>>();
+    let mut matrix = Array2D::filled_with(0f64, interior.len(), interior.len());
+    let mut rhs = vec![RealPoint2d::origin(); interior.len()];
+    for (r, (i, j)) in interior.iter().enumerate() {
+        for k in 0..n {
+            for l in 0..m {
+                let kij = stiffness(*i, *j, k, l);
+                if k == 0 || l == 0 || k == n - 1 || l == m - 1 {
+                    rhs[r] -= surf.data[(k, l)]*kij;
+                }
+                else {
+                    matrix[(r, (k - 1)*(m - 2) + l - 1)] = kij;
+                }
+            }
+        }
+    }
+    let solution = LupDecomposition::new(&matrix)?.solve_points(&rhs);
+    let mut data = surf.data.clone();
+    for ((i, j), x) in interior.iter().zip(solution.iter()) {
+        data[(*i, *j)] = *x;
+    }
+    Some(data)
+}
+
+///
+/// Derivatives of the non-vanishing rational basis functions at a quadrature point.
+/// 
+struct GaussSample {
+    param: RealPoint2d,
+    weight: f64,
+    /// Control point indices with the derivatives along Xi and Eta of their function.
+    ders: Vec<((usize, usize), f64, f64)>
+}
+
+///
+/// Returns the control point indices of the rational basis functions not vanishing at
+/// (xi, eta), with the derivatives of each function along Xi and Eta.
+/// 
+fn rational_ders(surf: &NurbsSurf<2>, xi: f64, eta: f64) -> Vec<((usize, usize), f64, f64)> {
+    let (p, q) = (surf.degree_xi, surf.degree_eta);
+    let (n, m) = (surf.data.num_rows(), surf.data.num_columns());
+    let span_xi = BsplineBasis::find_span(&surf.knots_xi, p, n - 1, xi);
+    let span_eta = BsplineBasis::find_span(&surf.knots_eta, q, m - 1, eta);
+    let d_xi = BsplineBasis::ders_nonvanishing(&surf.knots_xi, span_xi, p, xi, 1);
+    let d_eta = BsplineBasis::ders_nonvanishing(&surf.knots_eta, span_eta, q, eta, 1);
+
+    // Weighted basis functions and their sum, with the derivatives: value, d/dxi, d/deta.
+    let mut terms = Vec::new();
+    let mut w = [0f64; 3];
+    for (r, (nx, dnx)) in d_xi[0].iter().zip(d_xi[1].iter()).enumerate() {
+        for (s, (ny, dny)) in d_eta[0].iter().zip(d_eta[1].iter()).enumerate() {
+            let (i, j) = (span_xi - p + r, span_eta - q + s);
+            let wk = surf.weights[(i, j)];
+            let nk = [nx*ny*wk, dnx*ny*wk, nx*dny*wk];
+            w.iter_mut().zip(nk.iter()).for_each(|(a, b)| *a += b);
+            terms.push(((i, j), nk));
+        }
+    }
+    terms.iter().map(|(k, nk)| {
+        (*k, (nk[1]*w[0] - nk[0]*w[1])/(w[0]*w[0]), (nk[2]*w[0] - nk[0]*w[2])/(w[0]*w[0]))
+    }).collect()
+}
+
+///
+/// Returns the derivatives along Xi and Eta of the map with the given control points.
+/// 
+fn tangents(data: &Array2D<RealPoint2d>, ders: &[((usize, usize), f64, f64)]) -> (RealPoint2d, RealPoint2d) {
+    let (mut dx, mut dy) = (RealPoint2d::origin(), RealPoint2d::origin());
+    for (k, rx, ry) in ders {
+        dx += data[*k]*(*rx);
+        dy += data[*k]*(*ry);
+    }
+    (dx, dy)
+}
+
+///
+/// Returns the Jacobian determinant of the map with the given control points.
+/// 
+fn determinant(data: &Array2D<RealPoint2d>, ders: &[((usize, usize), f64, f64)]) -> f64 {
+    let (dx, dy) = tangents(data, ders);
+    dx.value(0)*dy.value(1) - dx.value(1)*dy.value(0)
+}
+
+///
+/// Returns the basis derivatives at the Gauss points of every element.
+/// 
+fn gauss_samples(surf: &NurbsSurf<2>) -> Vec<GaussSample> {
+    let (p, q) = (surf.degree_xi, surf.degree_eta);
+    let (n, m) = (surf.data.num_rows(), surf.data.num_columns());
+    let breaks_xi = distinct_knots(&surf.knots_xi[p..=n]);
+    let breaks_eta = distinct_knots(&surf.knots_eta[q..=m]);
+    let (nodes_xi, weights_xi) = gauss_legendre(p + 1);
+    let (nodes_eta, weights_eta) = gauss_legendre(q + 1);
+    let mut samples = Vec::new();
+    for wx in breaks_xi.windows(2) {
+        for we in breaks_eta.windows(2) {
+            for (gx, ox) in nodes_xi.iter().zip(weights_xi.iter()) {
+                for (ge, oe) in nodes_eta.iter().zip(weights_eta.iter()) {
+                    let xi = 0.5*(wx[0] + wx[1]) + 0.5*(wx[1] - wx[0])*gx;
+                    let eta = 0.5*(we[0] + we[1]) + 0.5*(we[1] - we[0])*ge;
+                    samples.push(GaussSample { param: p2(xi, eta), weight: 0.25*(wx[1] - wx[0])*(we[1] - we[0])*ox*oe,
+                                               ders: rational_ders(surf, xi, eta) });
+                }
+            }
+        }
+    }
+    samples
+}
+
+///
+/// Returns the Winslow functional of the control points and, if requested, its gradient,
+/// or None where the map folds. Orientation is the sign of the Jacobian of the unfolded
+/// map, -1 when Xi turns clockwise from Eta.
+/// 
+fn winslow_energy(data: &Array2D<RealPoint2d>, samples: &[GaussSample], orientation: f64,
+                  mut gradient: Option<&mut Array2D<RealPoint2d>>) -> Option<f64> {
+    let mut energy = 0f64;
+    for sample in samples {
+        let (dx, dy) = tangents(data, &sample.ders);
+        let det = orientation*(dx.value(0)*dy.value(1) - dx.value(1)*dy.value(0));
+        if det <= 0f64 {
+            return None;
+        }
+        let a = dx.dot(&dx) + dy.dot(&dy);
+        energy += sample.weight*a/det;
+        if let Some(g) = gradient.as_mut() {
+            for (k, rx, ry) in &sample.ders {
+                let da = (dx*(*rx) + dy*(*ry))*2f64;
+                let dd = p2(rx*dy.value(1) - ry*dx.value(1), ry*dx.value(0) - rx*dy.value(0))*orientation;
+                g[*k] += (da*det - dd*a)*(sample.weight/(det*det));
+            }
+        }
+    }
+    Some(energy)
+}
+
+///
+/// Minimises the Winslow functional over the interior control points of an unfolded
+/// surface by gradient descent with backtracking.
+/// 
+fn winslow_points(surf: &NurbsSurf<2>, iterations: usize) -> Array2D<RealPoint2d> {
+    let samples = gauss_samples(surf);
+    let orientation = surf.jacobian_report().orientation;
+    let (n, m) = (surf.data.num_rows(), surf.data.num_columns());
+    let mut data = surf.data.clone();
+    let mut step = 1f64;
+    for _ in 0..iterations {
+        let mut gradient = Array2D::filled_with(RealPoint2d::origin(), n, m);
+        let energy = match winslow_energy(&data, &samples, orientation, Some(&mut gradient)) {
+            Some(e) => e,
+            None => break
+        };
+        // Boundary control points stay fixed.
+        let mut norm2 = 0f64;
+        for i in 1..n - 1 {
+            for j in 1..m - 1 {
+                norm2 += gradient[(i, j)].dot(&gradient[(i, j)]);
+            }
+        }
+        if norm2 < 1E-24 {
+            break;
+        }
+        let mut accepted = false;
+        for _ in 0..50 {
+            let mut trial = data.clone();
+            for i in 1..n - 1 {
+                for j in 1..m - 1 {
+                    trial[(i, j)] -= gradient[(i, j)]*step;
+                }
+            }
+            match winslow_energy(&trial, &samples, orientation, None) {
+                Some(e) if e <= energy - 1E-4*step*norm2 => {
+                    data = trial;
+                    accepted = true;
+                    break;
+                }
+                _ => step *= 0.5
+            }
+        }
+        if !accepted {
+            break;
+        }
+        step *= 2f64;
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nurbs::{DomainSmoothing, NurbsCurve, NurbsSurf};
+    use crate::nurbs::domain::{gauss_samples, winslow_energy};
+    use crate::core::{Mapping, RealPoint2d, p1, p2};
+    use float_cmp::assert_approx_eq;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn line(a: RealPoint2d, b: RealPoint2d) -> NurbsCurve<2> {
+        NurbsCurve::create(vec![a, b], vec![1., 1.], vec![0., 0., 1., 1.], 1).unwrap()
+    }
+
+    #[test]
+    fn test_jacobian() {
+        // Quarter annulus, whose Xi turns clockwise from Eta, so the determinant is negative.
+        let inner = NurbsCurve::arc(p2(0., 0.), 1., 0., FRAC_PI_2).unwrap();
+        let outer = NurbsCurve::arc(p2(0., 0.), 2., 0., FRAC_PI_2).unwrap();
+        let annulus = NurbsSurf::from_boundaries(&inner, &outer, &line(p2(1., 0.), p2(2., 0.)), &line(p2(0., 1.), p2(0., 2.)),
+                                                 DomainSmoothing::Coons).unwrap();
+        let report = annulus.jacobian_report();
+        assert!(report.is_regular());
+        assert_eq!(report.orientation, -1.);
+        assert_eq!(report.folded, 0);
+        assert_eq!(report.samples, 3*2);
+        for (xi, eta) in [(0.1, 0.2), (0.5, 0.5), (0.8, 0.9)].iter() {
+            // The arc is not parametrised by angle, so compare with finite differences.
+            let h = 1E-6;
+            let d = |a: f64, b: f64| annulus.evaluate(&p2(a, b));
+            let dx = (d(xi + h, *eta) - d(xi - h, *eta))*(0.5/h);
+            let dy = (d(*xi, eta + h) - d(*xi, eta - h))*(0.5/h);
+            assert_approx_eq!(f64, annulus.jacobian(*xi, *eta), dx.value(0)*dy.value(1) - dx.value(1)*dy.value(0), epsilon = 1E-6);
+        }
+    }
+
+    #[test]
+    fn test_smoothing() {
+        // A rectangle whose left side bulges inwards, making the domain non-convex.
+        let refine = |c: NurbsCurve<2>| c.k_refine::<3>(3 - c.degree, 6);
+        let bulge = NurbsCurve::create(vec![p2(0., 0.), p2(3., 0.5), p2(3., 1.5), p2(0., 2.)], vec![1.; 4],
+                                       vec![0., 0., 0., 0., 1., 1., 1., 1.], 3).unwrap();
+        let (bottom, top) = (refine(line(p2(0., 0.), p2(4., 0.))), refine(line(p2(0., 2.), p2(4., 2.))));
+        let (left, right) = (refine(bulge), refine(line(p2(4., 0.), p2(4., 2.))));
+        let domain = |smoothing| NurbsSurf::from_boundaries(&bottom, &top, &left, &right, smoothing).unwrap();
+
+        // The harmonic map folds, while Winslow lowers its functional and keeps the Coons patch unfolded.
+        let harmonic = domain(DomainSmoothing::Harmonic).jacobian_report();
+        let coons = domain(DomainSmoothing::Coons).jacobian_report();
+        let winslow = domain(DomainSmoothing::Winslow { iterations: 200 });
+        let report = winslow.jacobian_report();
+        assert!(!harmonic.is_regular());
+        assert!(harmonic.folded > 0 && harmonic.folded < harmonic.samples);
+        assert!(coons.is_regular());
+        assert!(report.is_regular());
+        let samples = gauss_samples(&winslow);
+        let energy = |s: &NurbsSurf<2>| winslow_energy(&s.data, &samples, 1., None).unwrap();
+        assert!(energy(&winslow) < energy(&domain(DomainSmoothing::Coons)) - 0.1);
+        for i in 0..=20 {
+            let t = (i as f64)/20.;
+            assert_approx_eq!(f64, winslow.evaluate(&p2(0., t)).dist(&left.evaluate(&p1(t))), 0., epsilon = 1E-12);
+            assert_approx_eq!(f64, winslow.evaluate(&p2(t, 1.)).dist(&top.evaluate(&p1(t))), 0., epsilon = 1E-12);
+        }
+
+        // Swapping the directions reverses the orientation, which Winslow smoothing keeps.
+        let swapped = |smoothing| NurbsSurf::from_boundaries(&left, &right, &bottom, &top, smoothing).unwrap();
+        let (coons, winslow) = (swapped(DomainSmoothing::Coons), swapped(DomainSmoothing::Winslow { iterations: 200 }));
+        let report = winslow.jacobian_report();
+        assert!(coons.jacobian_report().max < 0.);
+        assert!(report.is_regular() && report.max < 0.);
+        let samples = gauss_samples(&winslow);
+        let energy = |s: &NurbsSurf<2>| winslow_energy(&s.data, &samples, -1., None).unwrap();
+        assert!(energy(&winslow) < energy(&coons) - 0.1);
+    }
+}
@@ -0,0 +1,657 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use crate::core::{RealPoint, RealPoint1d, RealPoint2d, RealPoint3d};
+use crate::core::RealRange;
+use crate::core::Mapping;
+use crate::core::{ParametricCurve, ParametricSurface, rational_derivatives, rational_surface_derivatives};
+use crate::bezier::{BezierCurve, BezierSurf, RatBezierCurve};
+use crate::bspline::{BsplineBasis, BsplineCurve, BsplineSurf};
+use crate::bspline::{is_valid_spline, distinct_knots};
+use crate::bspline::{reduce_spline, interior_knots, surface_knots, curve_samples, surface_samples};
+use array2d::Array2D;
+
+///
+/// Implements NURBS curves: B-spline curves whose control points have weights. A NURBS
+/// curve in ℝ^S is the projection of a B-spline curve in homogeneous coordinates.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use isogeometric_analysis::core::{Evaluator, p2};
+/// use isogeometric_analysis::nurbs::NurbsCurve;
+/// let h = 1f64/2f64.sqrt();
+/// let circle = NurbsCurve::create(
+///     vec![p2(1., 0.), p2(1., 1.), p2(0., 1.), p2(-1., 1.), p2(-1., 0.), p2(-1., -1.), p2(0., -1.), p2(1., -1.), p2(1., 0.)],
+///     vec![1., h, 1., h, 1., h, 1., h, 1.],
+///     vec![0., 0., 0., 0.25, 0.25, 0.5, 0.5, 0.75, 0.75, 1., 1., 1.],
+///     2
+/// ).unwrap();
+/// let (xpoints, ypoints) = Evaluator::<1, 2, 100>::evaluate_parametric_range1d(&circle, &0f64, &1f64);
+/// ```
+/// 
+#[derive(Debug, Clone)]
+pub struct NurbsCurve<const S: usize> {
+    pub p: Vec<RealPoint<S>>,
+    pub weights: Vec<f64>,
+    pub knots: Vec<f64>,
+    pub degree: usize
+}
+
+impl<const S: usize> Mapping<f64, f64, 1, S> for NurbsCurve<S> {
+    ///
+    /// Evaluates the NURBS curve in point xi. Point xi exists in the parametric space.
+    ///
+    #[inline(always)]
+    fn evaluate_fill<'a>(&self, xi: &RealPoint1d, output: &'a mut RealPoint<S>) -> &'a mut RealPoint<S> {
+        let n = self.p.len() - 1;
+        let span = BsplineBasis::find_span(&self.knots, self.degree, n, xi.x());
+        let basis = BsplineBasis::nonvanishing(&self.knots, span, self.degree, xi.x());
+        let mut w = 0f64;
+        output.reset();
+        for (k, b) in basis.iter().enumerate() {
+            let i = span - self.degree + k;
+            *output += self.p[i]*(b*self.weights[i]);
+            w += b*self.weights[i];
+        }
+        *output *= 1f64/w;
+        output
+    }
+}
+
+impl<const S: usize> NurbsCurve<S> {
+    ///
+    /// Creates a NURBS curve. There must be a positive weight for each control point.
+    /// 
+    pub fn create(cpoints: Vec<RealPoint<S>>, weights: Vec<f64>, knots: Vec<f64>, degree: usize) -> Option<NurbsCurve<S>> {
+        if cpoints.len() != weights.len() || weights.iter().any(|w| *w <= 0f64) {
+            log::warn!("A positive weight is needed for each control point");
+            return None;
+        }
+        if !is_valid_spline(cpoints.len(), &knots, degree) {
+            return None;
+        }
+        Some(NurbsCurve {
+            p: cpoints,
+            weights,
+            knots,
+            degree
+        })
+    }
+
+    ///
+    /// Builds a NURBS curve with unit weights from a B-spline curve.
+    /// 
+    pub fn from_bspline(bsp: &BsplineCurve<S>) -> NurbsCurve<S> {
+        NurbsCurve {
+            p: bsp.p.clone(),
+            weights: vec![1f64; bsp.p.len()],
+            knots: bsp.knots.clone(),
+            degree: bsp.degree
+        }
+    }
+
+    ///
+    /// Builds a NURBS curve from a Bezier curve.
+    /// 
+    pub fn from_bezier(bez: &BezierCurve<S>) -> NurbsCurve<S> {
+        let degree = bez.degree() as usize;
+        NurbsCurve {
+            p: bez.p.clone(),
+            weights: vec![1f64; bez.p.len()],
+            knots: bezier_knots(degree),
+            degree
+        }
+    }
+
+    ///
+    /// Builds a NURBS curve from a rational Bezier curve.
+    /// 
+    pub fn from_rat_bezier<const H: usize>(bez: &RatBezierCurve<S, H>) -> NurbsCurve<S> {
+        let degree = bez.p.len() - 1;
+        NurbsCurve {
+            p: bez.p.clone(),
+            weights: bez.weights.clone(),
+            knots: bezier_knots(degree),
+            degree
+        }
+    }
+
+    ///
+    /// Returns the B-spline curve in ℝ^H, with H = S + 1, whose control points are the
+    /// weighted control points in homogeneous coordinates.
+    /// 
+    pub fn to_homogeneous<const H: usize>(&self) -> BsplineCurve<H> {
+        BsplineCurve {
+            p: self.p.iter().zip(self.weights.iter()).map(|(p, w)| p.to_homogeneous::<H>(*w)).collect(),
+            knots: self.knots.clone(),
+            degree: self.degree
+        }
+    }
+
+    ///
+    /// Projects a B-spline curve in homogeneous coordinates back to a NURBS curve.
+    /// 
+    pub fn from_homogeneous<const H: usize>(pw: &BsplineCurve<H>) -> NurbsCurve<S> {
+        NurbsCurve {
+            p: pw.p.iter().map(|p| p.to_cartesian::<S>()).collect(),
+            weights: pw.p.iter().map(|p| p.value(H - 1)).collect(),
+            knots: pw.knots.clone(),
+            degree: pw.degree
+        }
+    }
+
+    ///
+    /// Inserts the sorted knots xs without changing the curve, see BsplineCurve::insert_knots.
+    /// 
+    pub fn insert_knots<const H: usize>(&self, xs: &[f64]) -> Option<NurbsCurve<S>> {
+        self.to_homogeneous::<H>().insert_knots(xs).map(|pw| NurbsCurve::from_homogeneous(&pw))
+    }
+
+    ///
+    /// Raises the degree by t without changing the curve.
+    /// 
+    pub fn elevate_degree<const H: usize>(&self, t: usize) -> NurbsCurve<S> {
+        NurbsCurve::from_homogeneous(&self.to_homogeneous::<H>().elevate_degree(t))
+    }
+
+    ///
+    /// Raises the degree by t and then splits every knot span into divisions equal parts.
+    /// 
+    pub fn k_refine<const H: usize>(&self, t: usize, divisions: usize) -> NurbsCurve<S> {
+        NurbsCurve::from_homogeneous(&self.to_homogeneous::<H>().k_refine(t, divisions))
+    }
+
+    ///
+    /// Removes the interior knot u num times, provided that the curve moves by at most tol.
+    /// The bound is enforced on the homogeneous control points, scaled as in Piegl and
+    /// Tiller (5.30).
+    /// 
+    pub fn remove_knot<const H: usize>(&self, u: f64, num: usize, tol: f64) -> Option<NurbsCurve<S>> {
+        let tol = homogeneous_tolerance(self.p.iter(), self.weights.iter(), tol);
+        self.to_homogeneous::<H>().remove_knot(u, num, tol).map(|pw| NurbsCurve::from_homogeneous(&pw))
+    }
+
+    ///
+    /// Removes as many interior knots as possible while the curve moves by at most tol.
+    /// 
+    pub fn reduce<const H: usize>(&self, tol: f64) -> NurbsCurve<S> {
+        let samples = curve_samples(&self.knots[self.degree..=self.p.len()], self.degree);
+        reduce_spline(self.clone(), &samples, tol,
+                      |c| interior_knots(&c.knots, c.degree, c.p.len()).into_iter().map(|u| (0, u)).collect(),
+                      |c, _, u| c.remove_knot::<H>(u, 1, tol))
+    }
+
+    ///
+    /// Returns the rational Bezier segments of the curve, one per non-empty knot span,
+    /// obtained by Bezier extraction of the homogeneous control points.
+    /// 
+    pub fn bezier_segments<const H: usize>(&self) -> Vec<RatBezierCurve<S, H>> {
+        self.to_homogeneous::<H>().bezier_segments().iter().map(|seg| {
+            let weights = seg.p.iter().map(|p| p.value(H - 1)).collect();
+            RatBezierCurve::create(seg.p.iter().map(|p| p.to_cartesian::<S>()).collect(), weights)
+        }).collect()
+    }
+
+    ///
+    /// Returns the parametric domain of the curve.
+    /// 
+    pub fn knot_range(&self) -> RealRange {
+        RealRange {
+            a: self.knots[self.degree],
+            b: self.knots[self.p.len()]
+        }
+    }
+}
+
+impl<const S: usize> ParametricCurve<S> for NurbsCurve<S> {
+    fn domain(&self) -> RealRange {
+        self.knot_range()
+    }
+
+    ///
+    /// Differentiates the weighted numerator and the weight function through the basis
+    /// derivatives and applies the quotient rule.
+    ///
+    fn derivatives(&self, xi: f64, n: usize) -> Vec<RealPoint<S>> {
+        let span = BsplineBasis::find_span(&self.knots, self.degree, self.p.len() - 1, xi);
+        let ders = BsplineBasis::ders_nonvanishing(&self.knots, span, self.degree, xi, n);
+        let mut a = vec![RealPoint::<S>::origin(); n + 1];
+        let mut w = vec![0f64; n + 1];
+        for (k, row) in ders.iter().enumerate() {
+            for (j, b) in row.iter().enumerate() {
+                let i = span - self.degree + j;
+                a[k] += self.p[i]*(b*self.weights[i]);
+                w[k] += b*self.weights[i];
+            }
+        }
+        rational_derivatives(&a, &w)
+    }
+
+    ///
+    /// Returns the distinct knots in the domain.
+    ///
+    fn breakpoints(&self) -> Vec<f64> {
+        distinct_knots(&self.knots[self.degree..=self.p.len()])
+    }
+}
+
+///
+/// Represents a NURBS surface. Control points and weights are stored in matrices whose
+/// rows run along the Xi direction.
+/// 
+#[derive(Debug, Clone)]
+pub struct NurbsSurf<const S: usize> {
+    pub data: Array2D<RealPoint<S>>,
+    pub weights: Array2D<f64>,
+    pub knots_xi: Vec<f64>,
+    pub knots_eta: Vec<f64>,
+    pub degree_xi: usize,
+    pub degree_eta: usize
+}
+
+impl<const S: usize> Mapping<f64, f64, 2, S> for NurbsSurf<S> {
+    ///
+    /// Evaluates the NURBS surface in point (xi, eta) of the parametric space.
+    ///
+    #[inline(always)]
+    fn evaluate_fill<'a>(&self, input: &RealPoint2d, output: &'a mut RealPoint<S>) -> &'a mut RealPoint<S> {
+        let (p, q) = (self.degree_xi, self.degree_eta);
+        let span_xi = BsplineBasis::find_span(&self.knots_xi, p, self.data.num_rows() - 1, input.x());
+        let span_eta = BsplineBasis::find_span(&self.knots_eta, q, self.data.num_columns() - 1, input.y());
+        let n_xi = BsplineBasis::nonvanishing(&self.knots_xi, span_xi, p, input.x());
+        let n_eta = BsplineBasis::nonvanishing(&self.knots_eta, span_eta, q, input.y());
+        let mut w = 0f64;
+        output.reset();
+        for (k, bk) in n_xi.iter().enumerate() {
+            for (l, bl) in n_eta.iter().enumerate() {
+                let idx = (span_xi - p + k, span_eta - q + l);
+                let f = bk*bl*self.weights[idx];
+                *output += self.data[idx]*f;
+                w += f;
+            }
+        }
+        *output *= 1f64/w;
+        output
+    }
+}
+
+impl<const S: usize> NurbsSurf<S> {
+    ///
+    /// Creates a NURBS surface. There must be a positive weight for each control point.
+    /// 
+    pub fn create(data: Array2D<RealPoint<S>>, weights: Array2D<f64>, knots_xi: Vec<f64>, knots_eta: Vec<f64>,
+                  degree_xi: usize, degree_eta: usize) -> Option<NurbsSurf<S>> {
+        if data.num_rows() != weights.num_rows() || data.num_columns() != weights.num_columns() ||
+            weights.elements_row_major_iter().any(|w| *w <= 0f64) {
+            log::warn!("A positive weight is needed for each control point");
+            return None;
+        }
+        if !is_valid_spline(data.num_rows(), &knots_xi, degree_xi) ||
+            !is_valid_spline(data.num_columns(), &knots_eta, degree_eta) {
+            return None;
+        }
+        Some(NurbsSurf { data, weights, knots_xi, knots_eta, degree_xi, degree_eta })
+    }
+
+    ///
+    /// Builds a NURBS surface with unit weights from a B-spline surface.
+    /// 
+    pub fn from_bspline(bsp: &BsplineSurf<S>) -> NurbsSurf<S> {
+        NurbsSurf {
+            data: bsp.data.clone(),
+            weights: Array2D::filled_with(1f64, bsp.data.num_rows(), bsp.data.num_columns()),
+            knots_xi: bsp.knots_xi.clone(),
+            knots_eta: bsp.knots_eta.clone(),
+            degree_xi: bsp.degree_xi,
+            degree_eta: bsp.degree_eta
+        }
+    }
+
+    ///
+    /// Builds a NURBS surface from a Bezier surface.
+    /// 
+    pub fn from_bezier(bez: &BezierSurf<S>) -> NurbsSurf<S> {
+        let (p, q) = (bez.data.num_rows() - 1, bez.data.num_columns() - 1);
+        NurbsSurf {
+            data: bez.data.clone(),
+            weights: Array2D::filled_with(1f64, p + 1, q + 1),
+            knots_xi: bezier_knots(p),
+            knots_eta: bezier_knots(q),
+            degree_xi: p,
+            degree_eta: q
+        }
+    }
+
+    ///
+    /// Returns the B-spline surface in ℝ^H, with H = S + 1, whose control points are
+    /// the weighted control points in homogeneous coordinates.
+    /// 
+    pub fn to_homogeneous<const H: usize>(&self) -> BsplineSurf<H> {
+        let mut data = Vec::new();
+        for (p, w) in self.data.elements_row_major_iter().zip(self.weights.elements_row_major_iter()) {
+            data.push(p.to_homogeneous::<H>(*w));
+        }
+        BsplineSurf {
+            data: Array2D::from_row_major(&data, self.data.num_rows(), self.data.num_columns()),
+            knots_xi: self.knots_xi.clone(),
+            knots_eta: self.knots_eta.clone(),
+            degree_xi: self.degree_xi,
+            degree_eta: self.degree_eta
+        }
+    }
+
+    ///
+    /// Projects a B-spline surface in homogeneous coordinates back to a NURBS surface.
+    /// 
+    pub fn from_homogeneous<const H: usize>(pw: &BsplineSurf<H>) -> NurbsSurf<S> {
+        let (rows, cols) = (pw.data.num_rows(), pw.data.num_columns());
+        let data = pw.data.elements_row_major_iter().map(|p| p.to_cartesian::<S>()).collect::<Vec<RealPoint<S>>>();
+        let weights = pw.data.elements_row_major_iter().map(|p| p.value(H - 1)).collect::<Vec<f64>>();
+        NurbsSurf {
+            data: Array2D::from_row_major(&data, rows, cols),
+            weights: Array2D::from_row_major(&weights, rows, cols),
+            knots_xi: pw.knots_xi.clone(),
+            knots_eta: pw.knots_eta.clone(),
+            degree_xi: pw.degree_xi,
+            degree_eta: pw.degree_eta
+        }
+    }
+
+    ///
+    /// Inserts the sorted knots xs along Xi and es along Eta without changing the surface.
+    /// 
+    pub fn insert_knots<const H: usize>(&self, xs: &[f64], es: &[f64]) -> Option<NurbsSurf<S>> {
+        self.to_homogeneous::<H>().insert_knots(xs, es).map(|pw| NurbsSurf::from_homogeneous(&pw))
+    }
+
+    ///
+    /// Raises the degrees by t_xi and t_eta without changing the surface.
+    /// 
+    pub fn elevate_degree<const H: usize>(&self, t_xi: usize, t_eta: usize) -> NurbsSurf<S> {
+        NurbsSurf::from_homogeneous(&self.to_homogeneous::<H>().elevate_degree(t_xi, t_eta))
+    }
+
+    ///
+    /// Raises the degrees by t_xi and t_eta and then splits every knot span into
+    /// divisions_xi and divisions_eta equal parts.
+    /// 
+    pub fn k_refine<const H: usize>(&self, t_xi: usize, t_eta: usize, divisions_xi: usize, divisions_eta: usize) -> NurbsSurf<S> {
+        NurbsSurf::from_homogeneous(&self.to_homogeneous::<H>().k_refine(t_xi, t_eta, divisions_xi, divisions_eta))
+    }
+
+    ///
+    /// Removes the interior knot u num times along Xi, provided that the surface moves by
+    /// at most tol.
+    /// 
+    pub fn remove_knot_xi<const H: usize>(&self, u: f64, num: usize, tol: f64) -> Option<NurbsSurf<S>> {
+        let tol = homogeneous_tolerance(self.data.elements_row_major_iter(), self.weights.elements_row_major_iter(), tol);
+        self.to_homogeneous::<H>().remove_knot_xi(u, num, tol).map(|pw| NurbsSurf::from_homogeneous(&pw))
+    }
+
+    ///
+    /// Removes the interior knot u num times along Eta, provided that the surface moves by
+    /// at most tol.
+    /// 
+    pub fn remove_knot_eta<const H: usize>(&self, u: f64, num: usize, tol: f64) -> Option<NurbsSurf<S>> {
+        let tol = homogeneous_tolerance(self.data.elements_row_major_iter(), self.weights.elements_row_major_iter(), tol);
+        self.to_homogeneous::<H>().remove_knot_eta(u, num, tol).map(|pw| NurbsSurf::from_homogeneous(&pw))
+    }
+
+    ///
+    /// Removes as many interior knots as possible along both directions while the surface
+    /// moves by at most tol.
+    /// 
+    pub fn reduce<const H: usize>(&self, tol: f64) -> NurbsSurf<S> {
+        let samples = surface_samples(&self.knots_xi[self.degree_xi..=self.data.num_rows()], self.degree_xi,
+                                      &self.knots_eta[self.degree_eta..=self.data.num_columns()], self.degree_eta);
+        reduce_spline(self.clone(), &samples, tol,
+                      |s| surface_knots(&s.knots_xi, s.degree_xi, &s.knots_eta, s.degree_eta), |s, dir, u| {
+            if dir == 0 { s.remove_knot_xi::<H>(u, 1, tol) } else { s.remove_knot_eta::<H>(u, 1, tol) }
+        })
+    }
+
+    ///
+    /// Returns the rational Bezier patches of the surface as single-element NURBS surfaces
+    /// over [0, 1]², one per pair of non-empty knot spans, with the elements along Eta
+    /// varying fastest.
+    /// 
+    pub fn bezier_patches<const H: usize>(&self) -> Vec<NurbsSurf<S>> {
+        let (p, q) = (self.degree_xi, self.degree_eta);
+        self.to_homogeneous::<H>().bezier_patches().into_iter().map(|bez| {
+            NurbsSurf::from_homogeneous(&BsplineSurf { data: bez.data, knots_xi: bezier_knots(p),
+                                                       knots_eta: bezier_knots(q), degree_xi: p, degree_eta: q })
+        }).collect()
+    }
+
+    ///
+    /// Returns the parametric domain along Xi.
+    /// 
+    pub fn knot_range_xi(&self) -> RealRange {
+        RealRange { a: self.knots_xi[self.degree_xi], b: self.knots_xi[self.data.num_rows()] }
+    }
+
+    ///
+    /// Returns the parametric domain along Eta.
+    /// 
+    pub fn knot_range_eta(&self) -> RealRange {
+        RealRange { a: self.knots_eta[self.degree_eta], b: self.knots_eta[self.data.num_columns()] }
+    }
+}
+
+impl ParametricSurface for NurbsSurf<3> {
+    fn domain(&self) -> (RealRange, RealRange) {
+        (self.knot_range_xi(), self.knot_range_eta())
+    }
+
+    ///
+    /// Differentiates the weighted numerator and the weight function and applies the
+    /// quotient rule.
+    ///
+    fn derivatives(&self, xi: f64, eta: f64, n: usize) -> Vec<Vec<RealPoint3d>> {
+        let (p, q) = (self.degree_xi, self.degree_eta);
+        let span_xi = BsplineBasis::find_span(&self.knots_xi, p, self.data.num_rows() - 1, xi);
+        let span_eta = BsplineBasis::find_span(&self.knots_eta, q, self.data.num_columns() - 1, eta);
+        let d_xi = BsplineBasis::ders_nonvanishing(&self.knots_xi, span_xi, p, xi, n);
+        let d_eta = BsplineBasis::ders_nonvanishing(&self.knots_eta, span_eta, q, eta, n);
+        let mut a = vec![vec![RealPoint3d::origin(); n + 1]; n + 1];
+        let mut w = vec![vec![0f64; n + 1]; n + 1];
+        for k in 0..=n {
+            for l in 0..=n - k {
+                for (r, br) in d_xi[k].iter().enumerate() {
+                    for (s, bs) in d_eta[l].iter().enumerate() {
+                        let idx = (span_xi - p + r, span_eta - q + s);
+                        let f = br*bs*self.weights[idx];
+                        a[k][l] += self.data[idx]*f;
+                        w[k][l] += f;
+                    }
+                }
+            }
+        }
+        rational_surface_derivatives(&a, &w)
+    }
+
+    ///
+    /// Returns the distinct knots in the domain along both directions.
+    ///
+    fn breakpoints(&self) -> (Vec<f64>, Vec<f64>) {
+        (distinct_knots(&self.knots_xi[self.degree_xi..=self.data.num_rows()]),
+         distinct_knots(&self.knots_eta[self.degree_eta..=self.data.num_columns()]))
+    }
+}
+
+///
+/// Scales a geometric tolerance to the homogeneous control points, so that moving them by
+/// less than the result moves the rational curve or surface by less than tol.
+/// 
+fn homogeneous_tolerance<'a, const S: usize>(p: impl Iterator<Item = &'a RealPoint<S>>, w: impl Iterator<Item = &'a f64>, tol: f64) -> f64 {
+    let pmax = p.map(|p| p.norm()).fold(0f64, f64::max);
+    let wmin = w.cloned().fold(f64::INFINITY, f64::min);
+    tol*wmin/(1f64 + pmax)
+}
+
+///
+/// Returns the knot vector [0, ..., 0, 1, ..., 1] that turns B-splines of degree p
+/// into Bernstein polynomials.
+/// 
+fn bezier_knots(p: usize) -> Vec<f64> {
+    let mut knots = vec![0f64; p + 1];
+    knots.extend(vec![1f64; p + 1]);
+    knots
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nurbs::{NurbsCurve, NurbsSurf};
+    use crate::bezier::{BezierCircle, BezierTeapot};
+    use crate::core::{Mapping, RealPoint2d, RealPoint3d, p1, p2, p3};
+    use array2d::Array2D;
+    use float_cmp::assert_approx_eq;
+
+    fn circle() -> NurbsCurve<2> {
+        let h = 1f64/2f64.sqrt();
+        NurbsCurve::create(
+            vec![p2(1., 0.), p2(1., 1.), p2(0., 1.), p2(-1., 1.), p2(-1., 0.), p2(-1., -1.), p2(0., -1.), p2(1., -1.), p2(1., 0.)],
+            vec![1., h, 1., h, 1., h, 1., h, 1.],
+            vec![0., 0., 0., 0.25, 0.25, 0.5, 0.5, 0.75, 0.75, 1., 1., 1.],
+            2
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_circle() {
+        let circle = circle();
+        for i in 0..=1000 {
+            let x = circle.evaluate(&p1((i as f64)/1000.));
+            assert_approx_eq!(f64, x.dist(&RealPoint2d::origin()), 1., epsilon = 1E-12);
+        }
+        assert_approx_eq!(RealPoint2d, circle.evaluate(&p1(0.25)), p2(0., 1.), epsilon = 1E-12);
+
+        let homogeneous = circle.to_homogeneous::<3>();
+        let back = NurbsCurve::<2>::from_homogeneous(&homogeneous);
+        for i in 0..circle.p.len() {
+            assert_approx_eq!(RealPoint2d, circle.p[i], back.p[i], epsilon = 1E-12);
+            assert_approx_eq!(f64, circle.weights[i], back.weights[i], epsilon = 1E-12);
+        }
+        assert!(NurbsCurve::create(circle.p.clone(), vec![1.; 8], circle.knots.clone(), 2).is_none());
+    }
+
+    #[test]
+    fn test_bezier() {
+        let arcs = BezierCircle { radius: 2, segments: 3 }.compute().unwrap();
+        for arc in &arcs {
+            let nurbs = NurbsCurve::from_rat_bezier(arc);
+            for i in 0..=100 {
+                let xi = p1((i as f64)/100.);
+                assert_approx_eq!(RealPoint2d, nurbs.evaluate(&xi), arc.evaluate(&xi), epsilon = 1E-12);
+            }
+        }
+
+        for patch in BezierTeapot::build_patches().iter().take(4) {
+            let nurbs = NurbsSurf::from_bezier(patch);
+            for i in 0..=10 {
+                for j in 0..=10 {
+                    let xi = p2((i as f64)/10., (j as f64)/10.);
+                    assert_approx_eq!(RealPoint3d, nurbs.evaluate(&xi), patch.evaluate(&xi), epsilon = 1E-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_extraction() {
+        let circle = circle();
+        let segments = circle.bezier_segments::<3>();
+        assert_eq!(segments.len(), 4);
+        for (e, seg) in segments.iter().enumerate() {
+            for i in 0..=10 {
+                let t = (i as f64)/10.;
+                let x = circle.evaluate(&p1(0.25*(e as f64 + t)));
+                assert_approx_eq!(RealPoint2d, seg.evaluate(&p1(t)), x, epsilon = 1E-12);
+            }
+        }
+
+        // A torus-like surface of revolution with two elements per direction.
+        let nurbs = NurbsSurf::create(
+            Array2D::from_rows(&[circle.p.iter().map(|p| p3(p.value(0), p.value(1), 0.)).collect(),
+                                 circle.p.iter().map(|p| p3(p.value(0), p.value(1), 1.)).collect(),
+                                 circle.p.iter().map(|p| p3(2.*p.value(0), 2.*p.value(1), 2.)).collect()]),
+            Array2D::from_rows(&[circle.weights.clone(), circle.weights.clone(), circle.weights.clone()]),
+            vec![0., 0., 0.5, 1., 1.], circle.knots.clone(), 1, 2
+        ).unwrap();
+        let patches = nurbs.bezier_patches::<4>();
+        assert_eq!(patches.len(), 8);
+        for (k, patch) in patches.iter().enumerate() {
+            let (e, f) = ((k/4) as f64, (k%4) as f64);
+            for i in 0..=4 {
+                for j in 0..=4 {
+                    let (u, v) = ((i as f64)/4., (j as f64)/4.);
+                    let x = nurbs.evaluate(&p2(0.5*(e + u), 0.25*(f + v)));
+                    assert_approx_eq!(RealPoint3d, patch.evaluate(&p2(u, v)), x, epsilon = 1E-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_refinement() {
+        let circle = circle();
+        let refined = circle.insert_knots::<3>(&[0.1, 0.6]).unwrap();
+        let elevated = circle.elevate_degree::<3>(2);
+        let k = circle.k_refine::<3>(1, 3);
+        assert_eq!(elevated.degree, 4);
+        assert_eq!(k.p.len(), 13 + 4*2);
+        for i in 0..=100 {
+            let xi = p1((i as f64)/100.);
+            let x = circle.evaluate(&xi);
+            assert_approx_eq!(RealPoint2d, refined.evaluate(&xi), x, epsilon = 1E-12);
+            assert_approx_eq!(RealPoint2d, elevated.evaluate(&xi), x, epsilon = 1E-12);
+            assert_approx_eq!(RealPoint2d, k.evaluate(&xi), x, epsilon = 1E-12);
+        }
+    }
+
+    #[test]
+    fn test_reduction() {
+        let circle = circle();
+        let refined = circle.insert_knots::<3>(&[0.1, 0.6, 0.6]).unwrap();
+        let removed = refined.remove_knot::<3>(0.6, 2, 1E-12).unwrap().remove_knot::<3>(0.1, 1, 1E-12).unwrap();
+        assert_eq!(removed.knots, circle.knots);
+        let reduced = refined.reduce::<3>(1E-12);
+        assert_eq!(reduced.knots, circle.knots);
+        for (a, b) in reduced.weights.iter().zip(circle.weights.iter()) {
+            assert_approx_eq!(f64, *a, *b, epsilon = 1E-12);
+        }
+
+        // The quarter circles cannot be merged.
+        assert!(circle.remove_knot::<3>(0.5, 1, 1E-3).is_none());
+
+        let surf = NurbsSurf::create(
+            Array2D::from_rows(&[circle.p.clone(), circle.p.iter().map(|p| *p*2.).collect()]),
+            Array2D::from_rows(&[circle.weights.clone(), circle.weights.clone()]),
+            vec![0., 0., 1., 1.], circle.knots.clone(), 1, 2
+        ).unwrap();
+        let refined = surf.insert_knots::<3>(&[0.3, 0.7], &[0.4]).unwrap();
+        let reduced = refined.reduce::<3>(1E-12);
+        assert_eq!(reduced.knots_xi, surf.knots_xi);
+        assert_eq!(reduced.knots_eta, surf.knots_eta);
+    }
+}
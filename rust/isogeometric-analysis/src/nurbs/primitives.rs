@@ -0,0 +1,241 @@
+/*
+ * Project: Approximation and Finite Elements in Isogeometric Problems
+ * Author:  Luca Carlon
+ * Date:    2026.10.18
+ *
+ * Copyright (c) 2026 Luca Carlon. All rights reserved.
+ *
+ * This program is free software; you can redistribute it and/or
+ * modify it under the terms of the GNU Lesser General Public
+ * License as published by the Free Software Foundation; either
+ * version 3 of the License, or (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+ * Lesser General Public License for more details.
+ * 
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with this program; if not, write to the Free Software Foundation,
+ * Inc., 51 Franklin Street, Fifth Floor, Boston, MA  02110-1301, USA.
+ */
+
+use std::f64::consts::{FRAC_PI_2, PI};
+use crate::core::{RealPoint, RealPoint2d, RealPoint3d, p2, p3};
+use crate::nurbs::{NurbsCurve, NurbsSurf};
+use array2d::Array2D;
+
+impl<const S: usize> NurbsCurve<S> {
+    ///
+    /// Returns the arc of the ellipse centre + cos(θ) u + sin(θ) v for θ from start to end,
+    /// made of up to four rational quadratic segments (Piegl and Tiller, A7.1). Circles are
+    /// obtained with orthogonal u and v of the same length.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use std::f64::consts::PI;
+    /// use isogeometric_analysis::core::{Mapping, p1, p3};
+    /// use isogeometric_analysis::nurbs::NurbsCurve;
+    /// let arc = NurbsCurve::elliptic_arc(p3(0., 0., 1.), p3(2., 0., 0.), p3(0., 0., 2.), 0., PI).unwrap();
+    /// assert_eq!(arc.p.len(), 5);
+    /// assert!(arc.evaluate(&p1(0.5)).dist(&p3(0., 0., 3.)) < 1E-12);
+    /// ```
+    /// 
+    pub fn elliptic_arc(centre: RealPoint<S>, u: RealPoint<S>, v: RealPoint<S>, start: f64, end: f64) -> Option<NurbsCurve<S>> {
+        let (points, weights, knots) = unit_arc(start, end - start)?;
+        Some(NurbsCurve {
+            p: points.iter().map(|p| centre + u*p.value(0) + v*p.value(1)).collect(),
+            weights,
+            knots,
+            degree: 2
+        })
+    }
+}
+
+impl NurbsCurve<2> {
+    ///
+    /// Returns the arc of the circle with the given centre and radius between the angles
+    /// start and end, in radians.
+    /// 
+    pub fn arc(centre: RealPoint2d, radius: f64, start: f64, end: f64) -> Option<NurbsCurve<2>> {
+        NurbsCurve::elliptic_arc(centre, p2(radius, 0.), p2(0., radius), start, end)
+    }
+
+    ///
+    /// Returns the full circle with the given centre and radius, starting on the x axis.
+    /// 
+    pub fn circle(centre: RealPoint2d, radius: f64) -> NurbsCurve<2> {
+        NurbsCurve::elliptic_arc(centre, p2(radius, 0.), p2(0., radius), 0., 2f64*PI).unwrap()
+    }
+
+    ///
+    /// Returns the full ellipse with semi-axes a and b, the first one rotated by rotation
+    /// radians from the x axis.
+    /// 
+    pub fn ellipse(centre: RealPoint2d, a: f64, b: f64, rotation: f64) -> NurbsCurve<2> {
+        let (c, s) = (rotation.cos(), rotation.sin());
+        NurbsCurve::elliptic_arc(centre, p2(a*c, a*s), p2(-b*s, b*c), 0., 2f64*PI).unwrap()
+    }
+}
+
+impl NurbsSurf<3> {
+    ///
+    /// Returns the torus around the z axis through centre, with the major circle along Xi
+    /// and the minor one, starting from the bottom, along Eta. With centre in the origin,
+    /// major radius 5 and minor radius 1, the control points and weights are those of the
+    /// NurbsToroid example of the TypeScript implementation, whose knots span [0, 4].
+    /// 
+    pub fn torus(centre: RealPoint3d, major: f64, minor: f64) -> NurbsSurf<3> {
+        let (ring, ring_weights, knots_xi) = unit_arc(0., 2f64*PI).unwrap();
+        let (tube, tube_weights, knots_eta) = unit_arc(-FRAC_PI_2, 2f64*PI).unwrap();
+        let mut data = Array2D::filled_with(RealPoint3d::origin(), ring.len(), tube.len());
+        let mut weights = Array2D::filled_with(0f64, ring.len(), tube.len());
+        for (i, (r, wr)) in ring.iter().zip(ring_weights.iter()).enumerate() {
+            for (j, (t, wt)) in tube.iter().zip(tube_weights.iter()).enumerate() {
+                let rho = major + minor*t.value(0);
+                data[(i, j)] = centre + p3(rho*r.value(0), rho*r.value(1), minor*t.value(1));
+                weights[(i, j)] = wr*wt;
+            }
+        }
+        NurbsSurf { data, weights, knots_xi, knots_eta, degree_xi: 2, degree_eta: 2 }
+    }
+
+    ///
+    /// Returns the sphere with the given centre and radius as the revolution around the z
+    /// axis of a meridian running from the south to the north pole along Xi.
+    /// 
+    pub fn sphere(centre: RealPoint3d, radius: f64) -> NurbsSurf<3> {
+        let meridian = NurbsCurve::elliptic_arc(centre, p3(radius, 0., 0.), p3(0., 0., radius), -FRAC_PI_2, FRAC_PI_2).unwrap();
+        NurbsSurf::revolve(&meridian, centre, p3(0., 0., 1.), 2f64*PI).unwrap()
+    }
+
+    ///
+    /// Returns the lateral surface of the cylinder around the z axis with the given base
+    /// centre, radius and height, with the circle along Xi and the height along Eta.
+    /// 
+    pub fn cylinder(base: RealPoint3d, radius: f64, height: f64) -> NurbsSurf<3> {
+        let circle = NurbsCurve::elliptic_arc(base, p3(radius, 0., 0.), p3(0., radius, 0.), 0., 2f64*PI).unwrap();
+        NurbsSurf::extrude(&circle, p3(0., 0., height))
+    }
+
+    ///
+    /// Returns the quarter of a square plate of side 8 with a hole of radius 1 in its
+    /// centre, as defined in defineNURBSPlateHole.m and refined in
+    /// drawNURBSPlateHoleKnotInsertion.m. The weights are all one, as in the reference,
+    /// so the hole is only approximately circular between its end points.
+    /// 
+    pub fn plate_with_hole() -> NurbsSurf<3> {
+        let h = 2f64.sqrt() - 1f64;
+        let rows = vec![
+            vec![p3(-1., 0., 0.), p3(-2.5, 0., 0.), p3(-4., 0., 0.)],
+            vec![p3(-1., h, 0.), p3(-2.5, 0.75, 0.), p3(-4., 4., 0.)],
+            vec![p3(-h, 1., 0.), p3(-0.75, 2.5, 0.), p3(-4., 4., 0.)],
+            vec![p3(0., 1., 0.), p3(0., 2.5, 0.), p3(0., 4., 0.)]
+        ];
+        NurbsSurf {
+            data: Array2D::from_rows(&rows),
+            weights: Array2D::filled_with(1f64, 4, 3),
+            knots_xi: vec![0., 0., 0., 0.5, 1., 1., 1.],
+            knots_eta: vec![0., 0., 0., 1., 1., 1.],
+            degree_xi: 2,
+            degree_eta: 2
+        }
+    }
+}
+
+///
+/// Returns the control points, the weights and the knots of the arc of the unit circle
+/// from start spanning angle radians, with one segment per quarter turn at most.
+/// 
+pub(crate) fn unit_arc(start: f64, angle: f64) -> Option<(Vec<RealPoint2d>, Vec<f64>, Vec<f64>)> {
+    if angle <= 0f64 {
+        log::warn!("The angle of an arc must be positive");
+        return None;
+    }
+    let angle = angle.min(2f64*PI);
+    let arcs = (angle/FRAC_PI_2 - 1E-12).ceil().max(1f64) as usize;
+    let dtheta = angle/(arcs as f64);
+    let wm = (dtheta/2f64).cos();
+    let mut knots = vec![0f64; 3];
+    for i in 1..arcs {
+        let u = (i as f64)/(arcs as f64);
+        knots.push(u);
+        knots.push(u);
+    }
+    knots.extend(vec![1f64; 3]);
+
+    // Even points are on the circle, odd ones at the intersection of the tangents.
+    let (points, weights) = (0..=2*arcs).map(|i| {
+        let theta = start + dtheta*(i as f64)/2f64;
+        let (r, w) = if i % 2 == 0 { (1f64, 1f64) } else { (1f64/wm, wm) };
+        (p2(r*theta.cos(), r*theta.sin()), w)
+    }).unzip();
+    Some((points, weights, knots))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+    use crate::nurbs::{NurbsCurve, NurbsSurf};
+    use crate::core::{Mapping, RealPoint2d, RealPoint3d, p1, p2, p3};
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_curves() {
+        let circle = NurbsCurve::circle(p2(1., -2.), 2.5);
+        let arc = NurbsCurve::arc(p2(1., -2.), 2.5, 0.5, 2.).unwrap();
+        let ellipse = NurbsCurve::ellipse(p2(0., 0.), 3., 1., PI/6.);
+        assert_eq!(circle.p.len(), 9);
+        assert_eq!(arc.p.len(), 3);
+        assert!(NurbsCurve::arc(p2(0., 0.), 1., 1., 1.).is_none());
+        let (c, s) = ((PI/6.).cos(), (PI/6.).sin());
+        for i in 0..=100 {
+            let xi = p1((i as f64)/100.);
+            assert_approx_eq!(f64, circle.evaluate(&xi).dist(&p2(1., -2.)), 2.5, epsilon = 1E-12);
+            assert_approx_eq!(f64, arc.evaluate(&xi).dist(&p2(1., -2.)), 2.5, epsilon = 1E-12);
+            let x = ellipse.evaluate(&xi);
+            let (a, b) = (x.value(0)*c + x.value(1)*s, -x.value(0)*s + x.value(1)*c);
+            assert_approx_eq!(f64, (a/3.).powi(2) + b.powi(2), 1., epsilon = 1E-12);
+        }
+        assert_approx_eq!(RealPoint2d, arc.evaluate(&p1(1.)), p2(1. + 2.5*2f64.cos(), -2. + 2.5*2f64.sin()), epsilon = 1E-12);
+    }
+
+    #[test]
+    fn test_surfaces() {
+        // Control net of the TypeScript toroid.
+        let torus = NurbsSurf::torus(p3(0., 0., 0.), 5., 1.);
+        let tube = [(5., -1.), (6., -1.), (6., 0.), (6., 1.), (5., 1.), (4., 1.), (4., 0.), (4., -1.), (5., -1.)];
+        let ring = [(1., 0.), (1., 1.), (0., 1.), (-1., 1.), (-1., 0.), (-1., -1.), (0., -1.), (1., -1.), (1., 0.)];
+        let h = 1./2f64.sqrt();
+        let w = [1., h, 1., h, 1., h, 1., h, 1.];
+        for i in 0..9 {
+            for j in 0..9 {
+                let expected = p3(tube[j].0*ring[i].0, tube[j].0*ring[i].1, tube[j].1);
+                assert_approx_eq!(RealPoint3d, torus.data[(i, j)], expected, epsilon = 1E-12);
+                assert_approx_eq!(f64, torus.weights[(i, j)], w[i]*w[j], epsilon = 1E-12);
+            }
+        }
+
+        let centre = p3(1., 2., 3.);
+        let sphere = NurbsSurf::sphere(centre, 2.);
+        let cylinder = NurbsSurf::cylinder(centre, 2., 3.);
+        let torus = NurbsSurf::torus(centre, 3., 1.);
+        for i in 0..=10 {
+            for j in 0..=10 {
+                let xi = p2((i as f64)/10., (j as f64)/10.);
+                assert_approx_eq!(f64, sphere.evaluate(&xi).dist(&centre), 2., epsilon = 1E-12);
+                let x = cylinder.evaluate(&xi) - centre;
+                assert_approx_eq!(f64, x.value(0).hypot(x.value(1)), 2., epsilon = 1E-12);
+                assert_approx_eq!(f64, x.value(2), 3.*(j as f64)/10., epsilon = 1E-12);
+                let x = torus.evaluate(&xi) - centre;
+                assert_approx_eq!(f64, (x.value(0).hypot(x.value(1)) - 3.).hypot(x.value(2)), 1., epsilon = 1E-12);
+            }
+        }
+
+        let plate = NurbsSurf::plate_with_hole();
+        assert_approx_eq!(RealPoint3d, plate.evaluate(&p2(0., 0.)), p3(-1., 0., 0.), epsilon = 1E-12);
+        assert_approx_eq!(RealPoint3d, plate.evaluate(&p2(1., 1.)), p3(0., 4., 0.), epsilon = 1E-12);
+        assert_approx_eq!(RealPoint3d, plate.evaluate(&p2(0.5, 1.)), p3(-4., 4., 0.), epsilon = 1E-12);
+    }
+}